@@ -0,0 +1,94 @@
+//! Measures rounds per second across thread counts and fitness costs.
+//!
+//! Run with `cargo bench`. Each benchmark is followed by the allocations
+//! made in a round, and with `--features contention`, by the time its
+//! threads spent waiting on each of the hive's locks.
+
+extern crate abc;
+#[macro_use]
+extern crate criterion;
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+
+use abc::{HiveBuilder, Objective};
+use abc::allocations::{self, CountingAllocator};
+use abc::vector::BoundedVecContext;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Rounds run for each iteration of a benchmark.
+const ROUNDS: usize = 10;
+
+/// Thread counts to compare.
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+
+/// The sphere function, made to cost about `work` square roots more.
+fn costly_sphere(work: usize) -> impl Fn(&[f64]) -> f64 + Send + Sync {
+    move |x: &[f64]| {
+        black_box((0..work).fold(0f64, |total, i| total + black_box(i as f64).sqrt()));
+        x.iter().map(|xi| xi * xi).sum::<f64>()
+    }
+}
+
+fn rounds(c: &mut Criterion) {
+    for &(name, work) in &[("cheap", 0), ("costly", 20_000)] {
+        let mut group = c.benchmark_group(format!("rounds/{}", name));
+        group.throughput(Throughput::Elements(ROUNDS as u64));
+        for &threads in &THREADS {
+            let context = BoundedVecContext::uniform(10, -5.12, 5.12, costly_sphere(work));
+            let hive = HiveBuilder::new(context, 20)
+                .set_objective(Objective::Minimize)
+                .set_threads(threads)
+                .build()
+                .unwrap();
+            contention::reset();
+            group.bench_with_input(BenchmarkId::from_parameter(threads), &threads, |b, _| {
+                b.iter(|| hive.run_for_rounds(ROUNDS).unwrap())
+            });
+            contention::print(&format!("rounds/{}/{}", name, threads));
+
+            allocations::reset();
+            hive.run_for_rounds(ROUNDS).unwrap();
+            let counted = allocations::report();
+            println!("allocations for rounds/{}/{}: {} per round, {} bytes per round",
+                     name,
+                     threads,
+                     counted.count / ROUNDS as u64,
+                     counted.bytes / ROUNDS as u64);
+        }
+        group.finish();
+    }
+}
+
+#[cfg(feature = "contention")]
+mod contention {
+    pub use abc::contention::reset;
+
+    pub fn print(benchmark: &str) {
+        let waits = abc::contention::report();
+        // Benchmarks that were filtered out take no locks.
+        if waits.is_empty() {
+            return;
+        }
+        println!("lock waits for {}:", benchmark);
+        for wait in waits {
+            println!("  {:<24} {:>10} acquisitions {:>12?} waiting {:>10?} mean {:>10?} longest",
+                     wait.stage.to_string(),
+                     wait.acquisitions,
+                     wait.waiting,
+                     wait.mean(),
+                     wait.longest);
+        }
+    }
+}
+
+#[cfg(not(feature = "contention"))]
+mod contention {
+    pub fn reset() {}
+
+    pub fn print(_: &str) {}
+}
+
+criterion_group!(benches, rounds);
+criterion_main!(benches);
@@ -1,6 +1,8 @@
 extern crate abc;
 extern crate rand;
 
+use std::sync::Arc;
+
 use rand::{Rng, thread_rng};
 
 use abc::{Context, Candidate, HiveBuilder, scaling};
@@ -10,6 +12,7 @@ struct Foo;
 
 impl Context for Foo {
     type Solution = i32;
+    type Fitness = f64;
 
     fn make(&self) -> i32 {
         thread_rng().gen_range(0, 100)
@@ -20,10 +23,10 @@ impl Context for Foo {
         for _ in 0..1_000 {
             x += 1;
         }
-        (x - x) as f64 + *solution as f64
+        (x - 1_000) as f64 + *solution as f64
     }
 
-    fn explore(&self, field: &[Candidate<i32>], n: usize) -> i32 {
+    fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
         field[n].solution + thread_rng().gen_range(-10, 10)
     }
 }
@@ -1,6 +1,8 @@
 extern crate abc;
 extern crate rand;
 
+use std::sync::Arc;
+
 use rand::{Rng, thread_rng};
 
 use abc::{Context, Candidate, HiveBuilder, scaling};
@@ -10,6 +12,7 @@ struct Foo;
 
 impl Context for Foo {
     type Solution = i32;
+    type Fitness = f64;
 
     fn make(&self) -> i32 {
         thread_rng().gen_range(0, 100)
@@ -20,10 +23,10 @@ impl Context for Foo {
         for _ in 0..1_000 {
             x += 1;
         }
-        (x - x) as f64 + *solution as f64
+        (x - 1_000) as f64 + *solution as f64
     }
 
-    fn explore(&self, field: &[Candidate<i32>], n: usize) -> i32 {
+    fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
         field[n].solution + thread_rng().gen_range(-10, 10)
     }
 }
@@ -33,12 +36,13 @@ fn main() {
         .set_threads(5)
         .set_observers(4)
         .set_scaling(scaling::power_rank(10_f64));
-    for candidate in hive.build()
-                         .unwrap()
-                         .stream()
-                         .iter()
-                         .skip_while(|c| c.fitness < 200_f64)
-                         .take(5) {
+    let (handle, receiver) = hive.build().unwrap().stream();
+    for candidate in receiver.iter()
+                             .skip_while(|c| c.fitness < 200_f64)
+                             .take(5) {
         println!("{:?}", candidate);
     }
+    handle.stop().unwrap();
+    println!("stopped at round {}", handle.round().unwrap());
+    println!("{:?}", handle.join().unwrap());
 }
\ No newline at end of file
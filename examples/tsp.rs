@@ -0,0 +1,35 @@
+extern crate abc;
+extern crate rand;
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+use abc::{HiveBuilder, Objective};
+use abc::discrete::PermutationContext;
+
+/// Length of the closed tour visiting `cities` in the given order.
+fn tour_length(cities: &[(f64, f64)], tour: &[usize]) -> f64 {
+    (0..tour.len())
+        .map(|i| {
+            let (x1, y1) = cities[tour[i]];
+            let (x2, y2) = cities[tour[(i + 1) % tour.len()]];
+            ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt()
+        })
+        .sum()
+}
+
+fn main() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let cities = (0..30).map(|_| (rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0))).collect::<Vec<_>>();
+
+    let context = {
+        let cities = cities.clone();
+        PermutationContext::new(cities.len(), move |tour: &[usize]| tour_length(&cities, tour))
+    };
+    let hive = HiveBuilder::new(context, 20)
+        .set_objective(Objective::Minimize)
+        .set_retries(100)
+        .build()
+        .unwrap();
+    let best = hive.run_for_rounds(2_000).unwrap();
+    println!("tour length {:.1}: {:?}", best.raw_fitness, best.solution);
+}
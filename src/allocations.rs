@@ -0,0 +1,94 @@
+//! Counts of heap allocations, for measuring how much the hive allocates.
+//!
+//! The counts are only kept once [`CountingAllocator`](struct.CountingAllocator.html)
+//! is installed as the program's global allocator, which is best done in a
+//! benchmark or test binary. Like the lock waits of the
+//! [`contention`](../contention/index.html) module, they cover the whole
+//! process.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate abc;
+//!
+//! use abc::HiveBuilder;
+//! use abc::allocations::{self, CountingAllocator};
+//! use abc::vector::BoundedVecContext;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! fn main() {
+//!     let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+//!     let hive = HiveBuilder::new(context, 10).set_threads(1).build().unwrap();
+//!     allocations::reset();
+//!     hive.run_for_rounds(20).unwrap();
+//!     let counted = allocations::report();
+//!     assert!(counted.count > 0);
+//!     println!("{} allocations per round", counted.count / 20);
+//! }
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations, and otherwise defers to the
+/// system allocator.
+///
+/// Reallocations count as allocations, since growing a vector is one of the
+/// costs worth counting.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The allocations made since the counts were last reset.
+pub struct Allocations {
+    /// The number of allocations.
+    pub count: u64,
+
+    /// The total size of the allocations, in bytes.
+    pub bytes: u64,
+}
+
+/// Returns the allocations made since the last [`reset`](fn.reset.html).
+/// Both counts are 0 unless the counting allocator is installed.
+pub fn report() -> Allocations {
+    Allocations {
+        count: COUNT.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Clears the counts so far.
+pub fn reset() {
+    COUNT.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+}
@@ -0,0 +1,204 @@
+extern crate crossbeam;
+
+use self::crossbeam::ScopedJoinHandle;
+
+use candidate::Candidate;
+use context::Context;
+use hive::Hive;
+use result::Result as AbcResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+/// Which islands of an [`Archipelago`](struct.Archipelago.html) send
+/// migrants to which.
+pub enum Topology {
+    /// Each island sends its migrants to the next, and the last to the
+    /// first. Good solutions spread slowly, which preserves diversity. This
+    /// is the default.
+    #[default]
+    Ring,
+
+    /// Each island sends its migrants to every other island.
+    FullyConnected,
+}
+
+/// Runs several hives in parallel, with periodic migration between them.
+///
+/// This is the island model: each hive (island) keeps its own population, so
+/// the islands don't contend over shared data, and every so often each
+/// island sends copies of its fittest candidates to its neighbours, where
+/// they replace the least fit candidates.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::{Archipelago, HiveBuilder, Topology};
+/// use abc::vector::BoundedVecContext;
+///
+/// let islands = (0..4).map(|_| {
+///     let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| {
+///         1.0 / (1.0 + x.iter().map(|xi| xi * xi).sum::<f64>())
+///     });
+///     HiveBuilder::new(context, 10).set_threads(1).build().unwrap()
+/// }).collect();
+///
+/// let archipelago = Archipelago::new(islands)
+///     .set_topology(Topology::FullyConnected)
+///     .set_migration_interval(5);
+/// let best = archipelago.run_for_rounds(50).unwrap();
+/// assert!(best.fitness > 0.0);
+/// # }
+/// ```
+pub struct Archipelago<Ctx: Context> {
+    islands: Vec<Hive<Ctx>>,
+    topology: Topology,
+    interval: usize,
+    migrants: usize,
+}
+
+impl<Ctx: Context> Archipelago<Ctx> {
+    /// Groups hives into an archipelago.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no hives.
+    pub fn new(islands: Vec<Hive<Ctx>>) -> Archipelago<Ctx> {
+        if islands.is_empty() {
+            panic!("Archipelago must have at least one island.");
+        }
+
+        Archipelago {
+            islands,
+            topology: Topology::default(),
+            interval: 10,
+            migrants: 1,
+        }
+    }
+
+    /// Sets which islands send migrants to which.
+    pub fn set_topology(mut self, topology: Topology) -> Archipelago<Ctx> {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets the number of rounds the islands run between migrations.
+    ///
+    /// This defaults to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn set_migration_interval(mut self, interval: usize) -> Archipelago<Ctx> {
+        if interval == 0 {
+            panic!("Archipelago migration interval must be at least one round.");
+        }
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the number of candidates each island sends at each migration.
+    ///
+    /// This defaults to 1.
+    pub fn set_migrants(mut self, migrants: usize) -> Archipelago<Ctx> {
+        self.migrants = migrants;
+        self
+    }
+
+    /// Returns the islands, e.g. to inspect their progress.
+    pub fn islands(&self) -> &[Hive<Ctx>] {
+        &self.islands
+    }
+
+    /// Runs each island for a number of rounds, migrating every
+    /// [interval](#method.set_migration_interval), then returns the best
+    /// solution found on any island.
+    ///
+    /// If any of the islands fails, this will return `Err(abc::Error)`
+    /// describing the failure, once the others have finished their rounds.
+    pub fn run_for_rounds(&self, rounds: usize) -> AbcResult<Candidate<Ctx::Solution, Ctx::Fitness>> {
+        let mut remaining = rounds;
+        while remaining > 0 {
+            let epoch = remaining.min(self.interval);
+            self.run_epoch(epoch)?;
+            self.migrate()?;
+            remaining -= epoch;
+        }
+        self.best()
+    }
+
+    /// Returns the best solution found on any island.
+    pub fn best(&self) -> AbcResult<Candidate<Ctx::Solution, Ctx::Fitness>> {
+        let mut best = self.islands[0].best_arc()?;
+        for island in &self.islands[1..] {
+            let candidate = island.best_arc()?;
+            if self.islands[0].fitter(&candidate, &best) {
+                best = candidate;
+            }
+        }
+        Ok((*best).clone())
+    }
+
+    #[allow(clippy::manual_try_fold)]
+    fn run_epoch(&self, rounds: usize) -> AbcResult<()> {
+        crossbeam::scope(|scope| {
+            let handles = self.islands
+                              .iter()
+                              .map(|island| scope.spawn(move || island.run_for_rounds(rounds).map(|_| ())))
+                              .collect::<Vec<ScopedJoinHandle<AbcResult<()>>>>();
+
+            // Join every island before reporting any failure.
+            handles.into_iter()
+                   .fold(Ok(()), |result, handle| result.and(handle.join()))
+        })
+    }
+
+    /// Sends copies of each island's fittest candidates to its neighbours.
+    fn migrate(&self) -> AbcResult<()> {
+        let n = self.islands.len();
+        // Gather all the emigrants first, so that migrants only travel one
+        // step per migration.
+        let emigrants = self.islands
+                            .iter()
+                            .map(|island| island.top_candidates(self.migrants))
+                            .collect::<AbcResult<Vec<_>>>()?;
+
+        for (from, migrants) in emigrants.iter().enumerate() {
+            let destinations = match self.topology {
+                Topology::Ring => vec![(from + 1) % n],
+                Topology::FullyConnected => (0..n).filter(|&to| to != from).collect(),
+            };
+            for to in destinations {
+                if to != from {
+                    self.islands[to].immigrate(migrants)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive::HiveBuilder;
+    use vector::BoundedVecContext;
+
+    #[test]
+    fn migration_spreads_the_best() {
+        let islands = (0..3)
+                          .map(|_| {
+                              let context = BoundedVecContext::uniform(1, 0.0, 10.0, |x: &[f64]| 1.0 + x[0]);
+                              HiveBuilder::new(context, 4).set_threads(1).build().unwrap()
+                          })
+                          .collect();
+        let archipelago = Archipelago::new(islands)
+                              .set_topology(Topology::FullyConnected)
+                              .set_migration_interval(3);
+        let best = archipelago.run_for_rounds(7).unwrap();
+
+        // Each island received the others' best at the last migration.
+        for island in archipelago.islands() {
+            assert_eq!(island.get().unwrap().fitness, best.fitness);
+        }
+    }
+}
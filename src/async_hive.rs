@@ -0,0 +1,494 @@
+//! Runs the ABC algorithm with a context that works asynchronously.
+//!
+//! When fitness is evaluated by a remote service, or anything else that
+//! spends most of its time waiting, dedicating an OS thread to each
+//! evaluation is wasteful. An [`AsyncContext`](trait.AsyncContext.html)
+//! returns futures instead, and an [`AsyncHive`](struct.AsyncHive.html)
+//! keeps many of them in flight at once.
+//!
+//! The hive's own methods return futures too, so it runs on whichever
+//! executor polls them, such as tokio's. Each round is split into three
+//! phases: every worker explores its candidate, then the observers explore
+//! the candidates they choose, then expired candidates are scouted. Within a
+//! phase, up to [`concurrency`](struct.AsyncHiveBuilder.html#method.set_concurrency)
+//! solutions are explored and evaluated at a time.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; extern crate futures; fn main() {
+//! use std::sync::Arc;
+//! use futures::executor::block_on;
+//! use futures::future::{BoxFuture, FutureExt, ready};
+//! use abc::{AsyncContext, AsyncHiveBuilder, Candidate};
+//!
+//! struct Remote;
+//!
+//! impl AsyncContext for Remote {
+//!     type Solution = i32;
+//!
+//!     fn make(&self) -> BoxFuture<'static, i32> {
+//!         ready(10).boxed()
+//!     }
+//!
+//!     fn evaluate_fitness(&self, solution: &i32) -> BoxFuture<'static, f64> {
+//!         // A real context would send the solution off to be scored here.
+//!         ready(1.0 / (1.0 + (*solution as f64).abs())).boxed()
+//!     }
+//!
+//!     fn explore(&self, field: &[Arc<Candidate<i32>>], index: usize) -> BoxFuture<'static, i32> {
+//!         ready(field[index].solution - 1).boxed()
+//!     }
+//! }
+//!
+//! let hive = block_on(AsyncHiveBuilder::new(Remote, 4).build()).unwrap();
+//! let best = block_on(hive.run_for_rounds(10)).unwrap();
+//! assert_eq!(best.solution, 0);
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::{thread_rng, Rng, XorShiftRng};
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use futures::future::{self, BoxFuture, FutureExt, TryFutureExt};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use candidate::{WorkingCandidate, Candidate};
+use scaling::{ScalingFn, proportionate};
+use stop::{StopCondition, RunState, MaxRounds, TargetFitness};
+use result::{Error, Result as AbcResult, Stage, LockResultExt, catch_panic, catch_future_panic};
+
+/// Context for generating and evaluating solutions asynchronously.
+///
+/// This mirrors [`Context`](trait.Context.html), except that each method
+/// returns a future. The futures must be `'static`, so they should own (or
+/// share, through an `Arc`) whatever they need from the context and their
+/// arguments.
+pub trait AsyncContext: Send + Sync {
+    /// Type of solution that will be generated and explored.
+    type Solution: Clone + Send + Sync + 'static;
+
+    /// Generates a fresh, random solution.
+    fn make(&self) -> BoxFuture<'static, Self::Solution>;
+
+    /// Scores a solution; the algorithm will maximize this.
+    fn evaluate_fitness(&self, solution: &Self::Solution) -> BoxFuture<'static, f64>;
+
+    /// Generates a variant of the solution at `index`, in the context of the
+    /// whole field of working candidates.
+    fn explore(&self,
+               field: &[Arc<Candidate<Self::Solution>>],
+               index: usize)
+               -> BoxFuture<'static, Self::Solution>;
+}
+
+/// Manages the parameters of an [`AsyncHive`](struct.AsyncHive.html).
+pub struct AsyncHiveBuilder<Ctx: AsyncContext> {
+    workers: usize,
+    observers: usize,
+    retries: usize,
+    concurrency: usize,
+    context: Ctx,
+    scale: Box<dyn ScalingFn>,
+}
+
+impl<Ctx: AsyncContext> AsyncHiveBuilder<Ctx> {
+    /// Creates a new asynchronous hive.
+    ///
+    /// * `context` - Factory-like state that can be used while generating solutions.
+    /// * `workers` - Number of working solution candidates to maintain at a time.
+    pub fn new(context: Ctx, workers: usize) -> AsyncHiveBuilder<Ctx> {
+        if workers == 0 {
+            panic!("AsyncHiveBuilder must have at least one worker.");
+        }
+
+        AsyncHiveBuilder {
+            workers,
+            observers: workers,
+            retries: workers,
+            concurrency: workers,
+            context,
+            scale: Box::new(proportionate()),
+        }
+    }
+
+    /// Sets the number of "bees" that will pick a candidate to work on at random.
+    ///
+    /// This defaults to the number of workers.
+    pub fn set_observers(mut self, observers: usize) -> AsyncHiveBuilder<Ctx> {
+        self.observers = observers;
+        self
+    }
+
+    /// Sets the number of times a candidate can go unimproved before being reinitialized.
+    ///
+    /// This defaults to the number of workers.
+    pub fn set_retries(mut self, retries: usize) -> AsyncHiveBuilder<Ctx> {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the greatest number of solutions to explore and evaluate at once.
+    ///
+    /// This defaults to the number of workers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    pub fn set_concurrency(mut self, concurrency: usize) -> AsyncHiveBuilder<Ctx> {
+        if concurrency == 0 {
+            panic!("AsyncHiveBuilder concurrency must be at least one.");
+        }
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets the scaling function for observers to use.
+    ///
+    /// As with [`HiveBuilder::set_scaling`](struct.HiveBuilder.html#method.set_scaling),
+    /// this may be a [`ScalingFn`](scaling/trait.ScalingFn.html) that changes
+    /// from round to round.
+    pub fn set_scaling<S: ScalingFn + 'static>(mut self, scale: S) -> AsyncHiveBuilder<Ctx> {
+        self.scale = Box::new(scale);
+        self
+    }
+}
+
+impl<Ctx: AsyncContext + 'static> AsyncHiveBuilder<Ctx> {
+    /// Activates the builder, generating and evaluating the initial candidates.
+    pub fn build(self) -> BoxFuture<'static, AbcResult<AsyncHive<Ctx>>> {
+        let AsyncHiveBuilder { workers, observers, retries, concurrency, context, scale } = self;
+        let context = Arc::new(context);
+
+        let candidates = (0..workers)
+                             .map(|n| {
+                                 let solution = catch_panic(Stage::Make, Some(n), || context.make());
+                                 evaluate(context.clone(), Stage::Make, n, solution)
+                             })
+                             .collect::<Vec<_>>();
+
+        stream::iter(candidates)
+            .buffer_unordered(concurrency)
+            .try_collect::<Vec<_>>()
+            .map_ok(move |candidates| {
+                // Cache the best of the initial candidates.
+                let best = candidates.iter()
+                                     .fold(&candidates[0], |best, next| {
+                                         if next.fitness > best.fitness { next } else { best }
+                                     })
+                                     .clone();
+
+                AsyncHive {
+                    workers,
+                    observers,
+                    retries,
+                    concurrency,
+                    context,
+                    scale,
+                    working: Mutex::new(candidates.into_iter()
+                                                  .map(|c| WorkingCandidate::new(c, retries))
+                                                  .collect()),
+                    best: Mutex::new(best),
+                    rng: Mutex::new(thread_rng().gen()),
+                    evaluations: AtomicUsize::new(workers),
+                    rounds: AtomicUsize::new(0),
+                }
+            })
+            .boxed()
+    }
+}
+
+/// Awaits a solution from the context, then awaits its fitness.
+fn evaluate<Ctx>(context: Arc<Ctx>,
+                 stage: Stage,
+                 index: usize,
+                 solution: AbcResult<BoxFuture<'static, Ctx::Solution>>)
+                 -> BoxFuture<'static, AbcResult<Candidate<Ctx::Solution>>>
+    where Ctx: AsyncContext + 'static
+{
+    future::ready(solution)
+        .and_then(move |solution| catch_future_panic(stage, Some(index), solution))
+        .and_then(move |solution| {
+            future::ready(catch_panic(Stage::EvaluateFitness,
+                                      Some(index),
+                                      || context.evaluate_fitness(&solution)))
+                .and_then(move |fitness| catch_future_panic(Stage::EvaluateFitness, Some(index), fitness))
+                .map_ok(move |fitness| Candidate::new(solution, fitness))
+        })
+        .boxed()
+}
+
+/// Runs the ABC algorithm on an asynchronous context.
+pub struct AsyncHive<Ctx: AsyncContext> {
+    workers: usize,
+    observers: usize,
+    retries: usize,
+    concurrency: usize,
+    context: Arc<Ctx>,
+    scale: Box<dyn ScalingFn>,
+
+    working: Mutex<Vec<WorkingCandidate<Ctx::Solution>>>,
+    best: Mutex<Candidate<Ctx::Solution>>,
+
+    // Drives the observers' choices.
+    rng: Mutex<XorShiftRng>,
+
+    evaluations: AtomicUsize,
+
+    // Rounds completed over the hive's lifetime, for scheduled scaling.
+    rounds: AtomicUsize,
+}
+
+impl<Ctx: AsyncContext + 'static> AsyncHive<Ctx> {
+    /// Returns a guard for the current best solution found by the hive.
+    ///
+    /// As with [`Hive::get`](struct.Hive.html#method.get), drop the guard as
+    /// soon as convenient if the hive is running.
+    pub fn get(&self) -> AbcResult<MutexGuard<'_, Candidate<Ctx::Solution>>> {
+        self.best.lock().at(Stage::Best)
+    }
+
+    /// Get a reference to the hive's context.
+    pub fn context(&self) -> &Ctx {
+        &self.context
+    }
+
+    /// Runs for a fixed number of rounds, then returns the best solution found.
+    ///
+    /// If one of the `AsyncContext` methods or futures panics, this will
+    /// resolve to `Err(abc::Error)` describing the failure. Otherwise, it
+    /// will resolve to `Ok` with a `Candidate`.
+    pub fn run_for_rounds(&self, rounds: usize) -> BoxFuture<'_, AbcResult<Candidate<Ctx::Solution>>> {
+        self.run_with_condition(MaxRounds(rounds))
+    }
+
+    /// Runs until the best solution's fitness reaches `fitness`, then returns it.
+    pub fn run_until_fitness(&self, fitness: f64) -> BoxFuture<'_, AbcResult<Candidate<Ctx::Solution>>> {
+        self.run_with_condition(TargetFitness(fitness))
+    }
+
+    /// Runs until `condition` is met, then returns the best solution found.
+    ///
+    /// The condition is checked between rounds.
+    pub fn run_with_condition<'a, C>(&'a self,
+                                     mut condition: C)
+                                     -> BoxFuture<'a, AbcResult<Candidate<Ctx::Solution>>>
+        where C: StopCondition + 'a
+    {
+        let started = Instant::now();
+        let evaluations = self.evaluations.load(Ordering::Relaxed);
+
+        stream::iter(0..)
+            .map(Ok::<usize, Error>)
+            .try_take_while(move |&round| {
+                future::ready(self.get().map(|best| {
+                    !condition.should_stop(&RunState {
+                        round,
+                        evaluations: self.evaluations.load(Ordering::Relaxed) - evaluations,
+                        elapsed: started.elapsed(),
+                        best_fitness: best.fitness,
+                    })
+                }))
+            })
+            .try_for_each(move |_| self.round())
+            .and_then(move |()| future::ready(self.get().map(|best| best.clone())))
+            .boxed()
+    }
+
+    fn round(&self) -> BoxFuture<'_, AbcResult<()>> {
+        self.search((0..self.workers).collect())
+            .and_then(move |()| future::ready(self.observed()))
+            .and_then(move |observed| self.search(observed))
+            .and_then(move |()| self.scout())
+            .map_ok(move |()| {
+                self.rounds.fetch_add(1, Ordering::Relaxed);
+            })
+            .boxed()
+    }
+
+    /// Explores and evaluates each of the candidates at `indices`, keeping
+    /// the variants that improve on them.
+    fn search(&self, indices: Vec<usize>) -> BoxFuture<'_, AbcResult<()>> {
+        future::ready(self.current_working())
+            .and_then(move |field| {
+                let attempts = indices.into_iter()
+                                      .map(|n| {
+                                          let variant = catch_panic(Stage::Explore,
+                                                                    Some(n),
+                                                                    || self.context.explore(&field, n));
+                                          evaluate(self.context.clone(), Stage::Explore, n, variant)
+                                              .map_ok(move |variant| (n, variant))
+                                      })
+                                      .collect::<Vec<_>>();
+                stream::iter(attempts)
+                    .buffer_unordered(self.concurrency)
+                    .try_for_each(move |(n, variant)| future::ready(self.select(n, variant)))
+            })
+            .boxed()
+    }
+
+    /// Replaces each expired candidate with a fresh one.
+    fn scout(&self) -> BoxFuture<'_, AbcResult<()>> {
+        future::ready(self.working.lock().at(Stage::Candidate).map(|working| {
+                working.iter()
+                       .enumerate()
+                       .filter(|&(_, w)| w.expired())
+                       .map(|(n, _)| n)
+                       .collect::<Vec<_>>()
+            }))
+            .and_then(move |expired| {
+                let scouts = expired.into_iter()
+                                    .map(|n| {
+                                        let solution = catch_panic(Stage::Make, Some(n), || self.context.make());
+                                        evaluate(self.context.clone(), Stage::Make, n, solution)
+                                            .map_ok(move |candidate| (n, candidate))
+                                    })
+                                    .collect::<Vec<_>>();
+                stream::iter(scouts)
+                    .buffer_unordered(self.concurrency)
+                    .try_for_each(move |(n, candidate)| {
+                        future::ready(self.replace(n, candidate))
+                    })
+            })
+            .boxed()
+    }
+
+    fn current_working(&self) -> AbcResult<Vec<Arc<Candidate<Ctx::Solution>>>> {
+        let working = self.working.lock().at(Stage::Candidate)?;
+        Ok(working.iter().map(|w| w.candidate.clone()).collect())
+    }
+
+    /// Performs greedy selection between a candidate and its variant.
+    fn select(&self, n: usize, variant: Candidate<Ctx::Solution>) -> AbcResult<()> {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        let mut working = self.working.lock().at_index(Stage::Candidate, n)?;
+        if variant.fitness > working[n].candidate.fitness {
+            self.consider_improvement(&variant)?;
+            working[n] = WorkingCandidate::new(variant, self.retries);
+        } else {
+            working[n].deplete();
+        }
+        Ok(())
+    }
+
+    /// Replaces a scouted candidate, whether or not it improves.
+    fn replace(&self, n: usize, candidate: Candidate<Ctx::Solution>) -> AbcResult<()> {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        self.consider_improvement(&candidate)?;
+        let mut working = self.working.lock().at_index(Stage::Candidate, n)?;
+        working[n] = WorkingCandidate::new(candidate, self.retries);
+        Ok(())
+    }
+
+    fn consider_improvement(&self, candidate: &Candidate<Ctx::Solution>) -> AbcResult<()> {
+        let mut best_guard = self.best.lock().at(Stage::Best)?;
+        if candidate.fitness > best_guard.fitness {
+            *best_guard = candidate.clone();
+        }
+        Ok(())
+    }
+
+    /// Chooses a candidate for each observer, with probability
+    /// proportionate to its scaled fitness.
+    fn observed(&self) -> AbcResult<Vec<usize>> {
+        let fitnesses = {
+            let working = self.working.lock().at(Stage::Candidate)?;
+            let round = self.rounds.load(Ordering::Relaxed);
+            self.scale.scale(round, working.iter().map(|w| w.candidate.fitness).collect())
+        };
+        let total_fitness = fitnesses.iter().sum::<f64>();
+
+        let mut rng = self.rng.lock().at(Stage::Rng)?;
+        Ok((0..self.observers)
+               .map(|_| {
+                   let mut choice_point = rng.next_f64() * total_fitness;
+                   fitnesses.iter()
+                            .position(|fitness| {
+                                choice_point -= *fitness;
+                                choice_point < 0_f64
+                            })
+                            // If the weights are degenerate, pick one at random.
+                            .unwrap_or_else(|| rng.gen_range(0, fitnesses.len()))
+               })
+               .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    struct Remote;
+
+    impl AsyncContext for Remote {
+        type Solution = i32;
+
+        fn make(&self) -> BoxFuture<'static, i32> {
+            future::ready(thread_rng().gen_range(0, 100)).boxed()
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> BoxFuture<'static, f64> {
+            let solution = *solution;
+            future::lazy(move |_| solution as f64).boxed()
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> BoxFuture<'static, i32> {
+            future::ready(field[n].solution + thread_rng().gen_range(-10, 10)).boxed()
+        }
+    }
+
+    #[test]
+    fn runs_rounds() {
+        let hive = block_on(AsyncHiveBuilder::new(Remote, 5).set_concurrency(2).build()).unwrap();
+        let start = hive.get().unwrap().fitness;
+        let best = block_on(hive.run_for_rounds(10)).unwrap();
+        assert!(best.fitness >= start);
+        // The initial candidates, plus a worker and an observer per worker
+        // each round, plus any scouts.
+        assert!(hive.evaluations.load(Ordering::Relaxed) >= 5 + 10 * 10);
+    }
+
+    struct Fragile;
+
+    impl AsyncContext for Fragile {
+        type Solution = i32;
+
+        fn make(&self) -> BoxFuture<'static, i32> {
+            future::ready(0).boxed()
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> BoxFuture<'static, f64> {
+            let solution = *solution;
+            future::lazy(move |_| {
+                    if solution > 0 {
+                        panic!("too big");
+                    }
+                    0f64
+                })
+                .boxed()
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> BoxFuture<'static, i32> {
+            future::ready(field[n].solution + 1).boxed()
+        }
+    }
+
+    #[test]
+    fn future_panics_are_reported() {
+        let hive = block_on(AsyncHiveBuilder::new(Fragile, 3).build()).unwrap();
+        match block_on(hive.run_for_rounds(5)) {
+            Err(Error::Panicked { stage, index, message }) => {
+                assert_eq!(stage, Stage::EvaluateFitness);
+                assert!(index.unwrap() < 3);
+                assert_eq!(message, Some("too big".to_string()));
+            }
+            other => panic!("expected a panic to be reported, got {:?}", other.map(|_| ())),
+        }
+    }
+}
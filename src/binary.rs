@@ -0,0 +1,174 @@
+//! Ready-made context for binary strings, as in binary ABC.
+//!
+//! Feature selection, knapsack and other subset problems choose, for each of
+//! a fixed set of items, whether to include it. [`BinaryContext`](struct.BinaryContext.html)
+//! represents each solution as a `Vec<bool>`, and adapts Karaboga's
+//! exploration rule to bits by passing it through a [`sigmoid`](fn.sigmoid.html),
+//! so that only the fitness function needs to be written.
+//!
+//! This module requires the `binary` feature.
+//!
+//! # Examples
+//!
+//! A small knapsack: pick the most valuable items that fit in the bag.
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::binary::BinaryContext;
+//!
+//! let values = [10.0, 40.0, 30.0, 50.0];
+//! let weights = [5.0, 4.0, 6.0, 3.0];
+//! let context = BinaryContext::new(4, move |chosen: &[bool]| {
+//!     let pick = |xs: &[f64]| xs.iter().zip(chosen).filter(|&(_, &c)| c).map(|(x, _)| x).sum::<f64>();
+//!     // Overweight selections score nothing.
+//!     if pick(&weights) <= 10.0 { pick(&values) } else { 0.0 }
+//! });
+//! let hive = HiveBuilder::new(context, 10).build().unwrap();
+//! assert_eq!(hive.run_for_rounds(50).unwrap().solution, vec![false, true, false, true]);
+//! # }
+//! ```
+//!
+//! For feature selection, each bit says whether to keep a feature, and the
+//! fitness is the validation score of a model trained on the kept features,
+//! perhaps less a small penalty for each one.
+
+extern crate rand;
+
+use std::sync::Arc;
+
+use self::rand::{thread_rng, Rng};
+
+use candidate::Candidate;
+use context::Context;
+use util::choose_other_candidate;
+
+/// The logistic function, 1 / (1 + e<sup>−*x*</sup>).
+pub fn sigmoid(x: f64) -> f64 {
+    1f64 / (1f64 + (-x).exp())
+}
+
+/// Context for searching binary strings of a fixed length.
+///
+/// New solutions are drawn uniformly at random. Exploration follows the
+/// continuous rule on one random bit *j*, with a different candidate *k*
+/// from the field, treating bits as 0 or 1:
+///
+/// <center>*v* = *x*<sub>*j*</sub> + φ(*x*<sub>*j*</sub> − *x*<sub>*k*,*j*</sub>)</center>
+///
+/// where φ is drawn uniformly from [-1, 1]. The new bit is set with
+/// probability sigmoid(`slope` × (*v* − ½)). A bit on which the two
+/// candidates agree is almost always kept, while one on which they differ
+/// may go either way.
+pub struct BinaryContext<F> {
+    bits: usize,
+    slope: f64,
+    fitness: F,
+}
+
+impl<F> BinaryContext<F>
+    where F: Fn(&[bool]) -> f64 + Send + Sync
+{
+    /// Creates a context for strings of `bits` bits.
+    ///
+    /// * `bits` - Number of bits in each solution.
+    /// * `fitness` - Scores a solution (the algorithm will maximize this, and
+    ///   observers expect it to be positive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0.
+    pub fn new(bits: usize, fitness: F) -> BinaryContext<F> {
+        if bits == 0 {
+            panic!("BinaryContext must have at least one bit.");
+        }
+        BinaryContext {
+            bits,
+            slope: 10f64,
+            fitness,
+        }
+    }
+
+    /// Sets the slope of the sigmoid, which defaults to 10.
+    ///
+    /// A gentler slope makes exploration flip bits more freely.
+    pub fn with_slope(mut self, slope: f64) -> BinaryContext<F> {
+        self.slope = slope;
+        self
+    }
+
+    /// Returns the number of bits in each solution.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+}
+
+impl<F> Context for BinaryContext<F>
+    where F: Fn(&[bool]) -> f64 + Send + Sync
+{
+    type Solution = Vec<bool>;
+    type Fitness = f64;
+
+    fn make(&self) -> Vec<bool> {
+        self.make_with_rng(&mut thread_rng())
+    }
+
+    fn evaluate_fitness(&self, solution: &Vec<bool>) -> f64 {
+        (self.fitness)(solution)
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<Vec<bool>>>], index: usize) -> Vec<bool> {
+        self.explore_with_rng(field, index, &mut thread_rng())
+    }
+
+    /// Hamming distance.
+    fn distance(&self, a: &Vec<bool>, b: &Vec<bool>) -> Option<f64> {
+        Some(a.iter().zip(b.iter()).filter(|&(x, y)| x != y).count() as f64)
+    }
+
+    /// Flips every bit.
+    fn opposite(&self, solution: &Vec<bool>) -> Vec<bool> {
+        solution.iter().map(|bit| !bit).collect()
+    }
+
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<bool> {
+        (0..self.bits).map(|_| rng.gen()).collect()
+    }
+
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Vec<bool>>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Vec<bool> {
+        let mut variant = field[index].solution.clone();
+
+        let partner = choose_other_candidate(field, index, rng);
+
+        let j = rng.gen_range(0, self.bits);
+        let (x, xk) = (variant[j] as u8 as f64, field[partner].solution[j] as u8 as f64);
+        let v = x + rng.gen_range(-1f64, 1f64) * (x - xk);
+        variant[j] = rng.next_f64() < sigmoid(self.slope * (v - 0.5));
+        variant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agreeing_bits_are_kept() {
+        let context = BinaryContext::new(3, |x: &[bool]| 1.0 + x.len() as f64).with_slope(1000.0);
+        let field = (0..2).map(|_| Arc::new(Candidate::new(vec![true, false, true], 1.0))).collect::<Vec<_>>();
+        for _ in 0..100 {
+            assert_eq!(context.explore(&field, 0), vec![true, false, true]);
+        }
+        assert_eq!(context.opposite(&vec![true, false, true]), vec![false, true, false]);
+    }
+
+    #[test]
+    fn sigmoid_is_centred() {
+        assert_eq!(sigmoid(0.0), 0.5);
+        assert!((sigmoid(2.0) + sigmoid(-2.0) - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,31 +1,138 @@
-use std::fmt::{Debug, Formatter, Result as FmtResult};
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// One solution being explored by the hive, plus additional data.
 ///
 /// This implementation was written with the expectation that the
-/// [`evaluate_fitness`](trait.Solution.html#tymethod.evaluate_fitness)
+/// [`evaluate_fitness`](trait.Context.html#tymethod.evaluate_fitness)
 /// method may be very expensive, so the `Candidate` struct caches the
 /// computed fitness of its solution.
-pub struct Candidate<S: Clone + Send + Sync + 'static> {
+///
+/// With the `serde` feature enabled, candidates can be serialized (as long as
+/// their solutions can), so that the hive's results can be logged or sent
+/// over the network directly.
+pub struct Candidate<S: Clone + Send + Sync + 'static, F = f64> {
     /// Actual candidate solution.
     pub solution: S,
 
-    /// Cached fitness of the solution.
+    /// Cached fitness of the solution, as an `f64` to be maximized, for
+    /// scaling and statistics.
     pub fitness: f64,
+
+    /// The value returned by `evaluate_fitness`, of the context's
+    /// [`Fitness`](trait.Fitness.html) type, before it was transformed
+    /// according to the hive's [`Objective`](enum.Objective.html). The hive
+    /// compares candidates by this value; see
+    /// [`Objective::compare`](enum.Objective.html#method.compare). When
+    /// maximizing an `f64`, this is the same as `fitness`.
+    pub raw_fitness: F,
+
+    /// A score for reporting, from
+    /// [`Context::evaluate`](trait.Context.html#method.evaluate), if the
+    /// context gave one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub score: Option<f64>,
+
+    /// Where and when the hive created the candidate. This is `None` for
+    /// candidates made outside of a hive.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub provenance: Option<Provenance>,
+
+    /// How the repeated evaluations of a noisy fitness varied, if the hive
+    /// [resamples](struct.HiveBuilder.html#method.set_resampling) them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub noise: Option<Noise>,
 }
 
 impl<S: Clone + Send + Sync + 'static> Candidate<S> {
     /// Wrap a solution with its cached fitness.
     pub fn new(solution: S, fitness: f64) -> Candidate<S> {
+        Candidate::with_raw_fitness(solution, fitness, fitness)
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Candidate<S, F> {
+    /// Wrap a solution with its cached fitness, and the untransformed value
+    /// it came from.
+    pub fn with_raw_fitness(solution: S, fitness: f64, raw_fitness: F) -> Candidate<S, F> {
         Candidate {
-            solution: solution,
-            fitness: fitness,
+            solution,
+            fitness,
+            raw_fitness,
+            score: None,
+            provenance: None,
+            noise: None,
         }
     }
 }
 
-impl<S: Clone + Send + Sync + 'static> Debug for Candidate<S>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How a candidate came to be in the hive's field.
+pub enum Origin {
+    /// Generated for the initial population.
+    Initial,
+
+    /// A variant that improved on the candidate at the same index.
+    Explored,
+
+    /// Generated by a scout, replacing an abandoned candidate.
+    Scouted,
+
+    /// Received from another hive, with
+    /// [`Hive::immigrate`](struct.Hive.html#method.immigrate).
+    Immigrated,
+
+    /// The opposite of the candidate at the same index, which it beat. See
+    /// [`HiveBuilder::set_opposition`](struct.HiveBuilder.html#method.set_opposition).
+    Opposed,
+
+    /// A refinement of the candidate at the same index by a local search.
+    /// See [`HiveBuilder::set_local_search`](struct.HiveBuilder.html#method.set_local_search).
+    Refined,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Where and when a candidate was created, for tracing its lineage.
+pub struct Provenance {
+    /// The round under way when the candidate was created, counting from 0
+    /// over the hive's lifetime, as reported by
+    /// [`Hive::get_round`](struct.Hive.html#method.get_round).
+    pub round: usize,
+
+    /// Index of the working candidate it was created for. For an explored
+    /// candidate, this is also the index of its parent.
+    pub index: usize,
+
+    /// How the candidate was created.
+    pub origin: Origin,
+
+    /// How many times in a row exploration has improved on this index
+    /// since it was last filled some other way: one more than its parent's
+    /// for an explored candidate, the same as its parent's for a refined
+    /// candidate, and 0 otherwise.
+    pub improvements: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The spread of a fitness that was evaluated several times.
+///
+/// A resampled candidate's `raw_fitness` is the mean of the samples, and its
+/// `fitness` is discounted for the uncertainty in that mean.
+pub struct Noise {
+    /// Number of evaluations averaged.
+    pub samples: usize,
+
+    /// Sample variance of the values from `evaluate_fitness`.
+    pub variance: f64,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Debug for Candidate<S, F>
     where S: Debug
 {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -33,19 +140,33 @@ impl<S: Clone + Send + Sync + 'static> Debug for Candidate<S>
     }
 }
 
-pub struct WorkingCandidate<S: Clone + Send + Sync + 'static> {
-    pub candidate: Candidate<S>,
+/// A candidate in the field, with the retries it has left.
+///
+/// The candidate is reference-counted, so that snapshots of the field copy
+/// pointers rather than solutions.
+#[cfg(feature = "std")]
+pub struct WorkingCandidate<S: Clone + Send + Sync + 'static, F = f64> {
+    pub candidate: Arc<Candidate<S, F>>,
     retries: i32,
 }
 
-impl<S: Clone + Send + Sync + 'static> WorkingCandidate<S> {
-    pub fn new(candidate: Candidate<S>, retries: usize) -> WorkingCandidate<S> {
+#[cfg(feature = "std")]
+impl<S: Clone + Send + Sync + 'static, F> WorkingCandidate<S, F> {
+    pub fn new<C: Into<Arc<Candidate<S, F>>>>(candidate: C, retries: usize) -> WorkingCandidate<S, F> {
         WorkingCandidate {
-            candidate: candidate,
+            candidate: candidate.into(),
             retries: retries as i32,
         }
     }
 
+    pub fn retries(&self) -> usize {
+        if self.retries > 0 {
+            self.retries as usize
+        } else {
+            0
+        }
+    }
+
     pub fn expired(&self) -> bool {
         self.retries <= 0
     }
@@ -0,0 +1,175 @@
+//! Chaotic sequences, for chaotic ABC.
+//!
+//! Chaotic maps are deterministic, but never settle into a cycle, and are
+//! sensitive to their starting point. Drawing the random numbers for the
+//! search from a chaotic map rather than a pseudo-random generator is
+//! reported to help the colony escape local optima on some problems.
+//!
+//! A [`ChaoticSequence`](struct.ChaoticSequence.html) iterates a
+//! [`ChaoticMap`](enum.ChaoticMap.html), and implements `Rng` so that it
+//! can stand in for a random number generator. With
+//! [`HiveBuilder::set_chaos`](../struct.HiveBuilder.html#method.set_chaos),
+//! each working candidate follows its own chaotic sequence: it drives the
+//! candidate's calls to
+//! [`Context::explore_guided`](../trait.Context.html#method.explore_guided)
+//! (so, for instance, the φ coefficient of
+//! [`BoundedVecContext`](../vector/struct.BoundedVecContext.html)) and its
+//! reinitialization by scouts.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::chaos::{ChaoticMap, ChaoticSequence};
+//!
+//! let sequence = ChaoticSequence::new(ChaoticMap::Logistic { r: 4.0 }, 0.3);
+//! assert!(sequence.take(100).all(|x| x > 0.0 && x < 1.0));
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// A map from the open interval (0, 1) to itself.
+pub enum ChaoticMap {
+    /// The logistic map, *x* ← *rx*(1 − *x*). It is fully chaotic at
+    /// *r* = 4, which is the default.
+    Logistic {
+        /// The growth rate, at most 4.
+        r: f64,
+    },
+
+    /// The tent map, *x* ← μ min(*x*, 1 − *x*).
+    ///
+    /// At μ = 2, rounding drives every sequence to 0 within about 50 steps,
+    /// so μ should be a little less, such as 1.9999.
+    Tent {
+        /// The slope, at most 2.
+        mu: f64,
+    },
+}
+
+impl Default for ChaoticMap {
+    fn default() -> ChaoticMap {
+        ChaoticMap::Logistic { r: 4.0 }
+    }
+}
+
+impl ChaoticMap {
+    /// Applies the map once.
+    pub fn next(&self, x: f64) -> f64 {
+        match *self {
+            ChaoticMap::Logistic { r } => r * x * (1f64 - x),
+            ChaoticMap::Tent { mu } => mu * x.min(1f64 - x),
+        }
+    }
+}
+
+/// Where a sequence restarts if rounding drives it out of (0, 1).
+const RESTART: f64 = 0.618_033_988_749_894_9;
+
+#[derive(Clone, Debug, PartialEq)]
+/// The successive values of a chaotic map.
+pub struct ChaoticSequence {
+    map: ChaoticMap,
+    x: f64,
+}
+
+impl ChaoticSequence {
+    /// Starts a sequence at `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` isn't strictly between 0 and 1.
+    pub fn new(map: ChaoticMap, x: f64) -> ChaoticSequence {
+        if !(x > 0f64 && x < 1f64) {
+            panic!("ChaoticSequence must start strictly between 0 and 1.");
+        }
+        ChaoticSequence { map, x }
+    }
+
+    /// Starts a sequence at a random point.
+    pub fn random<R: Rng>(map: ChaoticMap, rng: &mut R) -> ChaoticSequence {
+        let x = rng.next_f64();
+        ChaoticSequence::new(map, if x > 0f64 { x } else { RESTART })
+    }
+
+    /// Returns the latest value.
+    pub fn value(&self) -> f64 {
+        self.x
+    }
+}
+
+impl Iterator for ChaoticSequence {
+    type Item = f64;
+
+    /// Steps the map, and returns the new value.
+    ///
+    /// If rounding drives the value to 0 or 1 (where it would otherwise stay
+    /// forever), the sequence restarts from a fixed point inside the
+    /// interval.
+    fn next(&mut self) -> Option<f64> {
+        let x = self.map.next(self.x);
+        self.x = if x > 0f64 && x < 1f64 { x } else { RESTART };
+        Some(self.x)
+    }
+}
+
+impl Rng for ChaoticSequence {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_f64() * 4_294_967_296f64) as u32
+    }
+
+    /// Returns the next value of the sequence, which is not uniformly
+    /// distributed.
+    fn next_f64(&mut self) -> f64 {
+        self.next().unwrap_or(RESTART)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive::HiveBuilder;
+    use vector::BoundedVecContext;
+
+    #[test]
+    fn tent_map_recovers() {
+        // At μ = 2, the sequence reaches 0 in a few dozen steps, and must
+        // restart rather than stick there.
+        let values = ChaoticSequence::new(ChaoticMap::Tent { mu: 2.0 }, 0.3).take(200).collect::<Vec<_>>();
+        assert!(values.iter().all(|&x| x > 0.0 && x < 1.0));
+        assert!(values.contains(&RESTART));
+    }
+
+    #[test]
+    fn generates_ranges() {
+        let mut sequence = ChaoticSequence::new(ChaoticMap::default(), 0.1);
+        for _ in 0..100 {
+            let phi = sequence.gen_range(-1.0, 1.0);
+            assert!((-1.0..1.0).contains(&phi));
+            assert!(sequence.gen_range(0, 7) < 7);
+        }
+    }
+
+    #[test]
+    fn hive_follows_sequences() {
+        let run = || {
+            let context = BoundedVecContext::uniform(2, -1.0, 1.0, |x: &[f64]| 3.0 - x[0] - x[1]);
+            let hive = HiveBuilder::new(context, 4)
+                .set_threads(1)
+                .set_retries(2)
+                .set_rng_seed(5)
+                .set_chaos(ChaoticMap::Tent { mu: 1.9999 })
+                .build()
+                .unwrap();
+            hive.run_for_rounds(10).unwrap().solution
+        };
+        let best = run();
+        assert!(best.iter().all(|x| x.abs() <= 1.0));
+        assert_eq!(best, run());
+    }
+}
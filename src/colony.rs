@@ -0,0 +1,544 @@
+//! The heart of the algorithm, without threads or the standard library.
+//!
+//! The threaded [`Hive`](../struct.Hive.html) is built on the pieces here:
+//! the [cycle of tasks](struct.TaskCycle.html) that makes up each round, or
+//! any other [schedule](trait.TaskSchedule.html) of them, and
+//! [roulette-wheel selection](fn.roulette.html). They only need `alloc`, so
+//! with the default `std` feature turned off, this module and
+//! [`Candidate`](../struct.Candidate.html) are all that the crate builds.
+//! A [`Colony`](struct.Colony.html) then runs the algorithm on a single
+//! thread, for embedded targets that tune their parameters on the device.
+//!
+//! Since the `rand` crate needs `std`, a colony takes its randomness from
+//! the [`Problem`](trait.Problem.html) it is solving, as numbers in [0, 1).
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::Candidate;
+//! use abc::colony::{Colony, Problem};
+//!
+//! // Maximize 10 - (x - 3)², with a small linear congruential generator.
+//! struct Parabola(u64);
+//!
+//! impl Problem<f64> for Parabola {
+//!     fn random(&mut self) -> f64 {
+//!         self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!         (self.0 >> 11) as f64 / (1u64 << 53) as f64
+//!     }
+//!
+//!     fn make(&mut self) -> f64 {
+//!         self.random() * 10.0
+//!     }
+//!
+//!     fn evaluate(&mut self, x: &f64) -> f64 {
+//!         10.0 - (x - 3.0) * (x - 3.0)
+//!     }
+//!
+//!     fn explore(&mut self, field: &[Candidate<f64>], index: usize) -> f64 {
+//!         let partner = (self.random() * field.len() as f64) as usize;
+//!         let x = field[index].solution;
+//!         x + (self.random() * 2.0 - 1.0) * (x - field[partner].solution)
+//!     }
+//! }
+//!
+//! let mut problem = Parabola(7);
+//! let mut colony = Colony::new(&mut problem, 10, 10, 20);
+//! for _ in 0..100 {
+//!     colony.run_round(&mut problem);
+//! }
+//! assert!((colony.best().solution - 3.0).abs() < 0.1);
+//! # }
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use candidate::Candidate;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Token indicating which abstract bee should do work next.
+pub enum Task {
+    /// The employed bee for the candidate at this index.
+    Worker(usize),
+
+    /// An observer. The index counts the observers through their phase;
+    /// which candidate it works on is chosen when the task is carried out.
+    Observer(usize),
+
+    /// The scout for the candidate at this index, which replaces it if it
+    /// has run out of retries. Only a cycle with a
+    /// [scout phase](enum.Phase.html#variant.Scouts) has these.
+    Scout(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// One pass through the bees of a kind, as part of a round.
+pub enum Phase {
+    /// A task for every worker, in order.
+    Workers,
+
+    /// A task for every observer.
+    Observers,
+
+    /// A scout task for every candidate, in order.
+    ///
+    /// With a scout phase in the cycle, candidates that run out of retries
+    /// are no longer replaced straight away, but wait for the phase, as in
+    /// the original formulation of the algorithm. They are still explored
+    /// in the meantime, and keep their place if they improve.
+    Scouts,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The phases that make up each round.
+///
+/// By default, a round is a worker phase followed by an observer phase.
+/// Phases may be repeated and put in any order, to weigh the observers more
+/// heavily, say, or to end each round with a scout phase.
+///
+/// # Examples
+///
+/// ```
+/// use abc::colony::{CycleSpec, Phase, Task, TaskCycle};
+///
+/// // Two observer passes for every worker pass.
+/// let spec = CycleSpec::new(vec![Phase::Workers, Phase::Observers, Phase::Observers]);
+/// assert_eq!(spec.tasks_per_round(2, 1), 4);
+///
+/// let mut cycle = TaskCycle::with_spec(2, 1, spec);
+/// let round = (0..4).map(|_| cycle.next_task()).collect::<Vec<_>>();
+/// assert_eq!(round, vec![Task::Worker(0), Task::Worker(1), Task::Observer(0), Task::Observer(0)]);
+/// assert_eq!(cycle.round(), 1);
+/// ```
+pub struct CycleSpec {
+    phases: Vec<Phase>,
+}
+
+impl CycleSpec {
+    /// Creates a cycle whose rounds go through `phases` in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no worker phase.
+    pub fn new(phases: Vec<Phase>) -> CycleSpec {
+        if !phases.contains(&Phase::Workers) {
+            panic!("CycleSpec must have a worker phase.");
+        }
+        CycleSpec { phases }
+    }
+
+    /// Returns the phases of each round.
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// Returns whether the rounds end their candidates' retries with a
+    /// scout phase, rather than as soon as they run out.
+    pub fn has_scouts(&self) -> bool {
+        self.phases.contains(&Phase::Scouts)
+    }
+
+    /// Returns the number of tasks in each round.
+    pub fn tasks_per_round(&self, workers: usize, observers: usize) -> usize {
+        self.phases.iter().map(|&phase| phase_len(phase, workers, observers)).sum()
+    }
+}
+
+impl Default for CycleSpec {
+    fn default() -> CycleSpec {
+        CycleSpec::new(vec![Phase::Workers, Phase::Observers])
+    }
+}
+
+/// The number of tasks in a phase.
+fn phase_len(phase: Phase, workers: usize, observers: usize) -> usize {
+    match phase {
+        Phase::Workers | Phase::Scouts => workers,
+        Phase::Observers => observers,
+    }
+}
+
+/// The endless sequence of tasks, one round after another.
+///
+/// Each round goes through the phases of a [`CycleSpec`](struct.CycleSpec.html):
+/// by default, a task for every worker, in order, followed by one for every
+/// observer.
+#[derive(Clone, Debug)]
+pub struct TaskCycle {
+    workers: usize,
+    observers: usize,
+    spec: CycleSpec,
+    // The phase under way, and the next task's place in it.
+    phase: usize,
+    position: usize,
+    round: usize,
+}
+
+impl TaskCycle {
+    /// Starts the first round.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no workers.
+    pub fn new(workers: usize, observers: usize) -> TaskCycle {
+        TaskCycle::with_spec(workers, observers, CycleSpec::default())
+    }
+
+    /// Starts the first round of a cycle through the phases of `spec`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no workers.
+    pub fn with_spec(workers: usize, observers: usize, spec: CycleSpec) -> TaskCycle {
+        assert!(workers > 0);
+        let mut cycle = TaskCycle {
+            workers,
+            observers,
+            spec,
+            phase: 0,
+            position: 0,
+            round: 0,
+        };
+        cycle.skip_finished();
+        cycle
+    }
+
+    /// Returns the phases of each round.
+    pub fn spec(&self) -> &CycleSpec {
+        &self.spec
+    }
+
+    /// Returns the number of rounds completed. This goes up as the last task
+    /// of each round is handed out, before it is carried out.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Changes the number of observers. If the current round has already
+    /// reached its observers, this applies from the next task.
+    pub fn set_observers(&mut self, observers: usize) {
+        self.observers = observers;
+        // The number of observers may have fallen partway through the phase.
+        self.skip_finished();
+    }
+
+    /// Hands out the next task.
+    pub fn next_task(&mut self) -> Task {
+        let current = match self.spec.phases[self.phase] {
+            Phase::Workers => Task::Worker(self.position),
+            Phase::Observers => Task::Observer(self.position),
+            Phase::Scouts => Task::Scout(self.position),
+        };
+        self.position += 1;
+        self.skip_finished();
+        current
+    }
+
+    /// Moves on from a phase once its tasks have all been handed out, and
+    /// past any phase with no tasks, ending the round after the last.
+    fn skip_finished(&mut self) {
+        // The worker phase always has tasks, so this can't go on forever.
+        while self.position >= phase_len(self.spec.phases[self.phase], self.workers, self.observers) {
+            self.position = 0;
+            self.phase += 1;
+            if self.phase == self.spec.phases.len() {
+                self.phase = 0;
+                self.round += 1;
+            }
+        }
+    }
+}
+
+/// Decides which task comes next, round after round.
+///
+/// The hive claims its tasks from a schedule, which by default is a
+/// [`TaskCycle`](struct.TaskCycle.html). Another schedule, set with
+/// [`HiveBuilder::set_task_schedule`](../struct.HiveBuilder.html#method.set_task_schedule),
+/// can interleave the workers, observers and scouts however it likes,
+/// without any change to the run loop. Each phase of a round should number
+/// its tasks from 0, since synchronous and deterministic hives finish each
+/// phase before starting the next, and tell where they start that way.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::HiveBuilder;
+/// use abc::colony::{Task, TaskSchedule};
+/// use abc::vector::BoundedVecContext;
+///
+/// // Alternates workers and observers, one candidate at a time.
+/// struct Interleaved {
+///     workers: usize,
+///     next: usize,
+///     round: usize,
+/// }
+///
+/// impl TaskSchedule for Interleaved {
+///     fn next_task(&mut self) -> Task {
+///         let (n, task) = (self.next / 2, self.next % 2);
+///         self.next += 1;
+///         if self.next == 2 * self.workers {
+///             self.next = 0;
+///             self.round += 1;
+///         }
+///         if task == 0 { Task::Worker(n) } else { Task::Observer(n) }
+///     }
+///
+///     fn round(&self) -> usize {
+///         self.round
+///     }
+///
+///     fn tasks_per_round(&self) -> usize {
+///         2 * self.workers
+///     }
+/// }
+///
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+/// let hive = HiveBuilder::new(context, 10)
+///     .set_task_schedule(|workers, _| Interleaved { workers, next: 0, round: 0 })
+///     .build()
+///     .unwrap();
+/// assert!(hive.run_for_rounds(20).unwrap().fitness > 50.0);
+/// # }
+/// ```
+pub trait TaskSchedule: Send {
+    /// Hands out the next task.
+    fn next_task(&mut self) -> Task;
+
+    /// Returns the number of rounds completed. This should go up as the last
+    /// task of each round is handed out.
+    fn round(&self) -> usize;
+
+    /// Returns the number of tasks in each round, for the hive's
+    /// [timings](../stats/struct.RoundTiming.html).
+    fn tasks_per_round(&self) -> usize;
+
+    /// Changes the number of observers, when a
+    /// [`HiveObserver`](../trait.HiveObserver.html) tunes it. By default,
+    /// this is ignored.
+    fn set_observers(&mut self, _observers: usize) {}
+
+    /// Returns whether the schedule hands out
+    /// [`Task::Scout`](enum.Task.html#variant.Scout). If it does, expired
+    /// candidates wait for a scout task to replace them, rather than being
+    /// scouted as soon as they expire. This defaults to `false`.
+    fn has_scouts(&self) -> bool {
+        false
+    }
+}
+
+impl TaskSchedule for TaskCycle {
+    fn next_task(&mut self) -> Task {
+        TaskCycle::next_task(self)
+    }
+
+    fn round(&self) -> usize {
+        self.round
+    }
+
+    fn tasks_per_round(&self) -> usize {
+        self.spec.tasks_per_round(self.workers, self.observers)
+    }
+
+    fn set_observers(&mut self, observers: usize) {
+        TaskCycle::set_observers(self, observers)
+    }
+
+    fn has_scouts(&self) -> bool {
+        self.spec.has_scouts()
+    }
+}
+
+/// Chooses an index by roulette wheel, with likelihood proportionate to its
+/// weight.
+///
+/// `weights` pairs each index that may be chosen with its weight, which
+/// must not be negative. `draw` is a uniform random number in [0, 1).
+/// Returns `None` if there are no weights, or they are all 0.
+pub fn roulette<I>(weights: I, draw: f64) -> Option<usize>
+    where I: IntoIterator<Item = (usize, f64)>
+{
+    roulette_in(weights, draw, &mut Vec::new())
+}
+
+/// Chooses an index by roulette wheel, as [`roulette`](fn.roulette.html)
+/// does, keeping the running totals in `totals` so that its space can be
+/// reused from one draw to the next.
+pub fn roulette_in<I>(weights: I, draw: f64, totals: &mut Vec<(usize, f64)>) -> Option<usize>
+    where I: IntoIterator<Item = (usize, f64)>
+{
+    totals.clear();
+    totals.extend(weights.into_iter()
+                         .scan(0f64, |total, (i, weight)| {
+                             *total += weight;
+                             Some((i, *total))
+                         }));
+    match totals.last() {
+        // Multiplying the choice point is equivalent to, and more efficient
+        // than, normalizing all of the weights.
+        Some(&(last, total)) if total > 0f64 => {
+            let choice_point = draw * total;
+            Some(totals.iter()
+                       .find(|&&(_, running)| running > choice_point)
+                       // Only reachable if the total overflowed.
+                       .map_or(last, |&(i, _)| i))
+        }
+        _ => None,
+    }
+}
+
+/// A problem for a [`Colony`](struct.Colony.html) to solve: the
+/// single-threaded counterpart of [`Context`](../trait.Context.html), which
+/// also supplies the randomness.
+pub trait Problem<S: Clone + Send + Sync + 'static> {
+    /// Returns a uniform random number in [0, 1).
+    fn random(&mut self) -> f64;
+
+    /// Generates a fresh random solution.
+    fn make(&mut self) -> S;
+
+    /// Scores a solution. The colony maximizes this, and observers expect it
+    /// to be positive.
+    fn evaluate(&mut self, solution: &S) -> f64;
+
+    /// Generates a variant of the candidate at `index`.
+    fn explore(&mut self, field: &[Candidate<S>], index: usize) -> S;
+}
+
+/// The ABC algorithm on a single thread, without the standard library.
+///
+/// Each round, every candidate is explored once by its worker, then the
+/// observers explore candidates chosen by roulette wheel on their fitness.
+/// A variant replaces its candidate if it is fitter; a candidate that fails
+/// to improve `retries` times in a row is abandoned, and replaced with a
+/// fresh solution.
+pub struct Colony<S: Clone + Send + Sync + 'static> {
+    field: Vec<Candidate<S>>,
+    retries: Vec<usize>,
+    limit: usize,
+    cycle: TaskCycle,
+    best: Candidate<S>,
+}
+
+impl<S: Clone + Send + Sync + 'static> Colony<S> {
+    /// Creates a colony of `workers` fresh candidates from `problem`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no workers.
+    pub fn new<P: Problem<S>>(problem: &mut P, workers: usize, observers: usize, retries: usize) -> Colony<S> {
+        let cycle = TaskCycle::new(workers, observers);
+        let field = (0..workers)
+                        .map(|_| {
+                            let solution = problem.make();
+                            let fitness = problem.evaluate(&solution);
+                            Candidate::new(solution, fitness)
+                        })
+                        .collect::<Vec<_>>();
+        let best = field.iter()
+                        .fold(&field[0], |best, candidate| if candidate.fitness > best.fitness { candidate } else { best })
+                        .clone();
+        Colony {
+            field,
+            retries: vec![retries; workers],
+            limit: retries,
+            cycle,
+            best,
+        }
+    }
+
+    /// Returns the working candidates.
+    pub fn field(&self) -> &[Candidate<S>] {
+        &self.field
+    }
+
+    /// Returns the fittest candidate found so far.
+    pub fn best(&self) -> &Candidate<S> {
+        &self.best
+    }
+
+    /// Returns the number of rounds completed.
+    pub fn round(&self) -> usize {
+        self.cycle.round()
+    }
+
+    /// Runs one round.
+    pub fn run_round<P: Problem<S>>(&mut self, problem: &mut P) {
+        let round = self.cycle.round();
+        while self.cycle.round() == round {
+            let index = match self.cycle.next_task() {
+                Task::Worker(n) => n,
+                // A colony scouts as soon as a candidate runs out of retries.
+                Task::Scout(_) => continue,
+                Task::Observer(_) => {
+                    let weights = self.field.iter().map(|candidate| candidate.fitness.max(0f64)).enumerate();
+                    let draw = problem.random();
+                    roulette(weights, draw).unwrap_or_else(|| {
+                        // With every weight 0, any candidate will do.
+                        ((problem.random() * self.field.len() as f64) as usize).min(self.field.len() - 1)
+                    })
+                }
+            };
+            self.work_on(problem, index);
+        }
+    }
+
+    fn work_on<P: Problem<S>>(&mut self, problem: &mut P, index: usize) {
+        let variant = problem.explore(&self.field, index);
+        let fitness = problem.evaluate(&variant);
+        if fitness > self.field[index].fitness {
+            self.replace(index, Candidate::new(variant, fitness));
+        } else {
+            self.retries[index] = self.retries[index].saturating_sub(1);
+            if self.retries[index] == 0 {
+                let solution = problem.make();
+                let fitness = problem.evaluate(&solution);
+                self.replace(index, Candidate::new(solution, fitness));
+            }
+        }
+    }
+
+    fn replace(&mut self, index: usize, candidate: Candidate<S>) {
+        if candidate.fitness > self.best.fitness {
+            self.best = candidate.clone();
+        }
+        self.field[index] = candidate;
+        self.retries[index] = self.limit;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roulette_follows_weights() {
+        let weights = vec![(0, 1.0), (2, 0.0), (3, 3.0)];
+        assert_eq!(roulette(weights.clone(), 0.0), Some(0));
+        assert_eq!(roulette(weights.clone(), 0.24), Some(0));
+        assert_eq!(roulette(weights.clone(), 0.26), Some(3));
+        assert_eq!(roulette(vec![(0, 0.0)], 0.5), None);
+        assert_eq!(roulette(Vec::new(), 0.5), None);
+    }
+
+    #[test]
+    fn cycles_follow_their_spec() {
+        let spec = CycleSpec::new(vec![Phase::Observers, Phase::Workers, Phase::Observers, Phase::Scouts]);
+        let mut cycle = TaskCycle::with_spec(2, 1, spec);
+        let round = (0..6).map(|_| cycle.next_task()).collect::<Vec<_>>();
+        assert_eq!(round,
+                   vec![Task::Observer(0), Task::Worker(0), Task::Worker(1), Task::Observer(0), Task::Scout(0), Task::Scout(1)]);
+        assert_eq!(cycle.round(), 1);
+
+        // Phases without tasks are passed over.
+        cycle.set_observers(0);
+        let round = (0..4).map(|_| cycle.next_task()).collect::<Vec<_>>();
+        assert_eq!(round, vec![Task::Worker(0), Task::Worker(1), Task::Scout(0), Task::Scout(1)]);
+        assert_eq!(cycle.round(), 2);
+    }
+}
@@ -0,0 +1,141 @@
+//! Checks the hive against random search on the same budget.
+//!
+//! Before tuning the hive for a new problem, it is worth knowing that the
+//! algorithm does better on it than the simplest alternative.
+//! [`compare`](fn.compare.html) runs a hive until it has made a budget of
+//! fitness evaluations, then spends as many evaluations on random search:
+//! fresh solutions from
+//! [`Context::make_with_rng`](../trait.Context.html#method.make_with_rng),
+//! scored as the hive scores them. The
+//! [`Comparison`](struct.Comparison.html) holds both runs' trajectories, as
+//! the rises in their best fitness, so that they can be compared at any
+//! point along the way, or plotted.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{HiveBuilder, Objective};
+//! use abc::compare::compare;
+//! use abc::testing::rastrigin;
+//!
+//! let builder = HiveBuilder::new(rastrigin(4), 10)
+//!     .set_objective(Objective::Minimize)
+//!     .set_threads(1)
+//!     .set_rng_seed(3);
+//! let comparison = compare(builder, 5_000).unwrap();
+//! assert!(comparison.hive_wins());
+//! println!("The hive beat random search by {}", comparison.margin());
+//! # }
+//! ```
+
+use candidate::Candidate;
+use context::Context;
+use hive::HiveBuilder;
+use result::Result as AbcResult;
+use stats::Improvement;
+
+/// One side of a [`Comparison`](struct.Comparison.html).
+pub struct Trajectory<S: Clone + Send + Sync + 'static, F = f64> {
+    /// The best candidate found.
+    pub best: Candidate<S, F>,
+
+    /// Every rise in the best fitness, in order, starting with the best of
+    /// the first candidates evaluated.
+    pub improvements: Vec<Improvement>,
+
+    /// The number of fitness evaluations made.
+    pub evaluations: usize,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Trajectory<S, F> {
+    /// Returns the best fitness found within the first `evaluations`
+    /// fitness evaluations, or `None` if there was none so soon.
+    pub fn best_at(&self, evaluations: usize) -> Option<f64> {
+        self.improvements
+            .iter()
+            .take_while(|improvement| improvement.evaluations <= evaluations)
+            .last()
+            .map(|improvement| improvement.fitness)
+    }
+}
+
+/// The hive's run and the random search's, on the same budget.
+///
+/// Created by [`compare`](fn.compare.html).
+pub struct Comparison<S: Clone + Send + Sync + 'static, F = f64> {
+    /// The hive's run.
+    pub hive: Trajectory<S, F>,
+
+    /// The random search, with as many evaluations as the hive made.
+    pub random_search: Trajectory<S, F>,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Comparison<S, F> {
+    /// Returns how much fitter the hive's best candidate is than the random
+    /// search's. This is negative if random search did better.
+    pub fn margin(&self) -> f64 {
+        self.hive.best.fitness - self.random_search.best.fitness
+    }
+
+    /// Returns whether the hive found a fitter candidate than random search.
+    pub fn hive_wins(&self) -> bool {
+        self.margin() > 0f64
+    }
+}
+
+/// Runs the hive from `builder` until it has made `evaluations` fitness
+/// evaluations, then spends the same number on random search, and gathers
+/// both runs.
+///
+/// The evaluation budget replaces any the builder has set. The hive may
+/// overrun it by a few evaluations per thread, and counts those for its
+/// initial population too, so the random search is given however many the
+/// hive made in all. It runs on the calling thread, from the builder's seed,
+/// if any, and makes and scores its solutions as the hive would, with the
+/// same objective and evaluator.
+pub fn compare<Ctx: Context>(builder: HiveBuilder<Ctx>, evaluations: usize) -> AbcResult<Comparison<Ctx::Solution, Ctx::Fitness>> {
+    let hive = builder.set_evaluation_budget(evaluations).build()?;
+    hive.run_forever()?;
+    let spent = hive.evaluations();
+    let (best, improvements) = hive.random_search(spent)?;
+    Ok(Comparison {
+        hive: Trajectory {
+            best: (*hive.best_arc()?).clone(),
+            improvements: hive.improvement_history()?,
+            evaluations: spent,
+        },
+        random_search: Trajectory {
+            best,
+            improvements,
+            evaluations: spent,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objective::Objective;
+    use testing::sphere;
+
+    #[test]
+    fn both_runs_share_the_budget() {
+        let builder = HiveBuilder::new(sphere(3), 8)
+            .set_objective(Objective::Minimize)
+            .set_threads(2)
+            .set_rng_seed(5)
+            .set_deterministic(true);
+        let comparison = compare(builder, 2_000).unwrap();
+        assert!(comparison.hive_wins());
+        let (hive, random_search) = (&comparison.hive, &comparison.random_search);
+        assert!(hive.evaluations >= 2_000);
+        assert_eq!(random_search.evaluations, hive.evaluations);
+        for trajectory in &[hive, random_search] {
+            assert!(trajectory.improvements.windows(2).all(|w| w[1].fitness > w[0].fitness));
+            assert_eq!(trajectory.best_at(trajectory.evaluations), Some(trajectory.best.fitness));
+        }
+        assert_eq!(random_search.best_at(0), None);
+        assert!(hive.best_at(1_000).unwrap() > random_search.best_at(1_000).unwrap());
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use context::Context;
+use executor::Executor;
+use fitness::FitnessPolicy;
+use hive::HiveBuilder;
+use limit::LimitStrategy;
+use niching::Niching;
+use objective::Objective;
+use scaling::Scaling;
+use selection::Selection;
+use priority::WorkerPriority;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The plain settings of a hive, for keeping experiment parameters in a file.
+///
+/// Each setting corresponds to a [`HiveBuilder`](struct.HiveBuilder.html)
+/// method, and is left at the builder's default if it is `None`. With the
+/// `serde` feature enabled, a config can be deserialized from TOML, JSON or
+/// any other format serde supports, where every field but `workers` may be
+/// left out. A config is turned into a builder with
+/// [`HiveBuilder::from_config`](struct.HiveBuilder.html#method.from_config).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::{HiveBuilder, HiveConfig};
+/// use abc::scaling::Scaling;
+/// use abc::vector::BoundedVecContext;
+///
+/// let mut config = HiveConfig::new(8);
+/// config.retries = Some(20);
+/// config.scaling = Some(Scaling::Rank);
+///
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+/// let hive = HiveBuilder::from_config(context, &config).build().unwrap();
+/// assert!(hive.run_for_rounds(50).unwrap().fitness > 59.0);
+/// # }
+/// ```
+pub struct HiveConfig {
+    /// Number of working candidates.
+    pub workers: usize,
+
+    /// See [`set_observers`](struct.HiveBuilder.html#method.set_observers).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub observers: Option<usize>,
+
+    /// See [`set_retries`](struct.HiveBuilder.html#method.set_retries).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub retries: Option<usize>,
+
+    /// See [`set_threads`](struct.HiveBuilder.html#method.set_threads).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub threads: Option<usize>,
+
+    /// See [`set_scaling`](struct.HiveBuilder.html#method.set_scaling).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scaling: Option<Scaling>,
+
+    /// See [`set_limit_strategy`](struct.HiveBuilder.html#method.set_limit_strategy).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limit: Option<LimitStrategy>,
+
+    /// See [`set_selection`](struct.HiveBuilder.html#method.set_selection).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selection: Option<Selection>,
+
+    /// See [`set_worker_priority`](struct.HiveBuilder.html#method.set_worker_priority).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub worker_priority: Option<WorkerPriority>,
+
+    /// See [`set_niching`](struct.HiveBuilder.html#method.set_niching).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub niching: Option<Niching>,
+
+    /// See [`set_objective`](struct.HiveBuilder.html#method.set_objective).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub objective: Option<Objective>,
+
+    /// See [`set_fitness_policy`](struct.HiveBuilder.html#method.set_fitness_policy).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fitness_policy: Option<FitnessPolicy>,
+
+    /// See [`set_panic_tolerance`](struct.HiveBuilder.html#method.set_panic_tolerance).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub panic_tolerance: Option<usize>,
+
+    /// See [`set_variants_per_visit`](struct.HiveBuilder.html#method.set_variants_per_visit).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variants_per_visit: Option<usize>,
+
+    /// See [`set_executor`](struct.HiveBuilder.html#method.set_executor).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub executor: Option<Executor>,
+
+    /// See [`set_synchronous`](struct.HiveBuilder.html#method.set_synchronous).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub synchronous: Option<bool>,
+
+    /// See [`set_deterministic`](struct.HiveBuilder.html#method.set_deterministic).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deterministic: Option<bool>,
+
+    /// See [`set_rng_seed`](struct.HiveBuilder.html#method.set_rng_seed).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: Option<u64>,
+
+    /// See [`set_time_budget`](struct.HiveBuilder.html#method.set_time_budget),
+    /// in seconds.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_budget: Option<f64>,
+
+    /// See [`set_evaluation_budget`](struct.HiveBuilder.html#method.set_evaluation_budget).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evaluation_budget: Option<usize>,
+
+    /// See [`set_evaluation_retries`](struct.HiveBuilder.html#method.set_evaluation_retries),
+    /// as a number of retries and a backoff in seconds.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub evaluation_retries: Option<(usize, f64)>,
+
+    /// See [`set_stagnation_limit`](struct.HiveBuilder.html#method.set_stagnation_limit),
+    /// as a number of rounds and an epsilon.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stagnation: Option<(usize, f64)>,
+
+    /// See [`set_duplicate_distance`](struct.HiveBuilder.html#method.set_duplicate_distance).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub duplicate_distance: Option<f64>,
+
+    /// See [`set_pareto_capacity`](struct.HiveBuilder.html#method.set_pareto_capacity).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pareto_capacity: Option<usize>,
+
+    /// See [`set_elite_capacity`](struct.HiveBuilder.html#method.set_elite_capacity).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elite_capacity: Option<usize>,
+
+    /// See [`set_slot_history`](struct.HiveBuilder.html#method.set_slot_history).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slot_history: Option<usize>,
+}
+
+impl HiveConfig {
+    /// Creates a config with `workers` working candidates, and every other
+    /// setting left at the builder's default.
+    pub fn new(workers: usize) -> HiveConfig {
+        HiveConfig {
+            workers,
+            observers: None,
+            retries: None,
+            threads: None,
+            scaling: None,
+            limit: None,
+            selection: None,
+            worker_priority: None,
+            niching: None,
+            objective: None,
+            fitness_policy: None,
+            panic_tolerance: None,
+            variants_per_visit: None,
+            executor: None,
+            synchronous: None,
+            deterministic: None,
+            seed: None,
+            time_budget: None,
+            evaluation_budget: None,
+            evaluation_retries: None,
+            stagnation: None,
+            duplicate_distance: None,
+            pareto_capacity: None,
+            elite_capacity: None,
+            slot_history: None,
+        }
+    }
+
+    /// Applies every setting but `workers` to `builder`.
+    ///
+    /// Settings that make no sense, such as an elite capacity of 0, are
+    /// reported by [`build`](struct.HiveBuilder.html#method.build).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time budget is negative.
+    pub fn apply<Ctx: Context>(&self, mut builder: HiveBuilder<Ctx>) -> HiveBuilder<Ctx> {
+        if let Some(observers) = self.observers {
+            builder = builder.set_observers(observers);
+        }
+        if let Some(retries) = self.retries {
+            builder = builder.set_retries(retries);
+        }
+        if let Some(threads) = self.threads {
+            builder = builder.set_threads(threads);
+        }
+        if let Some(scaling) = self.scaling {
+            builder = builder.set_scaling(scaling.build());
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.set_limit_strategy(limit);
+        }
+        if let Some(selection) = self.selection {
+            builder = builder.set_selection(selection);
+        }
+        if let Some(priority) = self.worker_priority {
+            builder = builder.set_worker_priority(priority);
+        }
+        if let Some(niching) = self.niching {
+            builder = builder.set_niching(niching);
+        }
+        if let Some(objective) = self.objective {
+            builder = builder.set_objective(objective);
+        }
+        if let Some(policy) = self.fitness_policy {
+            builder = builder.set_fitness_policy(policy);
+        }
+        if let Some(failures) = self.panic_tolerance {
+            builder = builder.set_panic_tolerance(failures);
+        }
+        if let Some(variants) = self.variants_per_visit {
+            builder = builder.set_variants_per_visit(variants);
+        }
+        if let Some(executor) = self.executor {
+            builder = builder.set_executor(executor);
+        }
+        if let Some(synchronous) = self.synchronous {
+            builder = builder.set_synchronous(synchronous);
+        }
+        if let Some(deterministic) = self.deterministic {
+            builder = builder.set_deterministic(deterministic);
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.set_rng_seed(seed);
+        }
+        if let Some(seconds) = self.time_budget {
+            builder = builder.set_time_budget(Duration::from_secs_f64(seconds));
+        }
+        if let Some(evaluations) = self.evaluation_budget {
+            builder = builder.set_evaluation_budget(evaluations);
+        }
+        if let Some((retries, seconds)) = self.evaluation_retries {
+            builder = builder.set_evaluation_retries(retries, Duration::from_secs_f64(seconds));
+        }
+        if let Some((rounds, epsilon)) = self.stagnation {
+            builder = builder.set_stagnation_limit(rounds, epsilon);
+        }
+        if let Some(distance) = self.duplicate_distance {
+            builder = builder.set_duplicate_distance(distance);
+        }
+        if let Some(capacity) = self.pareto_capacity {
+            builder = builder.set_pareto_capacity(capacity);
+        }
+        if let Some(capacity) = self.elite_capacity {
+            builder = builder.set_elite_capacity(capacity);
+        }
+        if let Some(depth) = self.slot_history {
+            builder = builder.set_slot_history(depth);
+        }
+        builder
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    extern crate serde_json;
+
+    use super::*;
+
+    #[test]
+    fn loads_partial_json() {
+        let json = r#"{"workers": 12, "retries": 30, "scaling": {"Power": {"k": 2.0}}, "stagnation": [50, 0.001]}"#;
+        let config: HiveConfig = serde_json::from_str(json).unwrap();
+        let mut expected = HiveConfig::new(12);
+        expected.retries = Some(30);
+        expected.scaling = Some(Scaling::Power { k: 2.0 });
+        expected.stagnation = Some((50, 0.001));
+        assert_eq!(config, expected);
+    }
+}
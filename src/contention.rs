@@ -0,0 +1,147 @@
+//! Time spent waiting on the hive's locks, with the `contention` feature.
+//!
+//! Every lock that the threaded hive takes is timed, and the waits are
+//! totalled by the [`Stage`](../enum.Stage.html) whose data the lock
+//! protects. This shows where the threads queue up, so that changes to the
+//! locking can be judged on numbers. The totals cover every hive in the
+//! process, and the timing itself costs a little, so the feature is meant
+//! for benchmarks and profiling rather than production builds.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::contention;
+//! use abc::vector::BoundedVecContext;
+//!
+//! contention::reset();
+//! let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+//! let hive = HiveBuilder::new(context, 10).set_threads(4).build().unwrap();
+//! hive.run_for_rounds(20).unwrap();
+//! for wait in contention::report() {
+//!     println!("{}: {} acquisitions, {:?} waiting", wait.stage, wait.acquisitions, wait.waiting);
+//! }
+//! # }
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use result::Stage;
+
+/// Every stage, in declaration order, so that each has a slot below.
+const STAGES: [Stage; 21] = [Stage::Make,
+                             Stage::Explore,
+                             Stage::EvaluateFitness,
+                             Stage::Candidate,
+                             Stage::Best,
+                             Stage::Tasks,
+                             Stage::Scouting,
+                             Stage::Stream,
+                             Stage::Rng,
+                             Stage::Distance,
+                             Stage::Stopping,
+                             Stage::Pareto,
+                             Stage::Elites,
+                             Stage::Stats,
+                             Stage::HiveObserver,
+                             Stage::ScoutStrategy,
+                             Stage::InitStrategy,
+                             Stage::Opposite,
+                             Stage::LocalSearch,
+                             Stage::Context,
+                             Stage::Config];
+
+static ACQUISITIONS: [AtomicU64; 21] = [const { AtomicU64::new(0) }; 21];
+static WAITING: [AtomicU64; 21] = [const { AtomicU64::new(0) }; 21];
+static LONGEST: [AtomicU64; 21] = [const { AtomicU64::new(0) }; 21];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The waits for the locks on one stage's data.
+pub struct LockWait {
+    /// The data the locks protect.
+    pub stage: Stage,
+
+    /// The number of times the locks were taken.
+    pub acquisitions: u64,
+
+    /// The total time spent waiting for them.
+    pub waiting: Duration,
+
+    /// The longest single wait.
+    pub longest: Duration,
+}
+
+impl LockWait {
+    /// Returns the average wait for each acquisition.
+    pub fn mean(&self) -> Duration {
+        match self.acquisitions {
+            0 => Duration::from_secs(0),
+            n => Duration::from_nanos((self.waiting.as_nanos() / n as u128) as u64),
+        }
+    }
+}
+
+/// Returns the waits recorded since the last [`reset`](fn.reset.html), for
+/// each stage whose locks were taken, longest total first.
+pub fn report() -> Vec<LockWait> {
+    let mut waits = STAGES.iter()
+                          .map(|&stage| {
+                              let slot = stage as usize;
+                              LockWait {
+                                  stage,
+                                  acquisitions: ACQUISITIONS[slot].load(Ordering::Relaxed),
+                                  waiting: Duration::from_nanos(WAITING[slot].load(Ordering::Relaxed)),
+                                  longest: Duration::from_nanos(LONGEST[slot].load(Ordering::Relaxed)),
+                              }
+                          })
+                          .filter(|wait| wait.acquisitions > 0)
+                          .collect::<Vec<_>>();
+    waits.sort_by_key(|wait| ::std::cmp::Reverse(wait.waiting));
+    waits
+}
+
+/// Clears the waits recorded so far.
+pub fn reset() {
+    for slot in 0..STAGES.len() {
+        ACQUISITIONS[slot].store(0, Ordering::Relaxed);
+        WAITING[slot].store(0, Ordering::Relaxed);
+        LONGEST[slot].store(0, Ordering::Relaxed);
+    }
+}
+
+/// Takes a lock with `acquire`, recording how long it took against `stage`.
+pub(crate) fn timed<T, F: FnOnce() -> T>(stage: Stage, acquire: F) -> T {
+    let started = Instant::now();
+    let guard = acquire();
+    let waited = started.elapsed().as_nanos() as u64;
+    let slot = stage as usize;
+    ACQUISITIONS[slot].fetch_add(1, Ordering::Relaxed);
+    WAITING[slot].fetch_add(waited, Ordering::Relaxed);
+    LONGEST[slot].fetch_max(waited, Ordering::Relaxed);
+    guard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use result::MutexExt;
+
+    #[test]
+    fn stages_fill_their_slots() {
+        assert!(STAGES.iter().enumerate().all(|(slot, &stage)| stage as usize == slot));
+    }
+
+    #[test]
+    fn waits_are_recorded() {
+        let mutex = Mutex::new(0);
+        for _ in 0..3 {
+            *mutex.lock_at(Stage::Config).unwrap() += 1;
+        }
+        let wait = report().into_iter().find(|wait| wait.stage == Stage::Config).unwrap();
+        assert!(wait.acquisitions >= 3);
+        assert!(wait.longest <= wait.waiting);
+    }
+}
@@ -1,4 +1,41 @@
+extern crate rand;
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use self::rand::Rng;
+
 use candidate::Candidate;
+use fitness::Fitness;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The result of [`Context::evaluate`](trait.Context.html#method.evaluate).
+pub struct Evaluation<F = f64> {
+    /// The solution's fitness, as from `evaluate_fitness`.
+    pub fitness: F,
+
+    /// A score for reporting, which the hive doesn't use.
+    pub score: Option<f64>,
+}
+
+impl<F> Evaluation<F> {
+    /// An evaluation with a fitness, but no score.
+    pub fn new(fitness: F) -> Evaluation<F> {
+        Evaluation {
+            fitness,
+            score: None,
+        }
+    }
+
+    /// An evaluation with both a fitness and a score.
+    pub fn with_score(fitness: F, score: f64) -> Evaluation<F> {
+        Evaluation {
+            fitness,
+            score: Some(score),
+        }
+    }
+}
 
 /// Context for generating and evaluating solutions.
 ///
@@ -23,6 +60,7 @@ use candidate::Candidate;
 /// extern crate rand;
 /// # extern crate abc; fn main() {
 ///
+/// use std::sync::Arc;
 /// use abc::{Context, Candidate};
 /// use rand::Rng;
 ///
@@ -30,6 +68,7 @@ use candidate::Candidate;
 ///
 /// impl Context for Ctx {
 ///     type Solution = i32;
+///     type Fitness = f64;
 ///
 ///     fn make(&self) -> i32 {
 ///         let mut rng = rand::thread_rng();
@@ -41,7 +80,7 @@ use candidate::Candidate;
 ///         1f64 / *solution as f64
 ///     }
 ///
-///     fn explore(&self, field: &[Candidate<i32>], n: usize) -> i32 {
+///     fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
 ///         let mut rng = rand::thread_rng();
 ///         field[n].solution + rng.gen_range(-10, 10)
 ///     }
@@ -56,8 +95,19 @@ pub trait Context : Send + Sync {
     /// be a pair of X and Y coordinates. For more complicated tasks, like
     /// playing a game, this could be a struct with fields for the various
     /// tuning knobs relevant to gameplay.
+    ///
+    /// Solutions can't borrow from the context. To share a large dataset
+    /// with them without copying it, see the [`shared`](shared/index.html)
+    /// module.
     type Solution : Clone + Send + Sync + 'static;
 
+    /// Type of the fitness of a solution.
+    ///
+    /// This is usually `f64`, but may be any [`Fitness`](trait.Fitness.html),
+    /// such as an integer for exact scores, or a tuple to rank solutions
+    /// lexicographically.
+    type Fitness : Fitness;
+
     /// Generates a fresh, random solution.
     fn make(&self) -> Self::Solution;
 
@@ -68,12 +118,16 @@ pub trait Context : Send + Sync {
     /// are diverse goals for optimization, the user must implement their
     /// own `evaluate_fitness` function.
     ///
+    /// To minimize a cost instead, return the cost and set
+    /// [`Objective::Minimize`](enum.Objective.html#variant.Minimize) on the
+    /// builder.
+    ///
     /// The user may wish to use information from the other solutions to
     /// evaluate a given solution. So, rather than simply providing the
     /// solution to be varied, `evaluate_fitness` receives a slice of solution refs
     /// that give information on the existing solutions, and the index of the
     /// solution to be evaluated.
-    fn evaluate_fitness(&self, solution: &Self::Solution) -> f64;
+    fn evaluate_fitness(&self, solution: &Self::Solution) -> Self::Fitness;
 
     /// Looks "near" an existing solution.
     ///
@@ -82,5 +136,277 @@ pub trait Context : Send + Sync {
     /// solution to be varied, `explore` receives a slice of solution refs
     /// that give information on the existing solutions, and the index of the
     /// solution to be modified.
-    fn explore(&self, field: &[Candidate<Self::Solution>], index: usize) -> Self::Solution;
+    fn explore(&self, field: &[Arc<Candidate<Self::Solution, Self::Fitness>>], index: usize) -> Self::Solution;
+
+    /// Scores a solution, along with a user-meaningful score for reporting.
+    ///
+    /// The hive calls this method rather than `evaluate_fitness`, by way of
+    /// [`evaluate_with_scratch`](#method.evaluate_with_scratch). The fitness
+    /// steers the search, while the score (if any) is kept on the
+    /// [`Candidate`](struct.Candidate.html) untouched, so that the true
+    /// objective can be reported without re-deriving it from the fitness.
+    ///
+    /// The default implementation defers to `evaluate_fitness`, without a
+    /// score.
+    fn evaluate(&self, solution: &Self::Solution) -> Evaluation<Self::Fitness> {
+        Evaluation::new(self.evaluate_fitness(solution))
+    }
+
+    /// Scores a solution against several objectives, each to be maximized.
+    ///
+    /// Returning `Some` opts into multi-objective optimization: the hive will
+    /// keep an archive of the non-dominated solutions it finds, available
+    /// through [`Hive::pareto_front`](struct.Hive.html#method.pareto_front).
+    /// The scalar `evaluate_fitness` still steers the colony, so it should
+    /// reward progress on the objectives (a weighted sum is a common choice).
+    ///
+    /// The default implementation returns `None`.
+    fn evaluate_objectives(&self, solution: &Self::Solution) -> Option<Vec<f64>> {
+        let _ = solution;
+        None
+    }
+
+    /// Measures how far apart two solutions are.
+    ///
+    /// Returning `Some` lets the hive report the diversity of its working
+    /// candidates in each round's [`RoundStats`](stats/struct.RoundStats.html),
+    /// and reject near-duplicate variants (see
+    /// [`set_duplicate_distance`](struct.HiveBuilder.html#method.set_duplicate_distance)).
+    /// The diversity is measured over every pair of working candidates, so
+    /// this should be cheap to compute.
+    ///
+    /// The default implementation returns `None`.
+    fn distance(&self, a: &Self::Solution, b: &Self::Solution) -> Option<f64> {
+        let _ = (a, b);
+        None
+    }
+
+    /// Generates the opposite of a solution, for opposition-based learning.
+    ///
+    /// The opposite is a point on the far side of the search space; for a
+    /// point *x* within bounds [*a*, *b*], it is usually *a* + *b* − *x*. The
+    /// hive only calls this when opposition is turned on with
+    /// [`set_opposition`](struct.HiveBuilder.html#method.set_opposition).
+    ///
+    /// The default implementation ignores `solution` and defers to `make`.
+    fn opposite(&self, solution: &Self::Solution) -> Self::Solution {
+        let _ = solution;
+        self.make()
+    }
+
+    /// Generates a fresh, random solution, drawing on the hive's RNG.
+    ///
+    /// The hive always calls this method rather than `make`. By default, it
+    /// ignores `rng` and defers to `make`. If a run needs to be reproducible
+    /// (see [`set_rng_seed`](struct.HiveBuilder.html#method.set_rng_seed)),
+    /// override this method and take all of its randomness from `rng`.
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Self::Solution {
+        let _ = rng;
+        self.make()
+    }
+
+    /// Looks "near" an existing solution, drawing on the hive's RNG.
+    ///
+    /// As with [`make_with_rng`](#method.make_with_rng), the default
+    /// implementation ignores `rng` and defers to `explore`.
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Self::Solution, Self::Fitness>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Self::Solution {
+        let _ = rng;
+        self.explore(field, index)
+    }
+
+    /// Looks "near" an existing solution, with knowledge of the global best.
+    ///
+    /// This supports the gbest-guided ABC (GABC) of Zhu and Kwong, in which
+    /// variants are pulled toward the best solution found so far:
+    ///
+    /// <center>*v*<sub>*i*</sub> = *x*<sub>*i*</sub>
+    /// + φ(*x*<sub>*i*</sub> − *x*<sub>*k*</sub>)
+    /// + ψ(*best* − *x*<sub>*i*</sub>)</center>
+    ///
+    /// The hive explores through this method, by way of
+    /// [`explore_with_scratch`](#method.explore_with_scratch), passing a
+    /// snapshot of the best candidate found so far, which may or may not be
+    /// in `field`. The default implementation ignores `best` and defers to
+    /// [`explore_with_rng`](#method.explore_with_rng).
+    fn explore_guided<R: Rng>(&self,
+                              field: &[Arc<Candidate<Self::Solution, Self::Fitness>>],
+                              index: usize,
+                              best: &Candidate<Self::Solution, Self::Fitness>,
+                              rng: &mut R)
+                              -> Self::Solution {
+        let _ = best;
+        self.explore_with_rng(field, index, rng)
+    }
+
+    /// Declares how many other candidates `explore` needs to see, for
+    /// hives whose field is too large to snapshot for every task.
+    ///
+    /// Returning `Some(k)` lets the hive's workers take only a partial
+    /// snapshot: `explore` is given a field with the candidate being
+    /// explored at index 0, followed by `k` partners drawn at random from
+    /// the other working candidates. The candidate explored is still the one
+    /// chosen by the builder's
+    /// [worker priority](struct.HiveBuilder.html#method.set_worker_priority).
+    /// The observers, which choose among every candidate, still see the
+    /// whole field, as do all tasks while duplicate rejection, opposition, a
+    /// fitness-based limit or deterministic mode is on, or if `k` would cover
+    /// the whole field.
+    ///
+    /// The default implementation returns `None`, so that `explore` always
+    /// sees the whole field.
+    fn partners(&self) -> Option<usize> {
+        None
+    }
+
+    /// Called once as each run of the hive starts, before any task.
+    ///
+    /// The default implementation does nothing.
+    fn on_start(&self) {}
+
+    /// Called once as each run of the hive stops, whether or not it
+    /// succeeded.
+    ///
+    /// The default implementation does nothing.
+    fn on_stop(&self) {}
+
+    /// Makes scratch state for one of the hive's threads, such as a
+    /// database connection or a buffer to evaluate into.
+    ///
+    /// The hive keeps the states it has made, and lends one to each thread
+    /// that evaluates or explores, so that no two threads use the same state
+    /// at once. A new state is only made when every existing one is in use,
+    /// so there are about as many as there are threads. A state is dropped,
+    /// rather than reused, if the call it was lent to panics.
+    ///
+    /// The default implementation returns an empty state, in which case the
+    /// hive doesn't keep any.
+    fn init_scratch(&self) -> Scratch {
+        Scratch::empty()
+    }
+
+    /// Scores a solution, using scratch state from
+    /// [`init_scratch`](#method.init_scratch).
+    ///
+    /// The hive always calls this method to evaluate. The default
+    /// implementation ignores `scratch` and defers to
+    /// [`evaluate`](#method.evaluate).
+    fn evaluate_with_scratch(&self, solution: &Self::Solution, scratch: &mut Scratch) -> Evaluation<Self::Fitness> {
+        let _ = scratch;
+        self.evaluate(solution)
+    }
+
+    /// Looks "near" an existing solution, using scratch state from
+    /// [`init_scratch`](#method.init_scratch).
+    ///
+    /// The hive always calls this method to explore. The default
+    /// implementation ignores `scratch` and defers to
+    /// [`explore_guided`](#method.explore_guided).
+    fn explore_with_scratch<R: Rng>(&self,
+                                    field: &[Arc<Candidate<Self::Solution, Self::Fitness>>],
+                                    index: usize,
+                                    best: &Candidate<Self::Solution, Self::Fitness>,
+                                    rng: &mut R,
+                                    scratch: &mut Scratch)
+                                    -> Self::Solution {
+        let _ = scratch;
+        self.explore_guided(field, index, best, rng)
+    }
+}
+
+#[derive(Debug, Default)]
+/// Scratch state for a context, made by
+/// [`Context::init_scratch`](trait.Context.html#method.init_scratch).
+///
+/// The state can be of any type, and is recovered with
+/// [`get_mut`](#method.get_mut).
+///
+/// # Examples
+///
+/// ```
+/// use abc::Scratch;
+///
+/// let mut scratch = Scratch::new(Vec::<f64>::with_capacity(64));
+/// scratch.get_mut::<Vec<f64>>().unwrap().push(1.0);
+/// assert!(scratch.get_mut::<String>().is_none());
+/// ```
+pub struct Scratch(Option<Box<dyn Any + Send>>);
+
+impl Scratch {
+    /// Wraps `state`.
+    pub fn new<T: Any + Send>(state: T) -> Scratch {
+        Scratch(Some(Box::new(state)))
+    }
+
+    /// Creates a scratch state holding nothing.
+    pub fn empty() -> Scratch {
+        Scratch(None)
+    }
+
+    /// Returns whether there is no state.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns the state, if there is some and it is a `T`.
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.0.as_mut().and_then(|state| state.downcast_mut())
+    }
+}
+
+/// A context made of three closures, for problems too simple to need a
+/// type of their own.
+///
+/// The closures make, score and explore solutions, as
+/// [`make`](trait.Context.html#tymethod.make),
+/// [`evaluate_fitness`](trait.Context.html#tymethod.evaluate_fitness) and
+/// [`explore`](trait.Context.html#tymethod.explore) do. Usually, it is
+/// built by [`HiveBuilder::from_fns`](struct.HiveBuilder.html#method.from_fns).
+pub struct FnContext<S, M, F, E> {
+    make: M,
+    fitness: F,
+    explore: E,
+    solution: PhantomData<fn() -> S>,
+}
+
+impl<S, M, F, E> FnContext<S, M, F, E>
+    where S: Clone + Send + Sync + 'static,
+          M: Fn() -> S + Send + Sync,
+          F: Fn(&S) -> f64 + Send + Sync,
+          E: Fn(&[Arc<Candidate<S>>], usize) -> S + Send + Sync
+{
+    /// Wraps the closures in a context.
+    pub fn new(make: M, fitness: F, explore: E) -> FnContext<S, M, F, E> {
+        FnContext {
+            make,
+            fitness,
+            explore,
+            solution: PhantomData,
+        }
+    }
+}
+
+impl<S, M, F, E> Context for FnContext<S, M, F, E>
+    where S: Clone + Send + Sync + 'static,
+          M: Fn() -> S + Send + Sync,
+          F: Fn(&S) -> f64 + Send + Sync,
+          E: Fn(&[Arc<Candidate<S>>], usize) -> S + Send + Sync
+{
+    type Solution = S;
+    type Fitness = f64;
+
+    fn make(&self) -> S {
+        (self.make)()
+    }
+
+    fn evaluate_fitness(&self, solution: &S) -> f64 {
+        (self.fitness)(solution)
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<S>>], index: usize) -> S {
+        (self.explore)(field, index)
+    }
 }
@@ -0,0 +1,250 @@
+//! Ready-made context for permutation problems.
+//!
+//! Many combinatorial problems, such as the travelling salesman, scheduling
+//! and assignment, search over orderings of a fixed set of items. For those,
+//! [`PermutationContext`](struct.PermutationContext.html) represents each
+//! solution as a permutation of `0..size`, and explores by applying one of
+//! the classic [`Neighborhood`](enum.Neighborhood.html) moves, so that only
+//! the fitness function needs to be written. The moves are also available on
+//! their own, as [`swap`](fn.swap.html), [`insert`](fn.insert.html) and
+//! [`two_opt`](fn.two_opt.html).
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{HiveBuilder, Objective};
+//! use abc::discrete::PermutationContext;
+//!
+//! // Visit five points on a line in as short a tour as possible.
+//! let points = [4f64, 1.0, 3.0, 0.0, 2.0];
+//! let context = PermutationContext::new(points.len(), move |tour: &[usize]| {
+//!     (0..tour.len()).map(|i| (points[tour[i]] - points[tour[(i + 1) % tour.len()]]).abs()).sum::<f64>()
+//! });
+//! let hive = HiveBuilder::new(context, 10).set_objective(Objective::Minimize).build().unwrap();
+//! assert_eq!(hive.run_for_rounds(100).unwrap().raw_fitness, 8.0);
+//! # }
+//! ```
+//!
+//! See `examples/tsp.rs` for a travelling salesman problem.
+
+extern crate rand;
+
+use std::sync::Arc;
+
+use self::rand::{thread_rng, Rng};
+
+use candidate::Candidate;
+use context::Context;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// A move from one permutation to a neighbouring one.
+pub enum Neighborhood {
+    /// Exchanges two items. See [`swap`](fn.swap.html).
+    Swap,
+
+    /// Moves one item to another position. See [`insert`](fn.insert.html).
+    Insert,
+
+    /// Reverses a run of items. See [`two_opt`](fn.two_opt.html).
+    TwoOpt,
+}
+
+impl Neighborhood {
+    /// Applies the move to `permutation`, at random positions.
+    pub fn apply<R: Rng>(&self, permutation: &mut [usize], rng: &mut R) {
+        match *self {
+            Neighborhood::Swap => swap(permutation, rng),
+            Neighborhood::Insert => insert(permutation, rng),
+            Neighborhood::TwoOpt => two_opt(permutation, rng),
+        }
+    }
+}
+
+/// Draws two different positions in a slice of length `len`, in order.
+fn positions<R: Rng>(len: usize, rng: &mut R) -> (usize, usize) {
+    let i = rng.gen_range(0, len);
+    let mut j = rng.gen_range(0, len - 1);
+    if j >= i {
+        j += 1;
+    }
+    (i.min(j), i.max(j))
+}
+
+/// Exchanges the items at two random positions.
+///
+/// Permutations of fewer than two items are left as they are.
+pub fn swap<R: Rng>(permutation: &mut [usize], rng: &mut R) {
+    if permutation.len() < 2 {
+        return;
+    }
+    let (i, j) = positions(permutation.len(), rng);
+    permutation.swap(i, j);
+}
+
+/// Takes the item at one random position and inserts it at another,
+/// shifting the items in between.
+///
+/// Permutations of fewer than two items are left as they are.
+pub fn insert<R: Rng>(permutation: &mut [usize], rng: &mut R) {
+    if permutation.len() < 2 {
+        return;
+    }
+    let (i, j) = positions(permutation.len(), rng);
+    if rng.gen() {
+        permutation[i..j + 1].rotate_left(1);
+    } else {
+        permutation[i..j + 1].rotate_right(1);
+    }
+}
+
+/// Reverses the items between two random positions, inclusive.
+///
+/// For a tour, this is the 2-opt move: it removes two edges and reconnects
+/// the tour the other way, which untangles crossed paths.
+///
+/// Permutations of fewer than two items are left as they are.
+pub fn two_opt<R: Rng>(permutation: &mut [usize], rng: &mut R) {
+    if permutation.len() < 2 {
+        return;
+    }
+    let (i, j) = positions(permutation.len(), rng);
+    permutation[i..j + 1].reverse();
+}
+
+/// Context for searching permutations of `0..size`.
+///
+/// New solutions are drawn uniformly at random. Exploration copies the
+/// candidate and applies one move from a randomly chosen neighborhood; by
+/// default, swap, insertion and 2-opt are equally likely.
+pub struct PermutationContext<F> {
+    size: usize,
+    neighborhoods: Vec<Neighborhood>,
+    fitness: F,
+}
+
+impl<F> PermutationContext<F>
+    where F: Fn(&[usize]) -> f64 + Send + Sync
+{
+    /// Creates a context for permutations of `size` items.
+    ///
+    /// * `size` - Number of items in each permutation.
+    /// * `fitness` - Scores a solution (the algorithm will maximize this,
+    ///   unless the hive is set to minimize).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize, fitness: F) -> PermutationContext<F> {
+        if size == 0 {
+            panic!("PermutationContext must have at least one item.");
+        }
+        PermutationContext {
+            size,
+            neighborhoods: vec![Neighborhood::Swap, Neighborhood::Insert, Neighborhood::TwoOpt],
+            fitness,
+        }
+    }
+
+    /// Sets the neighborhoods that exploration chooses between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `neighborhoods` is empty.
+    pub fn with_neighborhoods(mut self, neighborhoods: Vec<Neighborhood>) -> PermutationContext<F> {
+        if neighborhoods.is_empty() {
+            panic!("PermutationContext must have at least one neighborhood.");
+        }
+        self.neighborhoods = neighborhoods;
+        self
+    }
+
+    /// Returns the number of items in each permutation.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the neighborhoods that exploration chooses between.
+    pub fn neighborhoods(&self) -> &[Neighborhood] {
+        &self.neighborhoods
+    }
+}
+
+impl<F> Context for PermutationContext<F>
+    where F: Fn(&[usize]) -> f64 + Send + Sync
+{
+    type Solution = Vec<usize>;
+    type Fitness = f64;
+
+    fn make(&self) -> Vec<usize> {
+        self.make_with_rng(&mut thread_rng())
+    }
+
+    fn evaluate_fitness(&self, solution: &Vec<usize>) -> f64 {
+        (self.fitness)(solution)
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<Vec<usize>>>], index: usize) -> Vec<usize> {
+        self.explore_with_rng(field, index, &mut thread_rng())
+    }
+
+    /// The number of positions at which the permutations differ.
+    fn distance(&self, a: &Vec<usize>, b: &Vec<usize>) -> Option<f64> {
+        Some(a.iter().zip(b.iter()).filter(|&(x, y)| x != y).count() as f64)
+    }
+
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<usize> {
+        let mut permutation = (0..self.size).collect::<Vec<_>>();
+        rng.shuffle(&mut permutation);
+        permutation
+    }
+
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Vec<usize>>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Vec<usize> {
+        let mut variant = field[index].solution.clone();
+        let neighborhood = self.neighborhoods[rng.gen_range(0, self.neighborhoods.len())];
+        neighborhood.apply(&mut variant, rng);
+        variant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_permutation(permutation: &[usize]) -> bool {
+        let mut sorted = permutation.to_vec();
+        sorted.sort();
+        sorted == (0..permutation.len()).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn moves_keep_permutations() {
+        let mut rng = thread_rng();
+        for neighborhood in [Neighborhood::Swap, Neighborhood::Insert, Neighborhood::TwoOpt] {
+            let mut permutation = (0..8).collect::<Vec<_>>();
+            for _ in 0..100 {
+                let before = permutation.clone();
+                neighborhood.apply(&mut permutation, &mut rng);
+                assert!(is_permutation(&permutation));
+                assert!(permutation != before);
+            }
+        }
+        let mut single = vec![0];
+        two_opt(&mut single, &mut rng);
+        assert_eq!(single, vec![0]);
+    }
+
+    #[test]
+    fn explores_permutations() {
+        let context = PermutationContext::new(6, |p: &[usize]| 1.0 + p[0] as f64)
+            .with_neighborhoods(vec![Neighborhood::Insert]);
+        let field = vec![Arc::new(Candidate::new(context.make(), 1.0)), Arc::new(Candidate::new(context.make(), 1.0))];
+        assert!(is_permutation(&context.explore(&field, 0)));
+        assert_eq!(context.distance(&vec![0, 1, 2], &vec![0, 2, 1]), Some(2.0));
+    }
+}
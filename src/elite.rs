@@ -0,0 +1,81 @@
+use core::cmp::Ordering;
+
+use candidate::Candidate;
+use fitness::Fitness;
+use objective::Objective;
+
+/// A bounded set of the fittest distinct candidates seen so far.
+///
+/// Members are kept fittest first, as the objective
+/// [compares](enum.Objective.html#method.compare) them. Two candidates that
+/// compare as equal are taken to be the same solution, so that
+/// re-evaluations of one good solution don't crowd out the others.
+pub struct EliteArchive<S: Clone + Send + Sync + 'static, F = f64> {
+    capacity: usize,
+    objective: Objective,
+    members: Vec<Candidate<S, F>>,
+}
+
+impl<S: Clone + Send + Sync + 'static, F: Fitness> EliteArchive<S, F> {
+    /// Creates an empty archive that holds at most `capacity` members,
+    /// ranked by `objective`.
+    pub fn new(capacity: usize, objective: Objective) -> EliteArchive<S, F> {
+        EliteArchive {
+            capacity,
+            objective,
+            members: Vec::with_capacity(capacity + 1),
+        }
+    }
+
+    /// Offers a candidate to the archive.
+    ///
+    /// The candidate is only cloned and added if it is fitter than the least
+    /// fit member (or the archive has room), and no member is as fit.
+    /// Returns `true` if the archive changed.
+    pub fn insert(&mut self, candidate: &Candidate<S, F>) -> bool {
+        if candidate.fitness.is_nan() {
+            return false;
+        }
+        if self.members.len() >= self.capacity &&
+           self.members.last().is_none_or(|last| self.objective.compare(candidate, last) != Ordering::Greater) {
+            return false;
+        }
+        let position = match self.members.binary_search_by(|m| self.objective.compare(candidate, m)) {
+            Ok(_) => return false,
+            Err(position) => position,
+        };
+        self.members.insert(position, candidate.clone());
+        self.members.truncate(self.capacity);
+        true
+    }
+
+    /// Returns the members, fittest first.
+    pub fn members(&self) -> &[Candidate<S, F>] {
+        &self.members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_fittest_distinct() {
+        let mut archive = EliteArchive::new(3, Objective::Maximize);
+        assert!(archive.insert(&Candidate::new('a', 1.0)));
+        assert!(archive.insert(&Candidate::new('b', 3.0)));
+        assert!(!archive.insert(&Candidate::new('c', 3.0)));
+        assert!(archive.insert(&Candidate::new('d', 2.0)));
+        assert!(!archive.insert(&Candidate::new('e', 0.5)));
+        assert!(archive.insert(&Candidate::new('f', 2.5)));
+        let solutions = archive.members().iter().map(|c| c.solution).collect::<Vec<_>>();
+        assert_eq!(solutions, vec!['b', 'f', 'd']);
+
+        let mut archive = EliteArchive::new(2, Objective::Minimize);
+        for (solution, cost) in [('a', (2, 0)), ('b', (1, 9)), ('c', (1, 3)), ('d', (1, 3))] {
+            archive.insert(&Candidate::with_raw_fitness(solution, 0.0, cost));
+        }
+        let solutions = archive.members().iter().map(|c| c.solution).collect::<Vec<_>>();
+        assert_eq!(solutions, vec!['c', 'b']);
+    }
+}
@@ -0,0 +1,71 @@
+use context::Context;
+
+/// Scores whole batches of solutions at once, in place of the context.
+///
+/// Some fitness functions are cheap to run on many solutions together and
+/// expensive to run one at a time: a kernel on a GPU, or a simulator in
+/// another process. An evaluator set with
+/// [`HiveBuilder::set_evaluator`](struct.HiveBuilder.html#method.set_evaluator)
+/// takes over from [`Context::evaluate`](trait.Context.html#method.evaluate).
+/// The hive hands it every solution it has ready at once: the whole initial
+/// population, the variants of each visit (see
+/// [`set_variants_per_visit`](struct.HiveBuilder.html#method.set_variants_per_visit)),
+/// and every sample of a noisy solution. Other evaluations come one at a
+/// time, in batches of one.
+///
+/// Evaluators have no equivalent of an evaluation's score. The context still
+/// provides the objectives of multi-objective problems.
+///
+/// Any `Fn(&[S]) -> Vec<F>` is an evaluator, where `F` is the context's
+/// [`Fitness`](trait.Fitness.html) type, usually `f64`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::HiveBuilder;
+/// use abc::vector::BoundedVecContext;
+///
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |_: &[f64]| unreachable!());
+/// let hive = HiveBuilder::new(context, 10)
+///     .set_evaluator(|batch: &[Vec<f64>]| {
+///         batch.iter().map(|x| 60.0 - x[0] * x[0] - x[1] * x[1]).collect()
+///     })
+///     .build()
+///     .unwrap();
+/// assert!(hive.run_for_rounds(50).unwrap().fitness > 59.0);
+/// # }
+/// ```
+pub trait Evaluator<S, F = f64>: Send + Sync {
+    /// Returns the fitness of each solution, in order.
+    ///
+    /// # Panics
+    ///
+    /// The hive treats a panic, or a result of the wrong length, as a panic
+    /// in [`Context::evaluate_fitness`](trait.Context.html#tymethod.evaluate_fitness).
+    fn evaluate(&self, solutions: &[S]) -> Vec<F>;
+}
+
+impl<S, F, E> Evaluator<S, F> for E
+    where E: Fn(&[S]) -> Vec<F> + Send + Sync
+{
+    fn evaluate(&self, solutions: &[S]) -> Vec<F> {
+        self(solutions)
+    }
+}
+
+/// Adapts a context into an [`Evaluator`](trait.Evaluator.html), which
+/// scores each solution in turn with
+/// [`Context::evaluate`](trait.Context.html#method.evaluate).
+///
+/// This is how a hive with no evaluator behaves. It is useful for wrapping
+/// in evaluators of your own, for instance to send only some batches
+/// elsewhere.
+#[derive(Debug)]
+pub struct ContextEvaluator<Ctx>(pub Ctx);
+
+impl<Ctx: Context> Evaluator<Ctx::Solution, Ctx::Fitness> for ContextEvaluator<Ctx> {
+    fn evaluate(&self, solutions: &[Ctx::Solution]) -> Vec<Ctx::Fitness> {
+        solutions.iter().map(|solution| self.0.evaluate(solution).fitness).collect()
+    }
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use candidate::Candidate;
+use stats::RoundStats;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Something noteworthy that happened while the hive was running.
+///
+/// These are sent by [`Hive::stream_events`](struct.Hive.html#method.stream_events).
+pub enum HiveEvent<S: Clone + Send + Sync + 'static, F = f64> {
+    /// The hive found a new best candidate.
+    NewBest(Arc<Candidate<S, F>>),
+
+    /// A round ended.
+    Round {
+        /// Summary of the working candidates' fitnesses. The standard
+        /// deviation of fitness gives a rough measure of the population's
+        /// diversity.
+        stats: RoundStats,
+
+        /// The best of the working candidates at the end of the round.
+        best: Arc<Candidate<S, F>>,
+    },
+}
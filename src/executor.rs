@@ -0,0 +1,46 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Strategy for spreading the hive's work across threads.
+///
+/// Apart from `CurrentThread`, the number of threads is set by
+/// [`HiveBuilder::set_threads`](struct.HiveBuilder.html#method.set_threads).
+pub enum Executor {
+    /// Scoped worker threads, each claiming one task at a time.
+    ///
+    /// Since tasks are claimed individually, the rounds are staggered, and the
+    /// stopping rules are checked before every task. This is the default.
+    #[default]
+    Threads,
+
+    /// Scoped worker threads, sharing a lock-free queue of tasks.
+    ///
+    /// Each round's tasks are claimed at once and queued, and the threads
+    /// take tasks from the queue without locking. Only the thread that finds
+    /// the queue empty locks the task generator, to queue up the next round.
+    /// The rounds are still staggered, but with cheap fitness evaluation,
+    /// there is much less contention than with `Threads`. As with `Rayon`,
+    /// the stopping rules are only checked between rounds.
+    Queued,
+
+    /// A rayon thread pool, working through one round's tasks at a time.
+    ///
+    /// Each round's tasks are claimed at once, then run with a parallel
+    /// iterator, which avoids contention over the task generator when fitness
+    /// evaluation is cheap. As a consequence, the stopping rules are only
+    /// checked between rounds. Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    Rayon,
+
+    /// Everything on the calling thread, one task after another.
+    ///
+    /// No threads are started, whatever
+    /// [`set_threads`](struct.HiveBuilder.html#method.set_threads) says: the
+    /// initial population, each round's tasks, and several
+    /// [variants per visit](struct.HiveBuilder.html#method.set_variants_per_visit)
+    /// are all handled in turn. This suits targets without threads, such as
+    /// `wasm32-unknown-unknown`, as long as nothing else needs them: not
+    /// [`Hive::spawn`](struct.Hive.html#method.spawn), nor a time budget or
+    /// logger, which read the system clock. The stopping rules are only
+    /// checked between rounds.
+    CurrentThread,
+}
@@ -0,0 +1,194 @@
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// What to do with a fitness the hive can't work with.
+///
+/// The hive compares fitnesses to keep the better of two candidates, so a
+/// NaN or infinite fitness would quietly derail the search. The observers'
+/// roulette wheel also needs each candidate's weight (its fitness, after
+/// the [scaling function](scaling/index.html) and any
+/// [niching](enum.Niching.html)) to be finite and non-negative. Negative
+/// fitnesses are fine in themselves, as long as the scaling function (such
+/// as [`rank`](scaling/fn.rank.html)) or the
+/// [selection](enum.Selection.html) copes with them.
+pub enum FitnessPolicy {
+    /// Stops the run with an
+    /// [`Error::InvalidFitness`](enum.Error.html#variant.InvalidFitness).
+    /// This is the default.
+    #[default]
+    Reject,
+
+    /// Corrects the value and carries on.
+    ///
+    /// A NaN fitness becomes 0, and an infinite one becomes the largest (or
+    /// most negative) finite `f64`. A weight that is NaN or negative becomes
+    /// 0, so that candidate is never chosen by an observer, and an infinite
+    /// weight becomes the largest that the wheel can hold.
+    Correct,
+
+    /// Treats a NaN fitness as the worst possible, and carries on.
+    ///
+    /// A NaN fitness becomes the most negative finite `f64`, so the
+    /// candidate loses every comparison, and is never chosen by an observer.
+    /// Infinities and weights are corrected as with `Correct`.
+    Worst,
+}
+
+/// A fitness with a total order, in which NaN is worse than any other value.
+///
+/// Fitnesses are `f64`s, which are only partially ordered, so comparing or
+/// sorting them has to decide what to do with NaN each time, or risk a
+/// panic. The hive compares fitnesses through this wrapper instead. NaN
+/// ranks below negative infinity, and equals itself.
+#[derive(Clone, Copy, Debug)]
+pub struct TotalFitness(pub f64);
+
+impl PartialEq for TotalFitness {
+    fn eq(&self, other: &TotalFitness) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalFitness {}
+
+impl PartialOrd for TotalFitness {
+    fn partial_cmp(&self, other: &TotalFitness) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalFitness {
+    fn cmp(&self, other: &TotalFitness) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+/// A value that can serve as the fitness of a solution.
+///
+/// Each [`Context`](trait.Context.html) declares its fitness type, which
+/// [`evaluate_fitness`](trait.Context.html#tymethod.evaluate_fitness)
+/// returns and each [`Candidate`](struct.Candidate.html) keeps as its
+/// `raw_fitness`. The hive compares candidates by this type's order,
+/// reversed when [minimizing](enum.Objective.html#variant.Minimize), to
+/// keep the better of two, choose tournament winners, and track the best
+/// and the elites. So the fitness can be an integer for exact scores, a
+/// tuple for lexicographic objectives, or a type of your own that carries
+/// metadata along with the value it is ordered by.
+///
+/// Scaling, the limit strategies, the stopping rules and the statistics
+/// are arithmetic, and work on the fitness as an `f64`, from `to_f64`.
+/// Two fitnesses that aren't comparable, such as NaNs, are compared by
+/// that instead.
+///
+/// This is implemented for the primitive numeric types, and for tuples of
+/// up to four elements whose first element is a `Fitness`.
+pub trait Fitness: PartialOrd + Clone + Send + Sync + 'static {
+    /// Converts the fitness to an `f64`, preserving its order: if `a > b`,
+    /// then `a.to_f64() >= b.to_f64()`.
+    ///
+    /// Integers beyond 2<sup>53</sup> lose precision, and a tuple converts
+    /// its first element, so fitnesses that differ may convert to the same
+    /// value.
+    fn to_f64(&self) -> f64;
+
+    /// Combines the samples of a noisy fitness, when the hive
+    /// [resamples](struct.HiveBuilder.html#method.set_resampling) it.
+    ///
+    /// The floating-point types take the arithmetic mean. By default, this
+    /// is the median sample, ordered by `to_f64`.
+    fn mean(samples: &[Self]) -> Self {
+        let key = |sample: &Self| TotalFitness(sample.to_f64());
+        let middle = samples.len() / 2;
+        samples.iter()
+               .find(|&sample| {
+                   let below = samples.iter().filter(|other| key(other) < key(sample)).count();
+                   let level = samples.iter().filter(|other| key(other) <= key(sample)).count();
+                   below <= middle && middle < level
+               })
+               .unwrap_or(&samples[0])
+               .clone()
+    }
+}
+
+macro_rules! float_fitness {
+    ($($t:ty),*) => {
+        $(
+            impl Fitness for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn mean(samples: &[$t]) -> $t {
+                    samples.iter().sum::<$t>() / samples.len() as $t
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! integer_fitness {
+    ($($t:ty),*) => {
+        $(
+            impl Fitness for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! tuple_fitness {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: Fitness $(, $rest: PartialOrd + Clone + Send + Sync + 'static)*> Fitness for ($first, $($rest,)*) {
+            fn to_f64(&self) -> f64 {
+                self.0.to_f64()
+            }
+        }
+    }
+}
+
+float_fitness!(f64, f32);
+integer_fitness!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+tuple_fitness!(A);
+tuple_fitness!(A, B);
+tuple_fitness!(A, B, C);
+tuple_fitness!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nan_is_the_worst_fitness() {
+        let mut fitnesses = [1.0, f64::NAN, f64::NEG_INFINITY, -2.0];
+        fitnesses.sort_by_key(|&f| TotalFitness(f));
+        assert!(fitnesses[0].is_nan());
+        assert_eq!(&fitnesses[1..], &[f64::NEG_INFINITY, -2.0, 1.0]);
+        assert_eq!(TotalFitness(f64::NAN), TotalFitness(f64::NAN));
+        assert!(TotalFitness(0.0) > TotalFitness(f64::NAN));
+    }
+
+    #[test]
+    fn numeric_fitness_keeps_order() {
+        assert_eq!(7u32.to_f64(), 7.0);
+        assert_eq!((-3i64).to_f64(), -3.0);
+        assert!(u64::MAX.to_f64() >= (u64::MAX - 1).to_f64());
+        assert!(0.5f32.to_f64() > 0.25f32.to_f64());
+        assert_eq!((2u8, 'z').to_f64(), 2.0);
+    }
+
+    #[test]
+    fn samples_are_combined() {
+        assert_eq!(f64::mean(&[1.0, 2.0, 6.0]), 3.0);
+        assert_eq!(i32::mean(&[9, 1, 4, 4]), 4);
+        assert_eq!(u8::mean(&[7]), 7);
+        assert_eq!(<(i32, char)>::mean(&[(3, 'a'), (1, 'b'), (2, 'c')]), (2, 'c'));
+    }
+}
@@ -1,31 +1,128 @@
 extern crate num_cpus;
 extern crate rand;
 extern crate crossbeam;
+extern crate crossbeam_deque;
 
-use self::rand::{thread_rng, Rng};
+use self::rand::{thread_rng, Rng, SeedableRng, XorShiftRng};
 use self::crossbeam::{scope, ScopedJoinHandle};
+use self::crossbeam_deque::{Injector, Steal};
 
+use std::io::Write;
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::VecDeque;
+use std::mem;
 use std::ops::Range;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
-use std::sync::{Mutex, RwLock, MutexGuard};
-use std::sync::mpsc::{Sender, Receiver, channel};
-use std::thread::spawn;
-use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, RwLock, MutexGuard, RwLockReadGuard};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Sender, Receiver, RecvTimeoutError};
+use std::thread::{self, spawn, JoinHandle};
+use std::time::{Duration, Instant};
 
 use task::{TaskGenerator, Task};
-use candidate::{WorkingCandidate, Candidate};
-use context::Context;
-use scaling::{ScalingFunction, proportionate};
-use result::{Result as AbcResult, Error as AbcError};
+use colony::{roulette_in, CycleSpec, TaskCycle, TaskSchedule};
+use candidate::{WorkingCandidate, Candidate, Noise, Origin, Provenance};
+use context::{Context, Evaluation, FnContext, Scratch};
+use executor::Executor;
+use evaluator::Evaluator;
+use config::HiveConfig;
+use snapshot::{HiveSnapshot, WorkingSnapshot};
+use scaling::{ScalingFn, proportionate};
+use stop::{StopCondition, MaxEvaluations, TimeLimit, Stagnation};
+use pareto::{ParetoArchive, ParetoMember};
+use elite::EliteArchive;
+use stats::{RoundStats, RoundSummary, ScoutEvent, Diversity, Progress, RoundTiming, Improvement};
+use observer::HiveObserver;
+use logger::{LogFormat, RunLogger};
+use event::HiveEvent;
+use stream::{self, StreamBuffer, StreamSender};
+use limit::LimitStrategy;
+use niching::Niching;
+use selection::Selection;
+use priority::WorkerPriority;
+use fitness::{Fitness, FitnessPolicy};
+use objective::Objective;
+use scout::{ScoutStrategy, Random};
+use init::InitStrategy;
+use chaos::{ChaoticMap, ChaoticSequence};
+use local::{LocalSearch, Schedule};
+use tuning::{ParameterController, Parameters};
+use trace::Span;
+use result::{Error, Result as AbcResult, Stage, LockResultExt, MutexExt, RwLockExt, catch_panic, thread_panic};
+
+/// Creates a fresh stop condition for each run.
+type StopFactory = dyn Fn() -> Box<dyn StopCondition> + Send + Sync;
+
+/// Creates a fresh task schedule for each run, from the numbers of workers
+/// and observers.
+type ScheduleFactory = dyn Fn(usize, usize) -> Box<dyn TaskSchedule> + Send + Sync;
+
+/// Updates the context between rounds.
+type RoundEndHook<Ctx> = dyn FnMut(&mut Ctx, &RoundStats) + Send;
+
+/// Reports the progress of a run.
+type ProgressHook = dyn FnMut(&Progress) + Send;
+
+/// Starts a job on a thread of its own, with the context. Boxed when the
+/// evaluation timeout is set, where the context is known to be `'static`.
+type Spawner<Ctx> = dyn Fn(Arc<RwLock<Ctx>>, Box<dyn FnOnce(&Ctx) + Send>) + Send + Sync;
 
 /// Manages the parameters of the ABC algorithm.
 pub struct HiveBuilder<Ctx: Context> {
     workers: usize,
     observers: usize,
     retries: usize,
-    context: Ctx,
+    limit: LimitStrategy,
+    scouting: Box<dyn ScoutStrategy<Ctx>>,
+    initial: Vec<Ctx::Solution>,
+    init: Option<Box<dyn InitStrategy<Ctx>>>,
+    opposition: Option<f64>,
+    quick: Option<f64>,
+    variants: usize,
+    chaos: Option<ChaoticMap>,
+    local_search: Option<(Box<dyn LocalSearch<Ctx>>, Schedule)>,
+    // Only written by the round-end hook, while no task is underway, so
+    // nested reads can't deadlock. Shared with timed evaluations, which may
+    // outlive the task that started them.
+    context: Arc<RwLock<Ctx>>,
+    round_end: Option<Mutex<Box<RoundEndHook<Ctx>>>>,
+    reevaluation: Option<usize>,
     threads: usize,
-    scale: Box<ScalingFunction>,
+    scale: Box<dyn ScalingFn>,
+    niching: Niching,
+    selection: Selection,
+    worker_priority: WorkerPriority,
+    fitness_policy: FitnessPolicy,
+    panic_tolerance: usize,
+    objective: Objective,
+    seed: Option<u64>,
+    time_budget: Option<Duration>,
+    evaluation_budget: Option<usize>,
+    evaluation_timeout: Option<(Duration, Box<Spawner<Ctx>>)>,
+    evaluation_retries: Option<(usize, Duration)>,
+    resampling: Option<(usize, f64)>,
+    progress: Option<Mutex<Box<ProgressHook>>>,
+    controller: Option<Mutex<Box<dyn ParameterController>>>,
+    evaluator: Option<Box<dyn Evaluator<Ctx::Solution, Ctx::Fitness>>>,
+    stagnation: Option<(usize, f64)>,
+    stop_condition: Option<Box<StopFactory>>,
+    pareto_capacity: usize,
+    elite_capacity: usize,
+    history_depth: usize,
+    duplicate_distance: Option<f64>,
+    executor: Executor,
+    synchronous: bool,
+    deterministic: bool,
+    stream_buffer: StreamBuffer,
+    cycle: CycleSpec,
+    schedule: Option<Box<ScheduleFactory>>,
+    // The context's scratch states that aren't lent out, and whether it
+    // makes any at all.
+    scratch: Mutex<Vec<Scratch>>,
+    scratchless: AtomicBool,
+    // Locked while running, so that observers can be called back from any thread.
+    hooks: Mutex<Vec<Box<ObserverOf<Ctx>>>>,
 }
 
 impl<Ctx: Context> HiveBuilder<Ctx> {
@@ -33,22 +130,69 @@ impl<Ctx: Context> HiveBuilder<Ctx> {
     ///
     /// * `context` - Factory-like state that can be used while generating solutions.
     /// * `workers` - Number of working solution candidates to maintain at a time.
+    ///
+    /// This used to panic if `workers` was 0; now [`build`](#method.build)
+    /// returns an error instead.
     pub fn new(context: Ctx, workers: usize) -> HiveBuilder<Ctx> {
-        if workers == 0 {
-            panic!("HiveBuilder must have at least one worker.");
-        }
-
         HiveBuilder {
-            workers: workers,
+            workers,
             observers: workers,
             retries: workers,
+            limit: LimitStrategy::default(),
+            scouting: Box::new(Random),
+            initial: Vec::new(),
+            init: None,
+            opposition: None,
+            quick: None,
+            variants: 1,
+            chaos: None,
+            local_search: None,
 
-            context: context,
+            context: Arc::new(RwLock::new(context)),
+            round_end: None,
+            reevaluation: None,
             threads: num_cpus::get(),
-            scale: proportionate(),
+            scale: Box::new(proportionate()),
+            niching: Niching::default(),
+            selection: Selection::default(),
+            worker_priority: WorkerPriority::default(),
+            fitness_policy: FitnessPolicy::default(),
+            panic_tolerance: 0,
+            objective: Objective::default(),
+            seed: None,
+            time_budget: None,
+            evaluation_budget: None,
+            evaluation_timeout: None,
+            evaluation_retries: None,
+            resampling: None,
+            progress: None,
+            controller: None,
+            evaluator: None,
+            stagnation: None,
+            stop_condition: None,
+            pareto_capacity: 100,
+            elite_capacity: 10,
+            history_depth: 0,
+            duplicate_distance: None,
+            executor: Executor::default(),
+            synchronous: false,
+            deterministic: false,
+            stream_buffer: StreamBuffer::default(),
+            cycle: CycleSpec::default(),
+            schedule: None,
+            scratch: Mutex::new(Vec::new()),
+            scratchless: AtomicBool::new(false),
+            hooks: Mutex::new(Vec::new()),
         }
     }
 
+    /// Creates a builder from the settings in `config`.
+    ///
+    /// See [`HiveConfig`](struct.HiveConfig.html).
+    pub fn from_config(context: Ctx, config: &HiveConfig) -> HiveBuilder<Ctx> {
+        config.apply(HiveBuilder::new(context, config.workers))
+    }
+
     /// Sets the number of "bees" that will pick a candidate to work on at random.
     ///
     /// This defaults to the number of workers.
@@ -65,364 +209,5303 @@ impl<Ctx: Context> HiveBuilder<Ctx> {
         self
     }
 
-    /// Sets the number of worker threads to use while running.
-    pub fn set_threads(mut self, threads: usize) -> HiveBuilder<Ctx> {
-        self.threads = threads;
+    /// Sets how the number of retries is chosen for each candidate.
+    ///
+    /// This defaults to [`LimitStrategy::Fixed`](enum.LimitStrategy.html#variant.Fixed),
+    /// which uses the number set by [`set_retries`](#method.set_retries).
+    pub fn set_limit_strategy(mut self, limit: LimitStrategy) -> HiveBuilder<Ctx> {
+        self.limit = limit;
         self
     }
 
-    /// Sets the scaling function for observers to use.
-    pub fn set_scaling(mut self, scale: Box<ScalingFunction>) -> HiveBuilder<Ctx> {
-        self.scale = scale;
+    /// Sets how abandoned candidates are replaced.
+    ///
+    /// This defaults to [`scout::Random`](scout/struct.Random.html), which
+    /// starts over from a fresh random solution. See the
+    /// [`scout`](scout/index.html) module for the alternatives.
+    pub fn set_scouting<S>(mut self, strategy: S) -> HiveBuilder<Ctx>
+        where S: ScoutStrategy<Ctx> + 'static
+    {
+        self.scouting = Box::new(strategy);
         self
     }
 
-    /// Activates the `HiveBuilder` to create a runnable object.
-    pub fn build(self) -> AbcResult<Hive<Ctx>> {
-        Hive::new(self)
+    /// Seeds the initial population with known solutions.
+    ///
+    /// The first working candidates start from `solutions`, which might come
+    /// from an earlier run or a domain heuristic; the rest are generated by
+    /// [`make_with_rng`](trait.Context.html#method.make_with_rng) as usual.
+    /// The solutions are still evaluated when the hive is built. This has no
+    /// effect on a hive resumed with
+    /// [`Hive::from_snapshot`](struct.Hive.html#method.from_snapshot). More
+    /// solutions than workers are reported as an error by
+    /// [`build`](#method.build).
+    pub fn set_initial_solutions(mut self, solutions: Vec<Ctx::Solution>) -> HiveBuilder<Ctx> {
+        self.initial = solutions;
+        self
     }
 
-    fn new_candidate(&self) -> Candidate<Ctx::Solution> {
-        let solution = self.context.make();
-        let fitness = self.context.evaluate_fitness(&solution);
-        Candidate::new(solution, fitness)
+    /// Sets how the initial population is generated.
+    ///
+    /// By default, each initial candidate is made independently by
+    /// [`make_with_rng`](trait.Context.html#method.make_with_rng). A strategy
+    /// generates the whole population at once, so that it can spread the
+    /// candidates evenly; see the [`init`](init/index.html) module. Any
+    /// solutions set with [`set_initial_solutions`](#method.set_initial_solutions)
+    /// are kept, and the strategy generates the rest.
+    pub fn set_init_strategy<S>(mut self, strategy: S) -> HiveBuilder<Ctx>
+        where S: InitStrategy<Ctx> + 'static
+    {
+        self.init = Some(Box::new(strategy));
+        self
     }
-}
 
-/// Runs the ABC algorithm, maintaining any necessary state.
-pub struct Hive<Ctx: Context> {
-    hive: HiveBuilder<Ctx>,
+    /// Turns on opposition-based learning.
+    ///
+    /// Each initial candidate is evaluated alongside its
+    /// [`opposite`](trait.Context.html#method.opposite), and the fitter of
+    /// the two is kept. After that, each time a worker bee visits its
+    /// candidate, it first tries the candidate's opposite with probability
+    /// `jumping_rate`, keeping it if it is fitter. This "generation jumping"
+    /// is spread over the round rather than done all at once, but amounts to
+    /// the same on average. A `jumping_rate` of 0 only affects the initial
+    /// population; 0.3 is a common choice. A rate outside 0 to 1 is reported
+    /// as an error by [`build`](#method.build).
+    pub fn set_opposition(mut self, jumping_rate: f64) -> HiveBuilder<Ctx> {
+        self.opposition = Some(jumping_rate);
+        self
+    }
 
-    working: Vec<RwLock<WorkingCandidate<Ctx::Solution>>>,
-    best: Mutex<Candidate<Ctx::Solution>>,
-    scouting: RwLock<BTreeSet<usize>>,
+    /// Draws the randomness for exploring and scouting from chaotic sequences.
+    ///
+    /// Each working candidate follows its own sequence of `map`, started at
+    /// a random point, in place of the hive's RNG. See the
+    /// [`chaos`](chaos/index.html) module. Observers still choose candidates
+    /// with the hive's RNG. By default, there is no chaos.
+    pub fn set_chaos(mut self, map: ChaoticMap) -> HiveBuilder<Ctx> {
+        self.chaos = Some(map);
+        self
+    }
 
-    tasks: Mutex<Option<TaskGenerator>>,
-    sender: Option<Mutex<Sender<Candidate<Ctx::Solution>>>>,
-}
+    /// Refines candidates with a local search, making a memetic hybrid.
+    ///
+    /// `schedule` says which candidates are refined, and when; see the
+    /// [`local`](local/index.html) module. A refined candidate replaces the
+    /// original if it is fitter. By default, there is no local search.
+    /// `Schedule::Best(0)` is reported as an error by [`build`](#method.build).
+    pub fn set_local_search<L>(mut self, search: L, schedule: Schedule) -> HiveBuilder<Ctx>
+        where L: LocalSearch<Ctx> + 'static
+    {
+        self.local_search = Some((Box::new(search), schedule));
+        self
+    }
 
-impl<Ctx: Context> Hive<Ctx> {
-    fn new(hive: HiveBuilder<Ctx>) -> AbcResult<Hive<Ctx>> {
-        // Start by populating the field with an initial set of solution candidates.
+    /// Sets the number of worker threads to use while running.
+    ///
+    /// This can be changed later with [`Hive::set_threads`](struct.Hive.html#method.set_threads).
+    pub fn set_threads(mut self, threads: usize) -> HiveBuilder<Ctx> {
+        self.threads = threads;
+        self
+    }
 
-        // Feed the worker threads a total of N items, each signifying that
-        // we need another candidate.
-        let tokens: Mutex<Range<usize>> = Mutex::new(0..hive.workers);
+    /// Sets the strategy for spreading work across the threads.
+    ///
+    /// This defaults to [`Executor::Threads`](enum.Executor.html#variant.Threads).
+    pub fn set_executor(mut self, executor: Executor) -> HiveBuilder<Ctx> {
+        self.executor = executor;
+        self
+    }
 
-        let candidates = Mutex::new(Vec::with_capacity(hive.workers));
-        let mut handles = Vec::<ScopedJoinHandle<AbcResult<()>>>::with_capacity(hive.threads);
+    /// Runs in strict generations, as in Karaboga's description of ABC.
+    ///
+    /// By default, tasks stream through the threads, so a round's observers
+    /// may start before its workers have finished, and the next round's
+    /// workers before its observers have. In synchronous mode, every worker
+    /// task of a round completes before any observer starts, and every
+    /// observer before the next round, which some published results depend
+    /// on. The threads sit idle while each phase winds down, and stopping
+    /// rules are only checked between rounds.
+    pub fn set_synchronous(mut self, synchronous: bool) -> HiveBuilder<Ctx> {
+        self.synchronous = synchronous;
+        self
+    }
 
-        try!(crossbeam::scope(|scope| {
-            for _ in 0..hive.threads {
-                handles.push(scope.spawn(|| {
-                    while let Some(_) = {
-                        let mut guard = tokens.lock().unwrap();
-                        guard.next()
-                    } {
-                        let candidate = hive.new_candidate();
-                        try!(candidates.lock()).push(candidate);
-                    }
-                    Ok(())
-                }));
-            }
+    /// Sets the phases that make up each round.
+    ///
+    /// By default, a round is a pass of the workers followed by a pass of
+    /// the observers, and expired candidates are scouted as soon as they
+    /// expire. A [`CycleSpec`](colony/struct.CycleSpec.html) can repeat the
+    /// observer pass to weight the search towards the best candidates, or
+    /// add a scout phase to replace every expired candidate together at a
+    /// set point in the round. In synchronous mode, each phase completes
+    /// before the next begins.
+    pub fn set_cycle(mut self, spec: CycleSpec) -> HiveBuilder<Ctx> {
+        self.cycle = spec;
+        self
+    }
 
-            // Gather and return `Ok` iff all of the workers finished
-            // successfully, otherwise abort the construction.
-            handles.drain(..)
-                   .fold(Ok(()), |result, handle| result.and(handle.join()))
-        }));
+    /// Sets a custom schedule of tasks, in place of the
+    /// [cycle](#method.set_cycle).
+    ///
+    /// `schedule` is called with the numbers of workers and observers at the
+    /// start of each run, so that every run starts its schedule afresh. See
+    /// [`TaskSchedule`](colony/trait.TaskSchedule.html) for what a schedule
+    /// must provide.
+    pub fn set_task_schedule<F, S>(mut self, schedule: F) -> HiveBuilder<Ctx>
+        where F: Fn(usize, usize) -> S + Send + Sync + 'static,
+              S: TaskSchedule + 'static
+    {
+        self.schedule = Some(Box::new(move |workers, observers| Box::new(schedule(workers, observers))));
+        self
+    }
 
-        // We don't need the mutex anymore, since we're no longer populating
-        // the candidate set from multiple threads.
-        let mut candidates = try!(candidates.into_inner());
+    /// Makes seeded runs reproducible on any number of threads.
+    ///
+    /// Ordinarily, a seed only fixes the run on a single thread, since the
+    /// threads race to claim tasks and update candidates. In deterministic
+    /// mode, the hive runs in strict generations, as in
+    /// [synchronous mode](#method.set_synchronous), and each task draws from
+    /// its own RNG, derived from the seed, the round and the task's place in
+    /// the round. Within each phase, the tasks explore and evaluate in
+    /// parallel against the field as it stood at the start of the phase; then
+    /// their results are applied one at a time, in task order. Scouting is
+    /// part of applying a result, so scouts are evaluated on the calling
+    /// thread.
+    ///
+    /// The context must draw all of its randomness from the RNGs it is
+    /// passed. [`build`](#method.build) returns an error without a
+    /// [seed](#method.set_rng_seed), or with a [chaotic map](#method.set_chaos),
+    /// whose sequences the tasks would share.
+    pub fn set_deterministic(mut self, deterministic: bool) -> HiveBuilder<Ctx> {
+        self.deterministic = deterministic;
+        self
+    }
 
-        // Find the current best candidate, since we want to cache the best
-        // at any given moment.
-        let best = {
-            let (first, rest) = candidates.split_first().unwrap();
-            let best_candidate = rest.iter()
-                                     .fold(first, |best, next| {
-                                         if next.fitness > best.fitness {
-                                             next
-                                         } else {
-                                             best
-                                         }
-                                     });
-            Mutex::new(best_candidate.clone())
-        };
+    /// Sets the scaling function for observers to use.
+    ///
+    /// This may be any [`ScalingFunction`](scaling/type.ScalingFunction.html),
+    /// or a [`ScalingFn`](scaling/trait.ScalingFn.html) that changes from
+    /// round to round, such as
+    /// [`scaling::annealed_boltzmann`](scaling/fn.annealed_boltzmann.html).
+    pub fn set_scaling<S: ScalingFn + 'static>(mut self, scale: S) -> HiveBuilder<Ctx> {
+        self.scale = Box::new(scale);
+        self
+    }
 
-        // Wrap the candidates in a structure that will let the eventual
-        // thread swarm work on them.
-        let working = candidates.drain(..)
-                                .map(|c| RwLock::new(WorkingCandidate::new(c, hive.retries)))
-                                .collect::<Vec<RwLock<WorkingCandidate<Ctx::Solution>>>>();
-
-        Ok(Hive {
-            hive: hive,
-            working: working,
-            best: best,
-            scouting: RwLock::new(BTreeSet::new()),
-            tasks: Mutex::new(None),
-            sender: None,
-        })
+    /// Sets how observers choose which candidate to work on.
+    ///
+    /// This defaults to [`Selection::Roulette`](enum.Selection.html#variant.Roulette),
+    /// which uses the scaling function. A tournament of size 0 is reported as
+    /// an error by [`build`](#method.build).
+    pub fn set_selection(mut self, selection: Selection) -> HiveBuilder<Ctx> {
+        self.selection = selection;
+        self
     }
 
-    /// Clone a snapshot of the current set of working candidates.
+    /// Sets which candidates the workers visit.
     ///
-    /// The goal of this function is to hold a guard for each solution for as
-    /// little time as possible, so we can get out of the way of the other
-    /// threads. To this end, we clone the solutions, so that the thread can do
-    /// its work on a snapshot.
-    fn current_working(&self) -> AbcResult<Vec<Candidate<Ctx::Solution>>> {
-        let mut current_working = Vec::with_capacity(self.working.len());
-        for candidate_mutex in &self.working {
-            let read_guard = try!(candidate_mutex.read());
-            current_working.push(read_guard.candidate.clone())
-        }
-        Ok(current_working)
+    /// This defaults to [`WorkerPriority::InOrder`](enum.WorkerPriority.html#variant.InOrder),
+    /// the classic scheme, in which every worker visits its own candidate.
+    /// [`WorkerPriority::Stale`](enum.WorkerPriority.html#variant.Stale)
+    /// instead steers the workers towards the candidates that have been
+    /// neglected, or are about to be abandoned.
+    pub fn set_worker_priority(mut self, priority: WorkerPriority) -> HiveBuilder<Ctx> {
+        self.worker_priority = priority;
+        self
     }
 
-    /// Returns a guard for the current best solution found by the hive.
+    /// Sets whether the value from `evaluate_fitness` is maximized or minimized.
     ///
-    /// If the hive is running, you should drop the guard returned by this
-    /// function as soon as convenient, since the logic of the hive can block
-    /// on the availability of the associated mutex. If you plan on performing
-    /// expensive computations, you should `drop` the guard as soon as
-    /// possible, or acquire and clone it within a small block.
-    pub fn get(&self) -> AbcResult<MutexGuard<Candidate<Ctx::Solution>>> {
-        self.best.lock().map_err(AbcError::from)
+    /// This defaults to [`Objective::Maximize`](enum.Objective.html#variant.Maximize).
+    /// With [`Objective::Minimize`](enum.Objective.html#variant.Minimize),
+    /// `evaluate_fitness` can return the raw cost to be minimized, and the
+    /// hive transforms it into a fitness. Each candidate's `fitness` is then
+    /// the transformed value, and `raw_fitness` is the cost; settings that
+    /// take a fitness, like
+    /// [`run_until_fitness`](struct.Hive.html#method.run_until_fitness), take
+    /// the transformed value, which
+    /// [`Objective::fitness`](enum.Objective.html#method.fitness) computes.
+    pub fn set_objective(mut self, objective: Objective) -> HiveBuilder<Ctx> {
+        self.objective = objective;
+        self
     }
 
-    /// Perform greedy selection between a new candidate and the current best.
-    fn consider_improvement(&self, candidate: &Candidate<Ctx::Solution>) -> AbcResult<()> {
-        let mut best_guard = try!(self.best.lock());
-        if candidate.fitness > best_guard.fitness {
-            *best_guard = candidate.clone();
-            if let Some(mutex) = self.sender.as_ref() {
-                // We're streaming, so we need to post the improved candidate.
-                let sender_guard = try!(mutex.lock());
-                // If this errors, the receiver was dropped, so we're done.
-                if let Err(_) = sender_guard.send(candidate.clone()) {
-                    try!(self.stop());
-                }
-            }
-        }
-        Ok(())
+    /// Sets what to do with a NaN, infinite or otherwise unusable fitness.
+    ///
+    /// This defaults to [`FitnessPolicy::Reject`](enum.FitnessPolicy.html#variant.Reject),
+    /// which ends the run with an error.
+    pub fn set_fitness_policy(mut self, policy: FitnessPolicy) -> HiveBuilder<Ctx> {
+        self.fitness_policy = policy;
+        self
     }
 
-    fn work_on(&self, current_working: &[Candidate<Ctx::Solution>], n: usize) -> AbcResult<()> {
-        let variant_solution = self.hive.context.explore(current_working, n);
-        let variant_fitness = self.hive.context.evaluate_fitness(&variant_solution);
-        let variant = Candidate::new(variant_solution, variant_fitness);
-        let mut write_guard = try!(self.working[n].write());
-        if variant.fitness > write_guard.candidate.fitness {
-            *write_guard = WorkingCandidate::new(variant, self.hive.retries);
-            try!(self.consider_improvement(&write_guard.candidate));
-        } else {
-            write_guard.deplete();
-            // Scouting has been folded into the working process
-            if write_guard.expired() {
-                {
-                    let mut scouting_guard = try!(self.scouting.write());
-                    scouting_guard.insert(n);
-                }
-                drop(write_guard);
+    /// Keeps running through up to `failures` consecutive tasks whose
+    /// `explore` or `evaluate_fitness` panics, or whose evaluation outlasts
+    /// the [evaluation timeout](#method.set_evaluation_timeout).
+    ///
+    /// The candidate that a panicking task worked on is abandoned and
+    /// replaced by a scout, and the panic is counted in the round's
+    /// [`RoundStats::panics`](stats/struct.RoundStats.html#structfield.panics).
+    /// If the replacement's evaluation fails the same way, that counts as
+    /// the next panic in a row, and the candidate is scouted again. Any task
+    /// that finishes cleanly resets the count; one more panic in a row than
+    /// `failures` ends the run with an error, as does a panic in the
+    /// [scout strategy](#method.set_scouting). This defaults to 0, so that
+    /// the first panic ends the run.
+    pub fn set_panic_tolerance(mut self, failures: usize) -> HiveBuilder<Ctx> {
+        self.panic_tolerance = failures;
+        self
+    }
 
-                let candidate = self.hive.new_candidate();
-                try!(self.consider_improvement(&candidate));
-                {
-                    let mut write_guard = try!(self.working[n].write());
-                    *write_guard = WorkingCandidate::new(candidate, self.hive.retries);
-                }
+    /// Sets how observers weigh candidates that have close neighbours.
+    ///
+    /// This defaults to [`Niching::Off`](enum.Niching.html#variant.Off).
+    /// With niching, the colony can settle on several optima at once; see
+    /// [`Niching`](enum.Niching.html).
+    pub fn set_niching(mut self, niching: Niching) -> HiveBuilder<Ctx> {
+        self.niching = niching;
+        self
+    }
 
-                let mut scouting_guard = try!(self.scouting.write());
-                scouting_guard.remove(&n);
-            }
-        }
-        Ok(())
+    /// Turns on quick ABC (qABC), in which observers work on the fittest
+    /// candidate near the one they choose.
+    ///
+    /// Following Karaboga and Gorkemli, an observer that chooses candidate
+    /// *m* looks at the candidates within `radius` times the mean distance
+    /// from *m* to the others, and explores the fittest of them, *m*
+    /// included, in its place. A `radius` of 0 is the classic behaviour, and
+    /// 1 is usual. This needs a context that measures
+    /// [`distance`](trait.Context.html#method.distance); otherwise, it has
+    /// no effect. A negative or NaN `radius` is reported as an error by
+    /// [`build`](#method.build).
+    pub fn set_quick_abc(mut self, radius: f64) -> HiveBuilder<Ctx> {
+        self.quick = Some(radius);
+        self
     }
 
-    fn choose(&self, current_working: &[Candidate<Ctx::Solution>]) -> AbcResult<usize> {
-        let fitnesses = (self.hive.scale)(current_working.iter()
-                                                         .map(|candidate| candidate.fitness)
-                                                         .collect::<Vec<f64>>());
+    /// Sets how many variants each worker or observer generates when it
+    /// visits a candidate, which defaults to 1.
+    ///
+    /// The variants are evaluated in parallel, each on its own thread, and
+    /// only the fittest is compared with the candidate. When fitness is
+    /// expensive and there are fewer tasks in a round than idle cores, this
+    /// puts the spare cores to work. Each visit still counts as one
+    /// exploration, whether or not its variants are duplicates, but each
+    /// variant evaluated counts toward the evaluation budget. A count of 0 is
+    /// reported as an error by [`build`](#method.build).
+    pub fn set_variants_per_visit(mut self, variants: usize) -> HiveBuilder<Ctx> {
+        self.variants = variants;
+        self
+    }
 
-        // Avoid observing candidates that are being scouted.
-        let running_totals = {
-            let scouting_guard = try!(self.scouting.read());
-            fitnesses.iter()
-                     .enumerate()
-                     .filter(|&(ref i, _)| !scouting_guard.contains(i))
-                     .scan(0f64, |total, (i, fitness)| {
-                         *total += *fitness;
-                         Some((i, *total))
-                     })
-                     .collect::<Vec<(usize, f64)>>()
-        };
-
-        // Multiplying the choice point is equivalent to, and more efficient than, normalizing
-        // all of the scaled fitnesses and having a choice point in [0,1)
-        match running_totals.last() {
-            Some(&(_, total_fitness)) => {
-                let choice_point = thread_rng().next_f64() * total_fitness;
-                for &(i, total) in &running_totals {
-                    if total > choice_point {
-                        return Ok(i);
-                    }
-                }
-                unreachable!();
-            }
+    /// Scores solutions in batches with `evaluator`, in place of the
+    /// context's [`evaluate`](trait.Context.html#method.evaluate).
+    ///
+    /// See [`Evaluator`](trait.Evaluator.html) for which solutions are
+    /// batched together. With an evaluator, the initial population is made on
+    /// a single thread, so that it can be scored in one batch.
+    pub fn set_evaluator<E>(mut self, evaluator: E) -> HiveBuilder<Ctx>
+        where E: Evaluator<Ctx::Solution, Ctx::Fitness> + 'static
+    {
+        self.evaluator = Some(Box::new(evaluator));
+        self
+    }
 
-            // If we are currently scouting all of the solutions, pick one at random.
-            None => Ok(thread_rng().gen_range::<usize>(0, fitnesses.len())),
-        }
+    /// Seeds the hive's random number generator.
+    ///
+    /// By default, the hive seeds itself from `thread_rng`. With a fixed
+    /// seed, the initial population, scouting, and observers' choices are
+    /// reproducible, as long as the hive runs on a single thread (or in
+    /// [deterministic mode](#method.set_deterministic)) and the context
+    /// draws its randomness from the RNG passed to
+    /// [`make_with_rng`](trait.Context.html#method.make_with_rng) and
+    /// [`explore_with_rng`](trait.Context.html#method.explore_with_rng).
+    pub fn set_rng_seed(mut self, seed: u64) -> HiveBuilder<Ctx> {
+        self.seed = Some(seed);
+        self
     }
 
-    fn execute(&self, task: &Task) -> AbcResult<()> {
-        let current_working = try!(self.current_working());
-        let index = match *task {
-            Task::Worker(n) => {
-                // If the worker's candidate is in the middle of being replaced, just skip it.
-                let scouting_guard = try!(self.scouting.read());
-                if scouting_guard.contains(&n) {
-                    return Ok(());
+    /// Sets a wall-clock budget for each run of the hive.
+    ///
+    /// Every run (whether for a number of rounds, forever, or streaming) will
+    /// stop handing out tasks once `budget` has elapsed. Tasks that are
+    /// already underway will still finish, so a run may overrun its budget
+    /// by up to one fitness evaluation per thread.
+    pub fn set_time_budget(mut self, budget: Duration) -> HiveBuilder<Ctx> {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Sets a budget of fitness evaluations for each run of the hive.
+    ///
+    /// Every run will stop handing out tasks once it has made `evaluations`
+    /// fitness evaluations, which is the usual way to compare metaheuristics
+    /// fairly. As with the time budget, tasks that are underway will still
+    /// finish, so a run may overrun by a few evaluations per thread (or, with
+    /// an executor that works a round at a time, by most of a round).
+    pub fn set_evaluation_budget(mut self, evaluations: usize) -> HiveBuilder<Ctx> {
+        self.evaluation_budget = Some(evaluations);
+        self
+    }
+
+    /// Gives up on any fitness evaluation that takes longer than `timeout`.
+    ///
+    /// Each evaluation then runs on a thread of its own, while the task that
+    /// needs it waits for at most `timeout`. An evaluation that overruns
+    /// fails with [`Error::TimedOut`](enum.Error.html#variant.TimedOut),
+    /// which ends the run, unless the hive has a
+    /// [panic tolerance](#method.set_panic_tolerance) to scout the candidate
+    /// anew. Threads can't be killed, so the evaluation itself carries on in
+    /// the background, still reading the context, until it returns; a
+    /// [round-end hook](#method.on_round_end) waits for it.
+    ///
+    /// Starting a thread for every evaluation has a cost, so this is meant
+    /// for slow fitness functions. It doesn't apply to an
+    /// [`Evaluator`](evaluator/trait.Evaluator.html), which evaluates
+    /// solutions in batches.
+    pub fn set_evaluation_timeout(mut self, timeout: Duration) -> HiveBuilder<Ctx>
+        where Ctx: 'static
+    {
+        let spawner = |context: Arc<RwLock<Ctx>>, job: Box<dyn FnOnce(&Ctx) + Send>| {
+            spawn(move || {
+                // A poisoned context drops the job, which the task hears of.
+                if let Ok(context) = context.read_at(Stage::Context) {
+                    job(&context);
                 }
-                n
-            }
-            Task::Observer(_) => try!(self.choose(&current_working)),
+            });
         };
-        self.work_on(&current_working, index)
+        self.evaluation_timeout = Some((timeout, Box::new(spawner)));
+        self
     }
 
-    fn run(&self, tasks: TaskGenerator) -> AbcResult<()> {
-        {
-            let mut guard = try!(self.tasks.lock());
-            *guard = Some(tasks);
-        }
+    /// Tries a fitness evaluation again, up to `retries` times, when it
+    /// panics or times out.
+    ///
+    /// This is for failures that pass, like a remote evaluator losing its
+    /// connection, so that they don't cost a good candidate its place. The
+    /// hive waits `backoff` before the first retry, and twice as long
+    /// before each one after. Only once the retries run out does the
+    /// evaluation fail, which ends the run or, with a
+    /// [panic tolerance](#method.set_panic_tolerance), scouts the candidate
+    /// anew. An evaluation that returns an invalid fitness is not retried.
+    pub fn set_evaluation_retries(mut self, retries: usize, backoff: Duration) -> HiveBuilder<Ctx> {
+        self.evaluation_retries = Some((retries, backoff));
+        self
+    }
 
-        let mut handles: Vec<ScopedJoinHandle<AbcResult<()>>> = Vec::new();
+    /// Evaluates each solution `samples` times, for fitness functions with
+    /// noise.
+    ///
+    /// With a single evaluation, a lucky sample can make a poor solution look
+    /// best, and greedy selection will then keep it. Instead, each candidate's
+    /// `raw_fitness` becomes the mean of its samples, and it is selected on a
+    /// pessimistic estimate: the mean, less `confidence` standard errors (or
+    /// plus, when minimizing). A `confidence` of 0 selects on the mean alone;
+    /// 1 or 2 guard against noise more strongly. The spread is kept as the
+    /// candidate's [`Noise`](struct.Noise.html), and every sample counts as
+    /// a fitness evaluation. By default, each solution is evaluated once.
+    /// A `samples` of 0, or a negative `confidence`, is reported as an error
+    /// by [`build`](#method.build).
+    pub fn set_resampling(mut self, samples: usize, confidence: f64) -> HiveBuilder<Ctx> {
+        self.resampling = Some((samples, confidence));
+        self
+    }
 
-        scope(|scope| {
-            for _ in 0..self.hive.threads {
-                handles.push(scope.spawn(|| {
-                    loop {
-                        let task = {
-                            let mut guard = try!(self.tasks.lock());
-                            guard.as_mut().and_then(|gen| gen.next())
-                        };
+    /// Stops each run once the search has converged.
+    ///
+    /// A run is considered converged after `rounds` consecutive rounds in
+    /// which the best fitness fails to improve by more than `epsilon`. This
+    /// applies alongside any other limit on the run, such as a number of
+    /// rounds or a time budget; whichever is reached first stops the run.
+    pub fn set_stagnation_limit(mut self, rounds: usize, epsilon: f64) -> HiveBuilder<Ctx> {
+        self.stagnation = Some((rounds, epsilon));
+        self
+    }
 
-                        match task {
-                            Some(t) => try!(self.execute(&t)),
-                            None => return Ok(()),
-                        };
-                    }
-                }));
-            }
+    /// Sets a custom rule for stopping each run of the hive.
+    ///
+    /// Each run starts with a fresh clone of `condition`, so stateful
+    /// conditions like [`Stagnation`](stop/struct.Stagnation.html) begin
+    /// anew. This applies alongside any other limit on the run; whichever is
+    /// reached first stops the run. See the [`stop`](stop/index.html) module
+    /// for the conditions available.
+    pub fn set_stop_condition<C>(mut self, condition: C) -> HiveBuilder<Ctx>
+        where C: StopCondition + Clone + Sync + 'static
+    {
+        self.stop_condition = Some(Box::new(move || Box::new(condition.clone())));
+        self
+    }
 
-            // Returns `Ok(())` only if all threads join cleanly, and the task
-            // cycle is successfully cleared away.
-            //
-            // We avoid `try!` because we want all of the following logic to
-            // execute unconditionally.
-            handles.drain(..)
-                   .fold(Ok(()), |result, handle| result.and(handle.join()))
-                   .and(self.tasks
-                            .lock()
-                            .map(|mut tasks_guard| *tasks_guard = None)
-                            .map_err(AbcError::from))
-        })
+    /// Sets the maximum number of solutions kept in the Pareto archive.
+    ///
+    /// This only matters for multi-objective contexts (see
+    /// [`evaluate_objectives`](trait.Context.html#method.evaluate_objectives)),
+    /// and defaults to 100.
+    pub fn set_pareto_capacity(mut self, capacity: usize) -> HiveBuilder<Ctx> {
+        self.pareto_capacity = capacity;
+        self
     }
 
-    /// Runs for a fixed number of rounds, then return the best solution found.
+    /// Sets the number of distinct candidates kept in the elite archive.
     ///
-    /// If one of the worker threads panics while working, this will return
-    /// `Err(abc::Error)`. Otherwise, it will return `Ok` with a `Candidate`.
-    pub fn run_for_rounds(&self, rounds: usize) -> AbcResult<Candidate<Ctx::Solution>> {
-        let tasks = TaskGenerator::new(self.hive.workers, self.hive.observers).max_rounds(rounds);
-        try!(self.run(tasks));
-        self.get().map(|guard| guard.clone())
+    /// The hive keeps the fittest candidates it has ever evaluated, available
+    /// through [`Hive::top_k`](struct.Hive.html#method.top_k) and offered to
+    /// the [`ScoutStrategy`](scout/trait.ScoutStrategy.html). This defaults to
+    /// 10; a `capacity` of 0 is reported as an error by [`build`](#method.build).
+    pub fn set_elite_capacity(mut self, capacity: usize) -> HiveBuilder<Ctx> {
+        self.elite_capacity = capacity;
+        self
     }
 
-    /// Run indefinitely.
+    /// Keeps the last `depth` fitnesses of each working candidate's slot,
+    /// for [`Hive::slot_history`](struct.Hive.html#method.slot_history).
     ///
-    /// If one of the worker threads panics while working, this will return
-    /// `Err(abc::Error)`. Otherwise, it will return `Ok(())`.
-    pub fn run_forever(&self) -> AbcResult<()> {
-        let tasks = TaskGenerator::new(self.hive.workers, self.hive.observers);
-        self.run(tasks)
+    /// A slot's history starts with its initial candidate, and gains an
+    /// entry each time the candidate is improved, replaced by a scout or an
+    /// immigrant, or re-evaluated. This defaults to 0, which keeps no
+    /// history.
+    pub fn set_slot_history(mut self, depth: usize) -> HiveBuilder<Ctx> {
+        self.history_depth = depth;
+        self
     }
 
-    /// Stops a running hive.
+    /// Rejects variants within `distance` of a working candidate.
     ///
-    /// If a worker thread has panicked, this returns `Err(abc::Error)`.
-    pub fn stop(&self) -> AbcResult<()> {
-        let mut tasks_guard = try!(self.tasks.lock());
-        Ok(tasks_guard.as_mut().map_or((), |t| t.stop()))
+    /// This needs a context that measures
+    /// [`distance`](trait.Context.html#method.distance). A rejected variant
+    /// isn't evaluated, and counts against its candidate's retries as a
+    /// failed exploration would, so a candidate that keeps retreading
+    /// occupied ground is soon abandoned. A `distance` of 0 rejects only
+    /// exact duplicates. By default, no variants are rejected.
+    pub fn set_duplicate_distance(mut self, distance: f64) -> HiveBuilder<Ctx> {
+        self.duplicate_distance = Some(distance);
+        self
     }
 
-    /// Each new best candidate will be sent to `sender`.
+    /// Sets how streaming hives buffer results that haven't been read.
     ///
-    /// This is kept in a separate function so that the hive can be borrowed
-    /// while running.
-    pub fn set_sender(&mut self, sender: Sender<Candidate<Ctx::Solution>>) {
-        if let Ok(best_guard) = self.best.lock() {
-            sender.send(best_guard.clone()).unwrap_or(());
+    /// This defaults to [`StreamBuffer::Unbounded`](enum.StreamBuffer.html#variant.Unbounded),
+    /// which keeps every result. For long runs with a slow receiver, a
+    /// bounded buffer keeps memory use in check.
+    pub fn set_stream_buffer(mut self, buffer: StreamBuffer) -> HiveBuilder<Ctx> {
+        self.stream_buffer = buffer;
+        self
+    }
+
+    /// Registers callbacks to follow the hive's progress.
+    ///
+    /// Any number of observers may be added; each is called in the order
+    /// it was added. See [`HiveObserver`](trait.HiveObserver.html).
+    pub fn add_observer(mut self, observer: Box<dyn HiveObserver<Ctx::Solution, Ctx::Fitness>>) -> HiveBuilder<Ctx> {
+        if let Ok(hooks) = self.hooks.get_mut() {
+            hooks.push(observer);
         }
-        self.sender = Some(Mutex::new(sender));
+        self
     }
 
-    /// Returns the current round of a running hive.
+    /// Writes a record of each round to `writer`, in the given format.
     ///
-    /// If a worker thread has panicked and poisoned the task generator lock,
-    /// `get_round` will return `Err(abc::Error)`. If the hive has not been
-    /// run, `get_round` will return `Ok(None)`.
+    /// This adds a [`RunLogger`](struct.RunLogger.html) as an
+    /// [observer](#method.add_observer). Wrap files in a `BufWriter`; the
+    /// logger flushes after each record anyway.
+    pub fn set_logger<W>(self, writer: W, format: LogFormat) -> HiveBuilder<Ctx>
+        where W: Write + Send + 'static
+    {
+        self.add_observer(Box::new(RunLogger::new(writer, format)))
+    }
+
+    /// Calls `hook` at the end of every round, with exclusive access to the
+    /// context, for instance to move the optimum of a dynamic problem.
     ///
-    /// If the hive is running, this will return `Ok(Some(n))`. `n` will start
-    /// at 0, and increment each time every task in the round has been claimed
-    /// (though not necessarily completed) by a worker thread.
-    pub fn get_round(&self) -> AbcResult<Option<usize>> {
-        let tasks_guard = try!(self.tasks.lock());
-        Ok(tasks_guard.as_ref().map(|tasks| tasks.round))
+    /// The hook needs every task of the round to have finished, so with
+    /// [`Executor::Threads`](enum.Executor.html#variant.Threads) or
+    /// [`Executor::Queued`](enum.Executor.html#variant.Queued), the hive
+    /// works through one round at a time (as in
+    /// [`set_synchronous`](#method.set_synchronous), but without separating
+    /// the workers from the observers), and checks the stopping rules between
+    /// rounds. The candidates keep the fitnesses they had under the old
+    /// context.
+    pub fn on_round_end<F>(mut self, hook: F) -> HiveBuilder<Ctx>
+        where F: FnMut(&mut Ctx, &RoundStats) + Send + 'static
+    {
+        self.round_end = Some(Mutex::new(Box::new(hook)));
+        self
     }
 
-    /// Get a reference to the hive's context.
-    pub fn context(&self) -> &Ctx {
-        &self.hive.context
+    /// Re-evaluates the candidates at the end of every `every`th round, for
+    /// problems whose objective drifts.
+    ///
+    /// This re-scores the working candidates, the best candidate and the
+    /// elite archive, as [`Hive::invalidate_fitness`](struct.Hive.html#method.invalidate_fitness)
+    /// does, after any [round-end hook](#method.on_round_end) has run. By
+    /// default, fitnesses are never re-evaluated. An `every` of 0 is reported
+    /// as an error by [`build`](#method.build).
+    pub fn set_reevaluation(mut self, every: usize) -> HiveBuilder<Ctx> {
+        self.reevaluation = Some(every);
+        self
     }
-}
 
-impl<Ctx: Context + 'static> Hive<Ctx> {
-    /// Runs indefinitely in the background, providing a stream of results.
+    /// Calls `report` with the [`Progress`](stats/struct.Progress.html) of
+    /// each run, as each round ends.
     ///
-    /// This method consumes the hive, which will run until the `HiveBuilder`
-    /// object is dropped. It returns an `mpsc::Receiver`, which receives a
-    /// `Candidate` each time the hive improves on its best solution.
-    pub fn stream(mut self) -> Receiver<Candidate<Ctx::Solution>> {
-        let (sender, receiver) = channel();
-        spawn(move || {
-            self.set_sender(sender);
-            let tasks = TaskGenerator::new(self.hive.workers, self.hive.observers);
-            self.run(tasks)
-        });
-        receiver
+    /// The report includes the best fitness and the evaluation rate, and for
+    /// [`Hive::run_for_rounds`](struct.Hive.html#method.run_for_rounds), an
+    /// estimate of the time remaining, which is enough to drive a progress bar.
+    pub fn set_progress<F>(mut self, report: F) -> HiveBuilder<Ctx>
+        where F: FnMut(&Progress) + Send + 'static
+    {
+        self.progress = Some(Mutex::new(Box::new(report)));
+        self
     }
-}
 
-impl<Ctx: Context> Debug for Hive<Ctx>
-    where Ctx::Solution: Debug
-{
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        for mutex in (&self.working).iter() {
-            let working = mutex.read().unwrap();
-            try!(write!(f, "..{:?}..\n", working.candidate));
+    /// Tunes the number of observers, the retries and the selection pressure
+    /// as the hive runs.
+    ///
+    /// At the end of each round, after any [round-end hook](#method.on_round_end),
+    /// `controller` sees the round's statistics and adjusts the
+    /// [`Parameters`](tuning/struct.Parameters.html) for the rounds that
+    /// follow. The current values are available from
+    /// [`Hive::parameters`](struct.Hive.html#method.parameters). See the
+    /// [`tuning`](tuning/index.html) module.
+    pub fn set_parameter_controller<C>(mut self, controller: C) -> HiveBuilder<Ctx>
+        where C: ParameterController + 'static
+    {
+        self.controller = Some(Mutex::new(Box::new(controller)));
+        self
+    }
+
+    /// Activates the `HiveBuilder` to create a runnable object.
+    ///
+    /// Returns [`Error::InvalidConfig`](enum.Error.html#variant.InvalidConfig)
+    /// if the settings make no sense: no workers, no threads, more initial
+    /// solutions than workers, an empty evaluation budget or elite archive,
+    /// a negative duplicate distance or stagnation epsilon, deterministic
+    /// mode without a seed, any of the setters' ranges broken, or a fixed
+    /// limit of 0 retries with no observers, under which every candidate is
+    /// abandoned the first time exploration fails.
+    pub fn build(self) -> AbcResult<Hive<Ctx>> {
+        self.validate()?;
+        Hive::new(self)
+    }
+
+    /// Checks the settings that the setters can't check on their own.
+    fn validate(&self) -> AbcResult<()> {
+        let problem = if self.workers == 0 {
+            Some("the hive must have at least one worker")
+        } else if self.threads == 0 {
+            Some("the hive must run on at least one thread")
+        } else if self.evaluation_budget == Some(0) {
+            Some("the evaluation budget must allow at least one evaluation")
+        } else if self.duplicate_distance.is_some_and(|d| d.is_nan() || d < 0f64) {
+            Some("the duplicate distance must not be negative")
+        } else if self.stagnation.is_some_and(|(_, epsilon)| epsilon.is_nan() || epsilon < 0f64) {
+            Some("the stagnation epsilon must not be negative")
+        } else if self.deterministic && self.seed.is_none() {
+            Some("deterministic mode needs an RNG seed")
+        } else if self.deterministic && self.chaos.is_some() {
+            Some("chaotic maps can't be used in deterministic mode")
+        } else if self.variants == 0 {
+            Some("each visit must generate at least one variant")
+        } else if self.initial.len() > self.workers {
+            Some("there must not be more initial solutions than workers")
+        } else if self.opposition.is_some_and(|rate| !(0f64..=1f64).contains(&rate)) {
+            Some("the jumping rate must be between 0 and 1")
+        } else if self.local_search.as_ref().is_some_and(|&(_, schedule)| schedule == Schedule::Best(0)) {
+            Some("the local search must refine the best candidate every 1 or more rounds")
+        } else if self.selection == Selection::Tournament(0) {
+            Some("tournaments must have at least one entrant")
+        } else if self.resampling.is_some_and(|(samples, _)| samples == 0) {
+            Some("resampling must evaluate each solution at least once")
+        } else if self.resampling.is_some_and(|(_, confidence)| confidence.is_nan() || confidence < 0f64) {
+            Some("the resampling confidence must not be negative")
+        } else if self.quick.is_some_and(|radius| radius.is_nan() || radius < 0f64) {
+            Some("the quick ABC radius must not be negative")
+        } else if self.elite_capacity == 0 {
+            Some("the elite archive must keep at least one candidate")
+        } else if self.reevaluation == Some(0) {
+            Some("re-evaluation must happen every 1 or more rounds")
+        } else if self.observers == 0 && self.retries == 0 && self.limit == LimitStrategy::Fixed {
+            Some("with no observers, candidates must get at least one retry")
+        } else {
+            None
+        };
+        match problem {
+            Some(description) => Err(Error::InvalidConfig { description: description.to_string() }),
+            None => Ok(()),
         }
-        let best_candidate = self.get().unwrap();
-        write!(f, ">>{:?}<<", *best_candidate)
     }
-}
 
-impl<Ctx: Context> Drop for Hive<Ctx> {
-    fn drop(&mut self) {
-        self.stop().unwrap_or(())
+    /// Decides how many retries a candidate gets, given the range of fitness
+    /// in the field and the fraction of explorations that have succeeded.
+    /// The fixed limit is `retries`, which a controller may have changed.
+    fn limit(&self, fitness: f64, worst: f64, best: f64, success_rate: Option<f64>, retries: usize) -> usize {
+        match self.limit {
+            LimitStrategy::Fixed => retries,
+            LimitStrategy::Fitness { min, max } => {
+                let relative = if best > worst { (fitness - worst) / (best - worst) } else { 1f64 };
+                let relative = if relative.is_nan() { 0f64 } else { relative.clamp(0f64, 1f64) };
+                min + (max.saturating_sub(min) as f64 * relative).round() as usize
+            }
+            LimitStrategy::SuccessRate { factor, min, max } => {
+                match success_rate {
+                    Some(rate) if rate > 0f64 => ((factor / rate).ceil().max(0f64) as usize).max(min).min(max),
+                    _ => max,
+                }
+            }
+        }
+    }
+
+    fn new_rng(&self) -> XorShiftRng {
+        match self.seed {
+            // XorShift can't be seeded with all zeroes, so the high words are
+            // salted with arbitrary nonzero constants.
+            Some(seed) => {
+                XorShiftRng::from_seed([seed as u32,
+                                        (seed >> 32) as u32,
+                                        0x9e37_79b9,
+                                        0x7f4a_7c15])
+            }
+            None => thread_rng().gen(),
+        }
+    }
+
+    /// Derives the RNG for task `index` of `round` in deterministic mode.
+    /// Round 0 is the initial population.
+    fn task_rng(&self, round: usize, index: usize) -> XorShiftRng {
+        // SplitMix64 spreads neighbouring rounds and tasks across the seeds.
+        let mix = |mut z: u64| {
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        let seed = self.seed.unwrap_or(0);
+        let z = mix(mix(seed.wrapping_add(0x9e37_79b9_7f4a_7c15) ^ round as u64) ^ index as u64);
+        let w = mix(z);
+        // XorShift can't be seeded with all zeroes, as in `new_rng`.
+        XorShiftRng::from_seed([z as u32, (z >> 32) as u32, w as u32 | 1, (w >> 32) as u32])
+    }
+
+    /// Starts the history of a slot with its first candidate's fitness, if
+    /// histories are kept.
+    fn new_history(&self, fitness: f64) -> VecDeque<f64> {
+        let mut history = VecDeque::with_capacity(self.history_depth);
+        if self.history_depth > 0 {
+            history.push_back(fitness);
+        }
+        history
+    }
+
+    /// Returns a guard for reading the context.
+    fn context(&self) -> AbcResult<RwLockReadGuard<'_, Ctx>> {
+        self.context.read_at(Stage::Context)
+    }
+
+    fn new_candidate<R: Rng>(&self, index: usize, rng: &mut R) -> AbcResult<Evaluated<Ctx::Solution, Ctx::Fitness>> {
+        let context = self.context()?;
+        let solution = match self.initial.get(index) {
+            Some(solution) => solution.clone(),
+            None => catch_panic(Stage::Make, Some(index), || context.make_with_rng(rng))?,
+        };
+        if self.opposition.is_none() {
+            return self.evaluate(Some(index), solution);
+        }
+        let opposite = catch_panic(Stage::Opposite, Some(index), || context.opposite(&solution))?;
+        let evaluated = self.evaluate(Some(index), solution)?;
+        let opposed = self.evaluate(Some(index), opposite)?;
+        Ok(if self.fitter(&opposed.0, &evaluated.0) { opposed } else { evaluated })
+    }
+
+    /// Makes and scores the whole initial population, in one batch for the
+    /// evaluator.
+    fn new_candidates<R: Rng>(&self, rng: &mut R) -> AbcResult<Vec<Evaluated<Ctx::Solution, Ctx::Fitness>>> {
+        let context = self.context()?;
+        let mut solutions = Vec::with_capacity(self.workers);
+        for index in 0..self.workers {
+            solutions.push(match self.initial.get(index) {
+                Some(solution) => solution.clone(),
+                None => catch_panic(Stage::Make, Some(index), || context.make_with_rng(rng))?,
+            });
+        }
+        if self.opposition.is_none() {
+            return self.evaluate_batch(None, solutions);
+        }
+        let opposites = solutions.iter()
+                                 .enumerate()
+                                 .map(|(index, solution)| {
+                                     catch_panic(Stage::Opposite, Some(index), || context.opposite(solution))
+                                 })
+                                 .collect::<AbcResult<Vec<_>>>()?;
+        solutions.extend(opposites);
+        let mut evaluated = self.evaluate_batch(None, solutions)?;
+        let opposed = evaluated.split_off(self.workers);
+        Ok(evaluated.into_iter()
+                    .zip(opposed)
+                    .map(|(evaluated, opposed)| if self.fitter(&opposed.0, &evaluated.0) { opposed } else { evaluated })
+                    .collect())
+    }
+
+    fn scout_candidate<R: Rng>(&self,
+                               field: &[Arc<CandidateOf<Ctx>>],
+                               index: usize,
+                               elites: &[CandidateOf<Ctx>],
+                               rng: &mut R)
+                               -> AbcResult<Evaluated<Ctx::Solution, Ctx::Fitness>> {
+        let context = self.context()?;
+        let solution = catch_panic(Stage::ScoutStrategy, Some(index), || {
+            self.scouting.scout(&context, field, index, elites, rng)
+        })?;
+        self.evaluate(Some(index), solution)
+    }
+
+    /// Decides whether a variant of the candidate at `index` is too close to
+    /// a working candidate to be worth evaluating.
+    fn is_duplicate(&self,
+                    index: usize,
+                    solution: &Ctx::Solution,
+                    field: &[Arc<CandidateOf<Ctx>>])
+                    -> AbcResult<bool> {
+        let min = match self.duplicate_distance {
+            Some(min) => min,
+            None => return Ok(false),
+        };
+        let context = self.context()?;
+        catch_panic(Stage::Distance, Some(index), || {
+            field.iter().any(|c| context.distance(solution, &c.solution).is_some_and(|d| d <= min))
+        })
+    }
+
+    /// Measures the distances between every pair of candidates in `field`,
+    /// if the context supports it.
+    fn diversity(&self, field: &[Arc<CandidateOf<Ctx>>]) -> AbcResult<Option<Diversity>> {
+        let context = self.context()?;
+        catch_panic(Stage::Distance, None, || {
+            let mut distances = Vec::with_capacity(field.len() * field.len().saturating_sub(1) / 2);
+            for (i, a) in field.iter().enumerate() {
+                for b in &field[i + 1..] {
+                    distances.push(context.distance(&a.solution, &b.solution)?);
+                }
+            }
+            Diversity::new(&distances)
+        })
+    }
+
+    /// Applies niching to the observers' weights for the candidates in `field`.
+    fn niche(&self, field: &[Arc<CandidateOf<Ctx>>], weights: Vec<f64>) -> AbcResult<Vec<f64>> {
+        if self.niching == Niching::Off {
+            return Ok(weights);
+        }
+        let context = self.context()?;
+        let distances = catch_panic(Stage::Distance, None, || {
+            field.iter()
+                 .map(|a| field.iter().map(|b| context.distance(&a.solution, &b.solution)).collect())
+                 .collect::<Option<Vec<Vec<f64>>>>()
+        })?;
+        Ok(match distances {
+            Some(distances) => self.niching.weigh(weights, &distances),
+            None => weights,
+        })
+    }
+
+    /// Scores a solution, along with its objectives if there are several.
+    fn evaluate(&self, index: Option<usize>, solution: Ctx::Solution) -> AbcResult<Evaluated<Ctx::Solution, Ctx::Fitness>> {
+        if self.evaluator.is_some() {
+            return Ok(self.evaluate_batch(index, vec![solution])?.remove(0));
+        }
+        let context = self.context()?;
+        let (evaluations, objectives) = self.retrying(|| self.with_scratch(&context, |scratch| {
+            match self.evaluation_timeout {
+                Some((limit, ref spawner)) => self.evaluate_timed(index, &solution, scratch, limit, &**spawner),
+                None => {
+                    catch_panic(Stage::EvaluateFitness, index, || {
+                        let evaluations = (0..self.samples())
+                                              .map(|_| context.evaluate_with_scratch(&solution, scratch))
+                                              .collect::<Vec<_>>();
+                        (evaluations, context.evaluate_objectives(&solution))
+                    })
+                }
+            }
+        }))?;
+        let score = evaluations[0].score;
+        let values = evaluations.into_iter().map(|evaluation| evaluation.fitness).collect::<Vec<_>>();
+        let mut candidate = self.aggregate(index, solution, &values)?;
+        candidate.score = score;
+        Ok((candidate, objectives))
+    }
+
+    /// Evaluates a solution on a thread of its own, and waits for at most
+    /// `limit`. The scratch state goes along with it, and only comes back if
+    /// the evaluation does.
+    fn evaluate_timed(&self,
+                      index: Option<usize>,
+                      solution: &Ctx::Solution,
+                      scratch: &mut Scratch,
+                      limit: Duration,
+                      spawner: &Spawner<Ctx>)
+                      -> AbcResult<Sampled<Ctx::Fitness>> {
+        let (sender, receiver) = sync_channel(1);
+        let (solution, mut lent, samples) = (solution.clone(), mem::take(scratch), self.samples());
+        spawner(self.context.clone(),
+                Box::new(move |context: &Ctx| {
+                    let result = catch_panic(Stage::EvaluateFitness, index, || {
+                        let evaluations = (0..samples)
+                                              .map(|_| context.evaluate_with_scratch(&solution, &mut lent))
+                                              .collect::<Vec<_>>();
+                        (evaluations, context.evaluate_objectives(&solution))
+                    });
+                    // Nobody is listening once the evaluation has timed out.
+                    sender.send((result, lent)).unwrap_or(());
+                }));
+        match receiver.recv_timeout(limit) {
+            Ok((result, lent)) => {
+                *scratch = lent;
+                result
+            }
+            Err(RecvTimeoutError::Timeout) => Err(Error::TimedOut { index, limit }),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(Error::Poisoned {
+                    stage: Stage::Context,
+                    index: None,
+                })
+            }
+        }
+    }
+
+    /// Calls `evaluate` until it succeeds, or fails other than by panicking
+    /// or timing out, or the builder's retries run out.
+    fn retrying<T, F>(&self, mut evaluate: F) -> AbcResult<T>
+        where F: FnMut() -> AbcResult<T>
+    {
+        let (retries, backoff) = self.evaluation_retries.unwrap_or((0, Duration::from_secs(0)));
+        let mut attempt = 0;
+        loop {
+            match evaluate() {
+                Err(ref err) if attempt < retries && transient(err) => {
+                    trace_event!(WARN, attempt, error = %err, "retrying evaluation");
+                    thread::sleep(backoff.saturating_mul(1 << attempt.min(31)));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Lends `f` one of the context's scratch states, making a new one if
+    /// they are all in use. The state goes back to the pool only if `f`
+    /// succeeds.
+    fn with_scratch<T, F>(&self, context: &Ctx, f: F) -> AbcResult<T>
+        where F: FnOnce(&mut Scratch) -> AbcResult<T>
+    {
+        if self.scratchless.load(Ordering::Relaxed) {
+            return f(&mut Scratch::empty());
+        }
+        let free = self.scratch.lock_at(Stage::Context)?.pop();
+        let mut scratch = match free {
+            Some(scratch) => scratch,
+            None => catch_panic(Stage::Context, None, || context.init_scratch())?,
+        };
+        if scratch.is_empty() {
+            self.scratchless.store(true, Ordering::Relaxed);
+            return f(&mut scratch);
+        }
+        let result = f(&mut scratch)?;
+        self.scratch.lock_at(Stage::Context)?.push(scratch);
+        Ok(result)
+    }
+
+    /// Scores several solutions, with a single call to the evaluator if
+    /// there is one, and otherwise one at a time.
+    fn evaluate_batch(&self,
+                      index: Option<usize>,
+                      solutions: Vec<Ctx::Solution>)
+                      -> AbcResult<Vec<Evaluated<Ctx::Solution, Ctx::Fitness>>> {
+        let evaluator = match self.evaluator {
+            Some(ref evaluator) => evaluator,
+            None => return solutions.into_iter().map(|solution| self.evaluate(index, solution)).collect(),
+        };
+        let samples = self.samples();
+        let batch = solutions.iter()
+                             .flat_map(|solution| ::std::iter::repeat_n(solution, samples))
+                             .cloned()
+                             .collect::<Vec<_>>();
+        let context = self.context()?;
+        let (values, objectives) = self.retrying(|| catch_panic(Stage::EvaluateFitness, index, || {
+            let values = evaluator.evaluate(&batch);
+            assert!(values.len() == batch.len(),
+                    "Evaluator returned {} fitnesses for {} solutions.",
+                    values.len(),
+                    batch.len());
+            (values, solutions.iter().map(|solution| context.evaluate_objectives(solution)).collect::<Vec<_>>())
+        }))?;
+        solutions.into_iter()
+                 .zip(values.chunks(samples))
+                 .zip(objectives)
+                 .map(|((solution, values), objectives)| Ok((self.aggregate(index, solution, values)?, objectives)))
+                 .collect()
+    }
+
+    /// Builds a candidate from the fitness of each sample of its solution.
+    fn aggregate(&self,
+                 index: Option<usize>,
+                 solution: Ctx::Solution,
+                 values: &[Ctx::Fitness])
+                 -> AbcResult<CandidateOf<Ctx>> {
+        let numbers = values.iter()
+                            .map(|fitness| self.check_fitness(index, fitness.to_f64()))
+                            .collect::<AbcResult<Vec<f64>>>()?;
+        Ok(match self.resampling {
+            Some((samples, confidence)) => {
+                let n = samples as f64;
+                let mean = numbers.iter().sum::<f64>() / n;
+                let variance = if samples > 1 {
+                    numbers.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1f64)
+                } else {
+                    0f64
+                };
+                let margin = confidence * (variance / n).sqrt();
+                let pessimistic = match self.objective {
+                    Objective::Maximize => mean - margin,
+                    Objective::Minimize => mean + margin,
+                };
+                let mut candidate = Candidate::with_raw_fitness(solution,
+                                                                self.objective.fitness(pessimistic),
+                                                                Ctx::Fitness::mean(values));
+                candidate.noise = Some(Noise { samples, variance });
+                candidate
+            }
+            None => Candidate::with_raw_fitness(solution, self.objective.fitness(numbers[0]), values[0].clone()),
+        })
+    }
+
+    /// Whether `a` is fitter than `b`, as the objective compares them.
+    fn fitter(&self, a: &CandidateOf<Ctx>, b: &CandidateOf<Ctx>) -> bool {
+        self.objective.compare(a, b) == cmp::Ordering::Greater
+    }
+
+    /// The number of times each solution is evaluated.
+    fn samples(&self) -> usize {
+        self.resampling.map_or(1, |(samples, _)| samples)
+    }
+
+    /// Rebuilds a candidate from its solution, (transformed) and raw
+    /// fitnesses, and score.
+    fn restore(&self,
+               solution: Ctx::Solution,
+               fitness: f64,
+               raw_fitness: Ctx::Fitness,
+               score: Option<f64>)
+               -> CandidateOf<Ctx> {
+        let mut candidate = Candidate::with_raw_fitness(solution, fitness, raw_fitness);
+        candidate.score = score;
+        candidate
+    }
+
+    /// Applies the fitness policy to a NaN or infinite fitness.
+    fn check_fitness(&self, index: Option<usize>, fitness: f64) -> AbcResult<f64> {
+        if fitness.is_finite() {
+            return Ok(fitness);
+        }
+        match self.fitness_policy {
+            FitnessPolicy::Reject => {
+                Err(Error::InvalidFitness {
+                    index,
+                    value: fitness,
+                })
+            }
+            FitnessPolicy::Correct => Ok(if fitness.is_nan() { 0f64 } else { fitness.clamp(f64::MIN, f64::MAX) }),
+            FitnessPolicy::Worst => Ok(if fitness.is_nan() { f64::MIN } else { fitness.clamp(f64::MIN, f64::MAX) }),
+        }
+    }
+
+    /// Applies the fitness policy to weights that won't fit on the roulette
+    /// wheel.
+    fn check_weights(&self, mut weights: Vec<f64>) -> AbcResult<Vec<f64>> {
+        let largest = f64::MAX / weights.len() as f64;
+        for (index, weight) in weights.iter_mut().enumerate() {
+            if weight.is_finite() && *weight >= 0f64 {
+                continue;
+            }
+            match self.fitness_policy {
+                FitnessPolicy::Reject => {
+                    return Err(Error::InvalidFitness {
+                        index: Some(index),
+                        value: *weight,
+                    })
+                }
+                FitnessPolicy::Correct | FitnessPolicy::Worst => {
+                    *weight = if *weight == f64::INFINITY { largest } else { 0f64 }
+                }
+            }
+        }
+        Ok(weights)
+    }
+}
+
+/// A freshly scored candidate, with its objectives if there are several.
+type Evaluated<S, F> = (Candidate<S, F>, Option<Vec<f64>>);
+
+/// A snapshot of working candidates, whole or partial.
+type Field<S, F = f64> = [Arc<Candidate<S, F>>];
+
+/// A candidate with the solution and fitness types of a context.
+type CandidateOf<Ctx> = Candidate<<Ctx as Context>::Solution, <Ctx as Context>::Fitness>;
+
+/// Space that a thread reuses from one task to the next, so that taking a
+/// snapshot of the field and choosing a candidate don't allocate afresh.
+struct Buffers<S: Clone + Send + Sync + 'static, F> {
+    working: Vec<Arc<Candidate<S, F>>>,
+    weights: Vec<f64>,
+    entrants: Vec<usize>,
+    totals: Vec<(usize, f64)>,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Buffers<S, F> {
+    fn new() -> Buffers<S, F> {
+        Buffers {
+            working: Vec::new(),
+            weights: Vec::new(),
+            entrants: Vec::new(),
+            totals: Vec::new(),
+        }
+    }
+}
+
+/// Finds the lowest and highest of some fitnesses.
+fn fitness_range<I: IntoIterator<Item = f64>>(fitnesses: I) -> (f64, f64) {
+    fitnesses.into_iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(worst, best), fitness| {
+        (worst.min(fitness), best.max(fitness))
+    })
+}
+
+/// Sends each new best candidate.
+type BestSender<S, F> = StreamSender<Arc<Candidate<S, F>>>;
+
+/// Receives each new best candidate.
+type BestReceiver<S, F> = Receiver<Arc<Candidate<S, F>>>;
+
+/// Tasks claimed for `Executor::Queued`, each with the round it ends, if any.
+type TaskQueue = Injector<(Task, Option<usize>)>;
+
+/// Sends the whole Pareto front each time it changes.
+type FrontSender<S> = Sender<Vec<ParetoMember<S>>>;
+
+/// Decides whether a candidate is good enough to stop running.
+type Target<Ctx> = dyn Fn(&CandidateOf<Ctx>) -> bool + Send + Sync;
+
+/// An event about a context's candidates.
+type EventOf<Ctx> = HiveEvent<<Ctx as Context>::Solution, <Ctx as Context>::Fitness>;
+
+/// Watches a run over a context's candidates.
+type ObserverOf<Ctx> = dyn HiveObserver<<Ctx as Context>::Solution, <Ctx as Context>::Fitness>;
+
+/// A worker's random number generator and scratch buffers.
+type ThreadState<Ctx> = (XorShiftRng, Buffers<<Ctx as Context>::Solution, <Ctx as Context>::Fitness>);
+
+/// The evaluations of one solution, with its objectives if there are several.
+type Sampled<F> = (Vec<Evaluation<F>>, Option<Vec<f64>>);
+
+/// The statistics recorded so far, and the counters as of the last round.
+struct StatsLog {
+    rounds: Vec<RoundStats>,
+    timings: Vec<RoundTiming>,
+    // When the round under way began, or `None` before the first run.
+    round_started: Option<Instant>,
+    evaluations: usize,
+    scouts: usize,
+    explorations: usize,
+    successes: usize,
+    panics: usize,
+}
+
+/// Splits a round's tasks into its phases, each of which numbers its tasks
+/// from 0.
+fn phases(round: &[Task]) -> Vec<&[Task]> {
+    round.chunk_by(|_, next| !matches!(*next, Task::Worker(0) | Task::Observer(0) | Task::Scout(0)))
+         .collect()
+}
+
+impl<S, M, F, E> HiveBuilder<FnContext<S, M, F, E>>
+    where S: Clone + Send + Sync + 'static,
+          M: Fn() -> S + Send + Sync,
+          F: Fn(&S) -> f64 + Send + Sync,
+          E: Fn(&[Arc<Candidate<S>>], usize) -> S + Send + Sync
+{
+    /// Creates a new hive from three closures, rather than a `Context`.
+    ///
+    /// `make` generates a fresh, random solution, `fitness` scores one, and
+    /// `explore` looks near the solution at an index of the field. See
+    /// [`FnContext`](struct.FnContext.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// // Find the x that maximizes 1 / (1 + (x - 42)²).
+    /// let hive = HiveBuilder::from_fns(|| thread_rng().gen_range(-100, 100),
+    ///                                  |x: &i32| 1.0 / (1.0 + ((x - 42) * (x - 42)) as f64),
+    ///                                  |field, n| field[n].solution + thread_rng().gen_range(-5, 6),
+    ///                                  10)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(hive.run_for_rounds(100).unwrap().solution, 42);
+    /// # }
+    /// ```
+    pub fn from_fns(make: M, fitness: F, explore: E, workers: usize) -> HiveBuilder<FnContext<S, M, F, E>> {
+        HiveBuilder::new(FnContext::new(make, fitness, explore), workers)
+    }
+}
+
+/// Runs the ABC algorithm, maintaining any necessary state.
+pub struct Hive<Ctx: Context> {
+    hive: HiveBuilder<Ctx>,
+
+    working: Vec<RwLock<WorkingCandidate<Ctx::Solution, Ctx::Fitness>>>,
+    // Shared with the handles from `watch_best`.
+    best: Arc<Mutex<Arc<CandidateOf<Ctx>>>>,
+    // The best candidate's fitness, as bits, so that candidates which can't
+    // improve on it are turned away without locking `best`.
+    best_fitness: AtomicU64,
+    // Whether each working candidate is being replaced by a scout, in which
+    // case workers skip it and observers pass it over.
+    scouting: Vec<AtomicBool>,
+    // The number of explorations made as of each candidate's latest, for
+    // telling how long it has been neglected.
+    visited: Vec<AtomicUsize>,
+    // The latest fitnesses of each slot, oldest first, if the builder keeps
+    // them.
+    slot_history: Vec<Mutex<VecDeque<f64>>>,
+    // Whether the running schedule has scout tasks, which expired
+    // candidates are left for.
+    scout_tasks: AtomicBool,
+
+    // Both shared with the handles from `controller`.
+    tasks: Arc<Mutex<Option<TaskGenerator>>>,
+    // Tasks claimed from the generator for `Executor::Queued`, each with the
+    // round it ends, if any.
+    queue: Arc<TaskQueue>,
+    // Every subscriber to the improvements. Locked after `best`.
+    senders: Mutex<Vec<BestSender<Ctx::Solution, Ctx::Fitness>>>,
+
+    pareto: Mutex<ParetoArchive<Ctx::Solution>>,
+    pareto_sender: Option<Mutex<FrontSender<Ctx::Solution>>>,
+
+    elites: Mutex<EliteArchive<Ctx::Solution, Ctx::Fitness>>,
+
+    // Seeds a fresh RNG for each worker thread whenever the hive runs.
+    rng: Mutex<XorShiftRng>,
+    // One chaotic sequence per working candidate, if the builder asks for chaos.
+    chaos: Option<Vec<Mutex<ChaoticSequence>>>,
+
+    // Threads wanted, and threads running. When they differ at the end of a
+    // round, the workers regroup with the new number.
+    threads: AtomicUsize,
+    running: AtomicUsize,
+    regroup: AtomicBool,
+
+    // Shared with the task generator, to drive stop conditions.
+    evaluations: Arc<AtomicUsize>,
+
+    // Explorations, and those that improved on their candidate, for
+    // tuning the limit.
+    explorations: AtomicUsize,
+    successes: AtomicUsize,
+
+    // The parameters a controller may tune, starting from the builder's.
+    // The pressure is stored as bits.
+    observers: AtomicUsize,
+    retries: AtomicUsize,
+    pressure: AtomicU64,
+
+    // Rounds completed in previous runs.
+    rounds: AtomicUsize,
+    // Rounds planned for the current run, or 0 if it isn't known.
+    planned_rounds: AtomicUsize,
+
+    scouts: AtomicUsize,
+    scout_counts: Vec<AtomicUsize>,
+    scout_history: Mutex<Vec<ScoutEvent>>,
+    // Tasks that panicked, in all and since the last that didn't.
+    panics: AtomicUsize,
+    consecutive_panics: AtomicUsize,
+    improvements: Mutex<Vec<Improvement>>,
+    created: Instant,
+    stats: Mutex<StatsLog>,
+    stats_sender: Option<Mutex<Sender<RoundStats>>>,
+    event_sender: Option<Mutex<StreamSender<EventOf<Ctx>>>>,
+
+    // Set for the duration of `run_until`.
+    target: RwLock<Option<Box<Target<Ctx>>>>,
+}
+
+impl<Ctx: Context> Hive<Ctx> {
+    #[allow(clippy::manual_try_fold)]
+    fn new(mut hive: HiveBuilder<Ctx>) -> AbcResult<Hive<Ctx>> {
+        // Start by populating the field with an initial set of solution candidates.
+
+        // Feed the worker threads a total of N items, each signifying that
+        // we need another candidate.
+        let tokens: Mutex<Range<usize>> = Mutex::new(0..hive.workers);
+
+        let candidates = Mutex::new(Vec::with_capacity(hive.workers));
+        let mut handles = Vec::<ScopedJoinHandle<AbcResult<()>>>::with_capacity(hive.threads);
+
+        let mut rng = hive.new_rng();
+
+        // Generate the rest of the population up front, if there's a strategy
+        // for it. Any solutions it doesn't supply are made independently.
+        if let Some(ref init) = hive.init {
+            let count = hive.workers - hive.initial.len();
+            let mut solutions = {
+                let context = hive.context()?;
+                catch_panic(Stage::InitStrategy, None, || init.initialize(&context, count, &mut rng))?
+            };
+            solutions.truncate(count);
+            hive.initial.append(&mut solutions);
+        }
+
+        let thread_rngs = (0..hive.threads).map(|_| rng.gen()).collect::<Vec<XorShiftRng>>();
+
+        if hive.evaluator.is_some() {
+            *candidates.lock_at(Stage::Candidate)? = hive.new_candidates(&mut rng)?;
+        } else if hive.executor == Executor::CurrentThread {
+            let mut candidates = candidates.lock_at(Stage::Candidate)?;
+            for index in 0..hive.workers {
+                let candidate = if hive.deterministic {
+                    hive.new_candidate(index, &mut hive.task_rng(0, index))?
+                } else {
+                    hive.new_candidate(index, &mut rng)?
+                };
+                candidates.push(candidate);
+            }
+        } else {
+            let indexed = Mutex::new(Vec::with_capacity(hive.workers));
+            crossbeam::scope(|scope| {
+                let (hive, tokens, indexed) = (&hive, &tokens, &indexed);
+                for mut thread_rng in thread_rngs {
+                    handles.push(scope.spawn(move || {
+                        while let Some(index) = {
+                            let mut guard = tokens.lock().unwrap();
+                            guard.next()
+                        } {
+                            let candidate = if hive.deterministic {
+                                hive.new_candidate(index, &mut hive.task_rng(0, index))?
+                            } else {
+                                hive.new_candidate(index, &mut thread_rng)?
+                            };
+                            indexed.lock_at(Stage::Candidate)?.push((index, candidate));
+                        }
+                        Ok(())
+                    }));
+                }
+
+                // Gather and return `Ok` iff all of the workers finished
+                // successfully, otherwise abort the construction.
+                handles.drain(..)
+                       .fold(Ok(()), |result, handle| result.and(handle.join()))
+            })?;
+
+            // The threads finish in any order, so put each candidate back in
+            // its place.
+            let mut indexed = indexed.into_inner().at(Stage::Candidate)?;
+            indexed.sort_by_key(|&(index, _)| index);
+            *candidates.lock_at(Stage::Candidate)? = indexed.into_iter().map(|(_, candidate)| candidate).collect();
+        }
+
+        // We don't need the mutex anymore, since we're no longer populating
+        // the candidate set from multiple threads.
+        let mut candidates = candidates.into_inner().at(Stage::Candidate)?;
+        hive.initial.clear();
+
+        // Gather the non-dominated solutions, for multi-objective contexts.
+        let mut pareto = ParetoArchive::new(hive.pareto_capacity);
+        let mut candidates = candidates.drain(..)
+                                       .enumerate()
+                                       .map(|(index, (mut candidate, objectives))| {
+                                           if let Some(objectives) = objectives {
+                                               pareto.insert(&candidate.solution, &objectives);
+                                           }
+                                           candidate.provenance = Some(Provenance {
+                                               round: 0,
+                                               index,
+                                               origin: Origin::Initial,
+                                               improvements: 0,
+                                           });
+                                           candidate
+                                       })
+                                       .collect::<Vec<_>>();
+
+        // Find the current best candidate, since we want to cache the best
+        // at any given moment.
+        let best = {
+            let (first, rest) = candidates.split_first().unwrap();
+            rest.iter()
+                .fold(first, |best, next| {
+                    if hive.fitter(next, best) {
+                        next
+                    } else {
+                        best
+                    }
+                })
+                .clone()
+        };
+
+        // Wrap the candidates in a structure that will let the eventual
+        // thread swarm work on them.
+        let (worst, best_fitness) = fitness_range(candidates.iter().map(|c| c.fitness));
+        let working = candidates.drain(..)
+                                .map(|c| {
+                                    let limit = hive.limit(c.fitness, worst, best_fitness, None, hive.retries);
+                                    WorkingCandidate::new(c, limit)
+                                })
+                                .collect();
+
+        // Each of the initial candidates was evaluated once, or twice with
+        // its opposite.
+        let evaluations = if hive.opposition.is_some() { 2 * hive.workers } else { hive.workers } * hive.samples();
+
+        let mut hive = Hive::assemble(hive, working, best, pareto, rng, evaluations, 0);
+
+        // The initial evaluations are counted in the statistics for round 0.
+        hive.stats.get_mut().at(Stage::Stats)?.evaluations = 0;
+        hive.record_round(0)?;
+        Ok(hive)
+    }
+
+    /// Resumes a hive from a snapshot, using the builder's parameters.
+    ///
+    /// The snapshot's working candidates replace the builder's initial
+    /// population, so the number of workers is taken from the snapshot
+    /// rather than the builder. No solutions are generated or evaluated.
+    /// A snapshot with no working candidates is reported as
+    /// [`Error::InvalidConfig`](enum.Error.html#variant.InvalidConfig).
+    pub fn from_snapshot(mut hive: HiveBuilder<Ctx>,
+                         snapshot: HiveSnapshot<Ctx::Solution, Ctx::Fitness>)
+                         -> AbcResult<Hive<Ctx>> {
+        if snapshot.working.is_empty() {
+            return Err(Error::InvalidConfig { description: "the snapshot must have at least one working candidate".to_string() });
+        }
+        hive.workers = snapshot.working.len();
+        hive.validate()?;
+
+        let working = snapshot.working
+                              .into_iter()
+                              .map(|w| {
+                                  let mut candidate = hive.restore(w.solution, w.fitness, w.raw_fitness, w.score);
+                                  candidate.provenance = w.provenance;
+                                  WorkingCandidate::new(candidate, w.retries)
+                              })
+                              .collect();
+        let best = hive.restore(snapshot.best_solution,
+                                snapshot.best_fitness,
+                                snapshot.best_raw_fitness,
+                                snapshot.best_score);
+        let mut pareto = ParetoArchive::new(hive.pareto_capacity);
+        for member in &snapshot.pareto_front {
+            pareto.insert(&member.solution, &member.objectives);
+        }
+        let rng = hive.new_rng();
+
+        let mut hive = Hive::assemble(hive, working, best, pareto, rng, snapshot.evaluations, snapshot.round);
+        {
+            let Hive { ref hive, ref mut elites, .. } = hive;
+            let elites = elites.get_mut().at(Stage::Elites)?;
+            for (solution, fitness, raw_fitness) in snapshot.elites {
+                elites.insert(&hive.restore(solution, fitness, raw_fitness, None));
+            }
+        }
+        Ok(hive)
+    }
+
+    fn assemble(hive: HiveBuilder<Ctx>,
+                working: Vec<WorkingCandidate<Ctx::Solution, Ctx::Fitness>>,
+                best: CandidateOf<Ctx>,
+                pareto: ParetoArchive<Ctx::Solution>,
+                mut rng: XorShiftRng,
+                evaluations: usize,
+                rounds: usize)
+                -> Hive<Ctx> {
+        let scout_counts = (0..working.len()).map(|_| AtomicUsize::new(0)).collect();
+        let scouting = (0..working.len()).map(|_| AtomicBool::new(false)).collect();
+        let visited = (0..working.len()).map(|_| AtomicUsize::new(0)).collect();
+        let slot_history = working.iter().map(|w| Mutex::new(hive.new_history(w.candidate.fitness))).collect();
+        let (threads, observers, retries) = (hive.threads, hive.observers, hive.retries);
+        let chaos = hive.chaos.map(|map| {
+            (0..working.len()).map(|_| Mutex::new(ChaoticSequence::random(map, &mut rng))).collect()
+        });
+        let start = Improvement {
+            round: rounds,
+            fitness: best.fitness,
+            evaluations,
+            elapsed: Duration::from_secs(0),
+        };
+        let mut elites = EliteArchive::new(hive.elite_capacity, hive.objective);
+        elites.insert(&best);
+        for w in &working {
+            elites.insert(&w.candidate);
+        }
+        Hive {
+            hive,
+            working: working.into_iter().map(RwLock::new).collect(),
+            best_fitness: AtomicU64::new(best.fitness.to_bits()),
+            best: Arc::new(Mutex::new(Arc::new(best))),
+            scouting,
+            visited,
+            slot_history,
+            scout_tasks: AtomicBool::new(false),
+            tasks: Arc::new(Mutex::new(None)),
+            queue: Arc::new(Injector::new()),
+            senders: Mutex::new(Vec::new()),
+            pareto: Mutex::new(pareto),
+            pareto_sender: None,
+            elites: Mutex::new(elites),
+            rng: Mutex::new(rng),
+            chaos,
+            threads: AtomicUsize::new(threads),
+            running: AtomicUsize::new(0),
+            regroup: AtomicBool::new(false),
+            evaluations: Arc::new(AtomicUsize::new(evaluations)),
+            explorations: AtomicUsize::new(0),
+            successes: AtomicUsize::new(0),
+            observers: AtomicUsize::new(observers),
+            retries: AtomicUsize::new(retries),
+            pressure: AtomicU64::new(1f64.to_bits()),
+            rounds: AtomicUsize::new(rounds),
+            planned_rounds: AtomicUsize::new(0),
+            scouts: AtomicUsize::new(0),
+            scout_counts,
+            scout_history: Mutex::new(Vec::new()),
+            panics: AtomicUsize::new(0),
+            consecutive_panics: AtomicUsize::new(0),
+            improvements: Mutex::new(vec![start]),
+            created: Instant::now(),
+            stats: Mutex::new(StatsLog {
+                rounds: Vec::new(),
+                timings: Vec::new(),
+                round_started: None,
+                evaluations,
+                scouts: 0,
+                explorations: 0,
+                successes: 0,
+                panics: 0,
+            }),
+            stats_sender: None,
+            event_sender: None,
+            target: RwLock::new(None),
+        }
+    }
+
+    /// Saves the state of the hive, so that it can be resumed later.
+    ///
+    /// This may be called while the hive is running, but since each working
+    /// candidate is saved in turn, the snapshot may mix candidates from
+    /// slightly different moments. For an exact checkpoint, take the
+    /// snapshot between runs.
+    pub fn snapshot(&self) -> AbcResult<HiveSnapshot<Ctx::Solution, Ctx::Fitness>> {
+        let working = self.population()?;
+        let best = self.best_arc()?;
+        let round = self.lifetime_round()?;
+        let pareto_front = self.pareto_front()?;
+        let elites = self.top_k()?.into_iter().map(|c| (c.solution, c.fitness, c.raw_fitness)).collect();
+
+        Ok(HiveSnapshot {
+            working,
+            best_solution: best.solution.clone(),
+            best_fitness: best.fitness,
+            best_raw_fitness: best.raw_fitness.clone(),
+            best_score: best.score,
+            round,
+            evaluations: self.evaluations.load(Ordering::Relaxed),
+            pareto_front,
+            elites,
+        })
+    }
+
+    /// Returns clones of the working candidates, in order, with the retries
+    /// each has left.
+    ///
+    /// This is useful for inspecting or plotting the whole population, not
+    /// just the best candidate. Like [`snapshot`](#method.snapshot), it may
+    /// be called while the hive is running, in which case the candidates may
+    /// come from slightly different moments.
+    pub fn population(&self) -> AbcResult<Vec<WorkingSnapshot<Ctx::Solution, Ctx::Fitness>>> {
+        let mut population = Vec::with_capacity(self.working.len());
+        for (n, candidate_mutex) in self.working.iter().enumerate() {
+            let read_guard = candidate_mutex.read_at_index(Stage::Candidate, n)?;
+            population.push(WorkingSnapshot {
+                solution: read_guard.candidate.solution.clone(),
+                fitness: read_guard.candidate.fitness,
+                raw_fitness: read_guard.candidate.raw_fitness.clone(),
+                retries: read_guard.retries(),
+                score: read_guard.candidate.score,
+                provenance: read_guard.candidate.provenance,
+            });
+        }
+        Ok(population)
+    }
+
+    /// Returns `field` if it is a snapshot of every working candidate, or
+    /// else takes one.
+    fn whole_field<'a>(&self, field: &'a Field<Ctx::Solution, Ctx::Fitness>) -> AbcResult<Cow<'a, Field<Ctx::Solution, Ctx::Fitness>>> {
+        if field.len() == self.working.len() {
+            Ok(Cow::Borrowed(field))
+        } else {
+            Ok(Cow::Owned(self.current_working()?))
+        }
+    }
+
+    /// Takes a snapshot of the candidate at `n` followed by `partners` of
+    /// the other working candidates, drawn at random, into `buffers`.
+    fn snapshot_partners(&self,
+                         n: usize,
+                         partners: usize,
+                         rng: &mut XorShiftRng,
+                         buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>)
+                         -> AbcResult<()> {
+        // A partial Fisher-Yates shuffle of the other indices.
+        let others = &mut buffers.entrants;
+        others.clear();
+        others.extend((0..self.working.len()).filter(|&i| i != n));
+        for i in 0..partners {
+            let j = rng.gen_range(i, others.len());
+            others.swap(i, j);
+        }
+        let field = &mut buffers.working;
+        field.clear();
+        for &i in ::std::iter::once(&n).chain(&others[..partners]) {
+            field.push(self.working[i].read_at_index(Stage::Candidate, i)?.candidate.clone());
+        }
+        Ok(())
+    }
+
+    /// Returns the number of partners that workers explore with, if they can
+    /// take partial snapshots.
+    fn partners(&self) -> AbcResult<Option<usize>> {
+        let whole = self.hive.duplicate_distance.is_some() || self.hive.opposition.is_some() ||
+                    matches!(self.hive.limit, LimitStrategy::Fitness { .. });
+        if whole {
+            return Ok(None);
+        }
+        let partners = self.hive.context()?.partners();
+        Ok(partners.filter(|&k| k + 1 < self.working.len()))
+    }
+
+    /// Take a snapshot of the current set of working candidates.
+    ///
+    /// The goal of this function is to hold a guard for each solution for as
+    /// little time as possible, so we can get out of the way of the other
+    /// threads. To this end, we copy the reference-counted candidates, so
+    /// that the thread can do its work on a snapshot without cloning any
+    /// solutions.
+    fn current_working(&self) -> AbcResult<Vec<Arc<CandidateOf<Ctx>>>> {
+        let mut current_working = Vec::with_capacity(self.working.len());
+        self.snapshot_into(&mut current_working)?;
+        Ok(current_working)
+    }
+
+    /// Takes a snapshot of the working candidates, as `current_working`
+    /// does, into the space `buffer` already has.
+    fn snapshot_into(&self, buffer: &mut Vec<Arc<CandidateOf<Ctx>>>) -> AbcResult<()> {
+        buffer.clear();
+        for (n, candidate_mutex) in self.working.iter().enumerate() {
+            let read_guard = candidate_mutex.read_at_index(Stage::Candidate, n)?;
+            buffer.push(read_guard.candidate.clone())
+        }
+        Ok(())
+    }
+
+    /// Re-evaluates every candidate the hive is keeping, for when the
+    /// objective has changed.
+    ///
+    /// The working candidates, the best candidate and the members of the
+    /// elite archive are all re-scored under the current context, and the
+    /// best candidate becomes the fittest of them, even if it is less fit
+    /// than before. Each re-evaluation counts as a fitness evaluation. The
+    /// Pareto archive is left as it is.
+    ///
+    /// This is best called between runs, or from a
+    /// [round-end hook](struct.HiveBuilder.html#method.on_round_end) with
+    /// [`HiveBuilder::set_reevaluation`](struct.HiveBuilder.html#method.set_reevaluation);
+    /// while the hive is running, candidates found under the old objective
+    /// may still be arriving.
+    pub fn invalidate_fitness(&self) -> AbcResult<()> {
+        let rescore = |index: Option<usize>, candidate: &mut CandidateOf<Ctx>| -> AbcResult<()> {
+            let (fresh, _) = self.hive.evaluate(index, candidate.solution.clone())?;
+            self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+            candidate.fitness = fresh.fitness;
+            candidate.raw_fitness = fresh.raw_fitness;
+            candidate.score = fresh.score;
+            candidate.noise = fresh.noise;
+            Ok(())
+        };
+
+        for (n, slot) in self.working.iter().enumerate() {
+            let mut write_guard = slot.write_at_index(Stage::Candidate, n)?;
+            rescore(Some(n), Arc::make_mut(&mut write_guard.candidate))?;
+            self.record_slot(n, write_guard.candidate.fitness)?;
+        }
+        let current_working = self.current_working()?;
+
+        let mut best_guard = self.best.lock_at(Stage::Best)?;
+        rescore(None, Arc::make_mut(&mut best_guard))?;
+        for candidate in &current_working {
+            if self.hive.fitter(candidate, &best_guard) {
+                *best_guard = candidate.clone();
+            }
+        }
+        self.best_fitness.store(best_guard.fitness.to_bits(), Ordering::Release);
+
+        let mut elites_guard = self.elites.lock_at(Stage::Elites)?;
+        let mut elites = EliteArchive::new(self.hive.elite_capacity, self.hive.objective);
+        for member in elites_guard.members() {
+            let mut member = member.clone();
+            rescore(None, &mut member)?;
+            elites.insert(&member);
+        }
+        elites.insert(&best_guard);
+        for candidate in &current_working {
+            elites.insert(candidate);
+        }
+        *elites_guard = elites;
+        Ok(())
+    }
+
+    /// Returns clones of the fittest distinct candidates found so far, best first.
+    ///
+    /// Unlike [`top_candidates`](#method.top_candidates), this draws on every
+    /// candidate the hive has evaluated, not just the current working set, so
+    /// it can hold several good answers that have since been abandoned. The
+    /// number kept is set by
+    /// [`HiveBuilder::set_elite_capacity`](struct.HiveBuilder.html#method.set_elite_capacity).
+    pub fn top_k(&self) -> AbcResult<Vec<CandidateOf<Ctx>>> {
+        Ok(self.elites.lock_at(Stage::Elites)?.members().to_vec())
+    }
+
+    /// Returns clones of the `count` fittest working candidates, best first.
+    pub fn top_candidates(&self, count: usize) -> AbcResult<Vec<CandidateOf<Ctx>>> {
+        let mut candidates = self.current_working()?;
+        candidates.sort_by(|a, b| self.hive.objective.compare(b, a));
+        candidates.truncate(count);
+        Ok(candidates.into_iter().map(|c| (*c).clone()).collect())
+    }
+
+    /// Offers candidates from elsewhere, such as another hive, to the field.
+    ///
+    /// Each candidate replaces the least fit working candidate, if it is
+    /// fitter. Since the candidates carry their fitness, they are not
+    /// evaluated again. Returns the number of candidates accepted.
+    pub fn immigrate(&self, candidates: &[CandidateOf<Ctx>]) -> AbcResult<usize> {
+        let field = self.current_working()?;
+        let mut accepted = 0;
+        for candidate in candidates {
+            let mut worst: Option<(usize, Arc<CandidateOf<Ctx>>)> = None;
+            for (n, candidate_mutex) in self.working.iter().enumerate() {
+                let current = candidate_mutex.read_at_index(Stage::Candidate, n)?.candidate.clone();
+                if worst.as_ref().is_none_or(|(_, worst)| self.hive.fitter(worst, &current)) {
+                    worst = Some((n, current));
+                }
+            }
+            if let Some((n, worst)) = worst {
+                if self.hive.fitter(candidate, &worst) {
+                    let mut candidate = candidate.clone();
+                    candidate.provenance = Some(self.provenance(n, Origin::Immigrated, 0)?);
+                    let candidate = Arc::new(candidate);
+                    {
+                        let mut write_guard = self.working[n].write_at_index(Stage::Candidate, n)?;
+                        let limit = self.limit_for(candidate.fitness, &field);
+                        *write_guard = WorkingCandidate::new(candidate.clone(), limit);
+                        self.record_slot(n, candidate.fitness)?;
+                    }
+                    self.consider_elite(&candidate)?;
+                    self.consider_improvement(&candidate)?;
+                    accepted += 1;
+                }
+            }
+        }
+        Ok(accepted)
+    }
+
+    /// Returns a guard for the current best solution found by the hive.
+    ///
+    /// If the hive is running, you should drop the guard returned by this
+    /// function as soon as convenient, since the logic of the hive can block
+    /// on the availability of the associated mutex. If you plan on performing
+    /// expensive computations, you should `drop` the guard as soon as
+    /// possible, or acquire and clone it within a small block.
+    pub fn get(&self) -> AbcResult<MutexGuard<'_, Arc<CandidateOf<Ctx>>>> {
+        self.best.lock_at(Stage::Best)
+    }
+
+    /// Returns the best candidate found by the hive, without cloning it.
+    ///
+    /// The hive never changes a candidate once it is found; a better one
+    /// takes its place instead. So the best candidate can be shared, rather
+    /// than copied, which matters when solutions are large.
+    pub fn best_arc(&self) -> AbcResult<Arc<CandidateOf<Ctx>>> {
+        self.get().map(|guard| guard.clone())
+    }
+
+    /// Returns a handle that always sees the best candidate found so far.
+    ///
+    /// Unlike the stream of improvements, the handle doesn't queue anything:
+    /// each call to [`latest`](struct.BestWatch.html#method.latest) gives
+    /// whichever candidate is best at the time, so a GUI can poll it at its
+    /// own rate. The handle can be cloned and sent to other threads, and
+    /// outlives the hive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 10).build().unwrap();
+    /// let watch = hive.watch_best();
+    /// let best = hive.run_for_rounds(20).unwrap();
+    /// assert_eq!(watch.latest().unwrap().fitness, best.fitness);
+    /// # }
+    /// ```
+    pub fn watch_best(&self) -> BestWatch<Ctx::Solution, Ctx::Fitness> {
+        BestWatch { best: self.best.clone() }
+    }
+
+    /// Returns the fitness of the best solution found by the hive.
+    ///
+    /// Unlike [`get`](#method.get), this never blocks, so it is the cheaper
+    /// way to watch a running hive's progress.
+    pub fn best_fitness(&self) -> f64 {
+        f64::from_bits(self.best_fitness.load(Ordering::Acquire))
+    }
+
+    /// Offers a freshly evaluated candidate to the elite archive.
+    fn consider_elite(&self, candidate: &CandidateOf<Ctx>) -> AbcResult<()> {
+        self.elites.lock_at(Stage::Elites)?.insert(candidate);
+        Ok(())
+    }
+
+    /// Perform greedy selection between a new candidate and the current best.
+    fn consider_improvement(&self, candidate: &Arc<CandidateOf<Ctx>>) -> AbcResult<()> {
+        // Most candidates fall short of the best, and the watermark only
+        // rises once the best has, so those need not wait for the lock. A
+        // candidate level with the watermark may still win on its raw
+        // fitness.
+        if candidate.fitness < self.best_fitness() {
+            return Ok(());
+        }
+        let mut best_guard = self.best.lock_at(Stage::Best)?;
+        if self.hive.fitter(candidate, &best_guard) {
+            *best_guard = candidate.clone();
+            self.best_fitness.store(candidate.fitness.to_bits(), Ordering::Release);
+            trace_event!(INFO, fitness = candidate.fitness, raw_fitness = candidate.raw_fitness.to_f64(), "new best");
+            self.improvements.lock_at(Stage::Stats)?.push(Improvement {
+                round: self.lifetime_round()?,
+                fitness: candidate.fitness,
+                evaluations: self.evaluations.load(Ordering::Relaxed),
+                elapsed: self.created.elapsed(),
+            });
+            self.notify(None, |hook| hook.on_new_best(candidate))?;
+            let mut senders = self.senders.lock_at(Stage::Stream)?;
+            if !senders.is_empty() {
+                // We're streaming, so we need to post the improved candidate.
+                // A subscriber whose receiver was dropped is done, and once
+                // they all are, so are we.
+                senders.retain_mut(|sender| sender.send(candidate.clone()).is_ok());
+                if senders.is_empty() {
+                    self.stop()?;
+                }
+            }
+            drop(senders);
+            self.send_event(HiveEvent::NewBest(candidate.clone()))?;
+            let mut tasks_guard = self.tasks.lock_at(Stage::Tasks)?;
+            if let Some(tasks) = tasks_guard.as_mut() {
+                tasks.improve(candidate.fitness);
+            }
+            drop(tasks_guard);
+
+            // Stop as soon as a run_until target is met, while we still hold
+            // the best candidate guard.
+            let target_guard = self.target.read_at(Stage::Stopping)?;
+            if target_guard.as_ref().is_some_and(|target| target(candidate)) {
+                self.stop()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delivers any results that the streams have held back.
+    fn flush_streams(&self) -> AbcResult<()> {
+        let mut disconnected = false;
+        {
+            let mut senders = self.senders.lock_at(Stage::Stream)?;
+            if !senders.is_empty() {
+                senders.retain_mut(|sender| sender.flush().is_ok());
+                disconnected |= senders.is_empty();
+            }
+        }
+        if let Some(mutex) = self.event_sender.as_ref() {
+            disconnected |= mutex.lock_at(Stage::Stream)?.flush().is_err();
+        }
+        if disconnected {
+            self.stop()?;
+        }
+        Ok(())
+    }
+
+    /// Sends an event, if we're streaming them.
+    fn send_event(&self, event: HiveEvent<Ctx::Solution, Ctx::Fitness>) -> AbcResult<()> {
+        if let Some(mutex) = self.event_sender.as_ref() {
+            let mut sender_guard = mutex.lock_at(Stage::Stream)?;
+            // If this errors, the receiver was dropped, so we're done.
+            if sender_guard.send(event).is_err() {
+                self.stop()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a callback on each of the registered observers.
+    fn notify<F>(&self, index: Option<usize>, mut callback: F) -> AbcResult<()>
+        where F: FnMut(&mut dyn HiveObserver<Ctx::Solution, Ctx::Fitness>)
+    {
+        let mut hooks_guard = self.hive.hooks.lock_at(Stage::HiveObserver)?;
+        for hook in hooks_guard.iter_mut() {
+            catch_panic(Stage::HiveObserver, index, || callback(&mut **hook))?;
+        }
+        Ok(())
+    }
+
+    /// Offers a solution to the Pareto archive, if there are several objectives.
+    fn consider_objectives(&self,
+                           solution: &Ctx::Solution,
+                           objectives: Option<Vec<f64>>)
+                           -> AbcResult<()> {
+        if let Some(objectives) = objectives {
+            let mut pareto_guard = self.pareto.lock_at(Stage::Pareto)?;
+            if pareto_guard.insert(solution, &objectives) {
+                if let Some(mutex) = self.pareto_sender.as_ref() {
+                    let sender_guard = mutex.lock_at(Stage::Stream)?;
+                    // Unlike the improvement stream, a dropped receiver here
+                    // doesn't mean the run should end.
+                    sender_guard.send(pareto_guard.members().to_vec()).unwrap_or(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Works on the candidate at `n`, which is at `at` in `current_working`:
+    /// the same place if that is a snapshot of the whole field, or the first
+    /// if it holds just the candidate and its partners.
+    fn work_on(&self,
+               current_working: &[Arc<CandidateOf<Ctx>>],
+               at: usize,
+               n: usize,
+               rng: &mut XorShiftRng)
+               -> AbcResult<()> {
+        let _span = trace_span!(TRACE, "work_on", index = n).entered();
+        let variants = self.visit(current_working, at, n, rng)?;
+        self.settle(current_working, at, n, variants, rng)
+    }
+
+    /// Explores and evaluates variants of the candidate at `n`, without
+    /// touching the field.
+    fn visit(&self,
+             current_working: &[Arc<CandidateOf<Ctx>>],
+             at: usize,
+             n: usize,
+             rng: &mut XorShiftRng)
+             -> AbcResult<Vec<Evaluated<Ctx::Solution, Ctx::Fitness>>> {
+        let context = self.hive.context()?;
+        let best = self.best_arc()?;
+        let variant_solutions = self.with_rng(n, rng, |mut rng| {
+            self.hive.with_scratch(&context, |scratch| {
+                (0..self.hive.variants)
+                    .map(|_| {
+                        catch_panic(Stage::Explore, Some(n), || {
+                            context.explore_with_scratch(current_working, at, &best, &mut rng, scratch)
+                        })
+                    })
+                    .collect::<AbcResult<Vec<_>>>()
+            })
+        })?;
+        drop(context);
+        let mut fresh = Vec::with_capacity(variant_solutions.len());
+        for solution in variant_solutions {
+            if !self.hive.is_duplicate(n, &solution, current_working)? {
+                fresh.push(solution);
+            }
+        }
+        self.evaluate_variants(n, fresh)
+    }
+
+    /// Keeps the fittest of the evaluated variants, if it improves on the
+    /// candidate at `n`. Otherwise, the candidate loses a retry, and is
+    /// scouted once it has none left.
+    fn settle(&self,
+              current_working: &[Arc<CandidateOf<Ctx>>],
+              at: usize,
+              n: usize,
+              variants: Vec<Evaluated<Ctx::Solution, Ctx::Fitness>>,
+              rng: &mut XorShiftRng)
+              -> AbcResult<()> {
+        let mut variant: Option<CandidateOf<Ctx>> = None;
+        for (mut candidate, objectives) in variants {
+            let improvements = current_working[at].provenance.map_or(0, |p| p.improvements);
+            candidate.provenance = Some(self.provenance(n, Origin::Explored, improvements + 1)?);
+            self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+            self.consider_objectives(&candidate.solution, objectives)?;
+            self.consider_elite(&candidate)?;
+            if variant.as_ref().is_none_or(|v| self.hive.fitter(&candidate, v)) {
+                variant = Some(candidate);
+            }
+        }
+        let explored = self.explorations.fetch_add(1, Ordering::Relaxed) + 1;
+        self.visited[n].store(explored, Ordering::Relaxed);
+        let mut write_guard = self.working[n].write_at_index(Stage::Candidate, n)?;
+        if let Some(variant) = variant.filter(|v| self.hive.fitter(v, &write_guard.candidate)) {
+            self.successes.fetch_add(1, Ordering::Relaxed);
+            trace_event!(TRACE, fitness = variant.fitness, "improved");
+            let limit = self.limit_for(variant.fitness, current_working);
+            *write_guard = WorkingCandidate::new(variant, limit);
+            self.record_slot(n, write_guard.candidate.fitness)?;
+            self.notify(Some(n), |hook| hook.on_candidate_improved(n, &write_guard.candidate))?;
+            self.consider_improvement(&write_guard.candidate)?;
+            if let Some((_, Schedule::Improved)) = self.hive.local_search {
+                drop(write_guard);
+                self.refine(current_working, n)?;
+            }
+        } else {
+            write_guard.deplete();
+            // Scouting has been folded into the working process, unless
+            // the schedule has tasks for it.
+            if write_guard.expired() && !self.scout_tasks.load(Ordering::Relaxed) {
+                self.scouting[n].store(true, Ordering::Release);
+                let abandoned_fitness = write_guard.candidate.fitness;
+                drop(write_guard);
+                self.scout(current_working, n, abandoned_fitness, rng)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the candidate at `n`, which has already been flagged as
+    /// scouting, with a scout.
+    ///
+    /// The flag is cleared however this ends, so that a scout that fails
+    /// doesn't leave the candidate out of the search.
+    fn scout(&self,
+             current_working: &[Arc<CandidateOf<Ctx>>],
+             n: usize,
+             abandoned_fitness: f64,
+             rng: &mut XorShiftRng)
+             -> AbcResult<()> {
+        let result = self.replace(current_working, n, abandoned_fitness, rng);
+        self.scouting[n].store(false, Ordering::Release);
+        result
+    }
+
+    /// Does the work of `scout`.
+    fn replace(&self,
+               current_working: &[Arc<CandidateOf<Ctx>>],
+               n: usize,
+               abandoned_fitness: f64,
+               rng: &mut XorShiftRng)
+               -> AbcResult<()> {
+        let elites = self.top_k()?;
+        let field = self.whole_field(current_working)?;
+        let (mut candidate, objectives) = self.with_rng(n, rng, |mut rng| {
+            self.hive.scout_candidate(&field, n, &elites, &mut rng)
+        })?;
+        candidate.provenance = Some(self.provenance(n, Origin::Scouted, 0)?);
+        let candidate = Arc::new(candidate);
+        self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+        self.consider_elite(&candidate)?;
+        self.record_scout(n, abandoned_fitness, candidate.fitness)?;
+        self.consider_objectives(&candidate.solution, objectives)?;
+        self.consider_improvement(&candidate)?;
+        self.notify(Some(n), |hook| hook.on_scout(n, &candidate))?;
+        {
+            let mut write_guard = self.working[n].write_at_index(Stage::Candidate, n)?;
+            let limit = self.limit_for(candidate.fitness, &field);
+            *write_guard = WorkingCandidate::new(candidate, limit);
+            self.record_slot(n, write_guard.candidate.fitness)?;
+        }
+        Ok(())
+    }
+
+    /// Scouts the candidate at `n`, for a scout phase, if it has run out of
+    /// retries.
+    fn scout_expired(&self, current_working: &[Arc<CandidateOf<Ctx>>], n: usize, rng: &mut XorShiftRng) -> AbcResult<()> {
+        // Another thread may already be replacing the candidate.
+        if self.scouting[n].swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let expired = {
+            let read_guard = self.working[n].read_at_index(Stage::Candidate, n)?;
+            Some(read_guard.candidate.fitness).filter(|_| read_guard.expired())
+        };
+        match expired {
+            Some(abandoned_fitness) => self.scout(current_working, n, abandoned_fitness, rng),
+            None => {
+                self.scouting[n].store(false, Ordering::Release);
+                Ok(())
+            }
+        }
+    }
+
+    /// Passes on the outcome of a task, unless it failed because the
+    /// context panicked while exploring or evaluating a candidate, and the
+    /// hive tolerates another panic in a row. Then the candidate is scouted
+    /// anew instead, and if the scout panics too, that counts as the next
+    /// panic in the row.
+    fn isolate(&self, result: AbcResult<()>, rng: &mut XorShiftRng) -> AbcResult<()> {
+        let mut err = match result {
+            Ok(()) => {
+                // Only written when there is a count to clear, since every
+                // thread passes through here after every task.
+                if self.consecutive_panics.load(Ordering::Relaxed) > 0 {
+                    self.consecutive_panics.store(0, Ordering::Relaxed);
+                }
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+        loop {
+            let n = match isolable(&err) {
+                Some(n) if self.consecutive_panics.fetch_add(1, Ordering::Relaxed) < self.hive.panic_tolerance => n,
+                _ => return Err(err),
+            };
+            self.panics.fetch_add(1, Ordering::Relaxed);
+            trace_event!(WARN, index = n, error = %err, "task panicked");
+            // Another thread may already be replacing the candidate.
+            if self.scouting[n].swap(true, Ordering::AcqRel) {
+                return Ok(());
+            }
+            let abandoned_fitness = self.working[n].read_at_index(Stage::Candidate, n)?.candidate.fitness;
+            let current_working = self.current_working()?;
+            err = match self.scout(&current_working, n, abandoned_fitness, rng) {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+        }
+    }
+
+    /// Evaluates the variants of the candidate at `n`, in one batch if the
+    /// hive has an evaluator, or else each on its own thread if there are
+    /// several.
+    fn evaluate_variants(&self,
+                         n: usize,
+                         solutions: Vec<Ctx::Solution>)
+                         -> AbcResult<Vec<Evaluated<Ctx::Solution, Ctx::Fitness>>> {
+        if solutions.len() < 2 || self.hive.evaluator.is_some() || self.hive.executor == Executor::CurrentThread {
+            return self.hive.evaluate_batch(Some(n), solutions);
+        }
+        let span = Span::current();
+        scope(|scope| {
+            let handles = solutions.into_iter()
+                                   .map(|solution| {
+                                       let span = &span;
+                                       scope.spawn(move || span.in_scope(|| self.hive.evaluate(Some(n), solution)))
+                                   })
+                                   .collect::<Vec<_>>();
+            // Join every thread before reporting any error.
+            let results = handles.into_iter().map(|handle| handle.join()).collect::<Vec<_>>();
+            results.into_iter().collect()
+        })
+    }
+
+    /// Calls `f` with the source of randomness for the candidate at `n`: its
+    /// chaotic sequence, if the hive has them, or else `rng`.
+    fn with_rng<T, F>(&self, n: usize, rng: &mut XorShiftRng, f: F) -> AbcResult<T>
+        where F: FnOnce(&mut dyn Rng) -> AbcResult<T>
+    {
+        match self.chaos {
+            Some(ref chaos) => f(&mut *chaos[n].lock_at_index(Stage::Rng, n)?),
+            None => f(rng),
+        }
+    }
+
+    /// Runs the local search on the candidate at `n`, and keeps the result if
+    /// it is fitter.
+    fn refine(&self, current_working: &[Arc<CandidateOf<Ctx>>], n: usize) -> AbcResult<()> {
+        let search = match self.hive.local_search {
+            Some((ref search, _)) => search,
+            None => return Ok(()),
+        };
+        let original = self.working[n].read_at_index(Stage::Candidate, n)?.candidate.clone();
+
+        // Evaluation errors can't pass through the search, so the first is
+        // kept to be reported afterward.
+        let mut error = None;
+        let refined = {
+            let mut fitness = |solution: &Ctx::Solution| {
+                self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+                match self.hive.evaluate(Some(n), solution.clone()) {
+                    Ok((candidate, _)) => candidate.fitness,
+                    Err(err) => {
+                        error.get_or_insert(err);
+                        f64::NEG_INFINITY
+                    }
+                }
+            };
+            let context = self.hive.context()?;
+            catch_panic(Stage::LocalSearch, Some(n), || search.refine(&context, &original, &mut fitness))?
+        };
+        if let Some(err) = error {
+            return Err(err);
+        }
+        let solution = match refined {
+            Some(solution) => solution,
+            None => return Ok(()),
+        };
+
+        let (mut candidate, objectives) = self.hive.evaluate(Some(n), solution)?;
+        let improvements = original.provenance.map_or(0, |p| p.improvements);
+        candidate.provenance = Some(self.provenance(n, Origin::Refined, improvements)?);
+        self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+        self.consider_objectives(&candidate.solution, objectives)?;
+        self.consider_elite(&candidate)?;
+        let mut write_guard = self.working[n].write_at_index(Stage::Candidate, n)?;
+        if self.hive.fitter(&candidate, &write_guard.candidate) {
+            let limit = self.limit_for(candidate.fitness, current_working);
+            *write_guard = WorkingCandidate::new(candidate, limit);
+            self.record_slot(n, write_guard.candidate.fitness)?;
+            self.notify(Some(n), |hook| hook.on_candidate_improved(n, &write_guard.candidate))?;
+            self.consider_improvement(&write_guard.candidate)?;
+        }
+        Ok(())
+    }
+
+    /// Wraps up a round: records its statistics, then refines the best
+    /// working candidate if the local search is due.
+    fn end_round(&self, round: usize) -> AbcResult<()> {
+        let stats = self.record_round(round)?;
+        if let Some((_, Schedule::Best(every))) = self.hive.local_search {
+            if round.is_multiple_of(every) {
+                let current_working = self.current_working()?;
+                let best = current_working.iter()
+                                          .enumerate()
+                                          .fold(0, |best, (n, c)| {
+                                              if self.hive.fitter(c, &current_working[best]) { n } else { best }
+                                          });
+                self.refine(&current_working, best)?;
+            }
+        }
+        if let Some(ref mutex) = self.hive.round_end {
+            let mut hook = mutex.lock_at(Stage::Context)?;
+            let mut context = self.hive.context.write_at(Stage::Context)?;
+            catch_panic(Stage::Context, None, || hook(&mut context, &stats))?;
+        }
+        if let Some(ref mutex) = self.hive.controller {
+            let mut parameters = self.parameters();
+            let mut controller = mutex.lock_at(Stage::HiveObserver)?;
+            catch_panic(Stage::HiveObserver, None, || controller.adjust(&stats, &mut parameters))?;
+            self.set_parameters(parameters)?;
+        }
+        if self.hive.reevaluation.is_some_and(|every| round.is_multiple_of(every)) {
+            self.invalidate_fitness()?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the candidate at `n` with its opposite, if that is fitter.
+    fn jump(&self, current_working: &mut [Arc<CandidateOf<Ctx>>], n: usize) -> AbcResult<()> {
+        let context = self.hive.context()?;
+        let solution = catch_panic(Stage::Opposite, Some(n), || context.opposite(&current_working[n].solution))?;
+        let (mut candidate, objectives) = self.hive.evaluate(Some(n), solution)?;
+        candidate.provenance = Some(self.provenance(n, Origin::Opposed, 0)?);
+        self.evaluations.fetch_add(self.hive.samples(), Ordering::Relaxed);
+        self.consider_objectives(&candidate.solution, objectives)?;
+        self.consider_elite(&candidate)?;
+        let mut write_guard = self.working[n].write_at_index(Stage::Candidate, n)?;
+        if self.hive.fitter(&candidate, &write_guard.candidate) {
+            let limit = self.limit_for(candidate.fitness, current_working);
+            *write_guard = WorkingCandidate::new(candidate, limit);
+            self.record_slot(n, write_guard.candidate.fitness)?;
+            self.notify(Some(n), |hook| hook.on_candidate_improved(n, &write_guard.candidate))?;
+            self.consider_improvement(&write_guard.candidate)?;
+            // Explore from the new candidate.
+            current_working[n] = write_guard.candidate.clone();
+        }
+        Ok(())
+    }
+
+    /// Records that a candidate was created just now, for index `index`.
+    fn provenance(&self, index: usize, origin: Origin, improvements: usize) -> AbcResult<Provenance> {
+        Ok(Provenance {
+            round: self.lifetime_round()?,
+            index,
+            origin,
+            improvements,
+        })
+    }
+
+    /// Returns the round under way, counting over the hive's lifetime.
+    fn lifetime_round(&self) -> AbcResult<usize> {
+        Ok(self.rounds.load(Ordering::Relaxed) + self.get_round()?.unwrap_or(0))
+    }
+
+    /// Counts a scouting event, and adds it to the history.
+    fn record_scout(&self, index: usize, abandoned_fitness: f64, replacement_fitness: f64) -> AbcResult<()> {
+        self.scouts.fetch_add(1, Ordering::Relaxed);
+        self.scout_counts[index].fetch_add(1, Ordering::Relaxed);
+        let round = self.lifetime_round()?;
+        trace_event!(DEBUG, index, round, abandoned_fitness, replacement_fitness, "scout");
+        self.scout_history.lock_at(Stage::Stats)?.push(ScoutEvent {
+            round,
+            index,
+            abandoned_fitness,
+            replacement_fitness,
+        });
+        Ok(())
+    }
+
+    /// Adds the fitness of the slot at `n`'s new candidate to its history,
+    /// dropping the oldest entry once the history is full.
+    fn record_slot(&self, n: usize, fitness: f64) -> AbcResult<()> {
+        if self.hive.history_depth == 0 {
+            return Ok(());
+        }
+        let mut history = self.slot_history[n].lock_at_index(Stage::Candidate, n)?;
+        if history.len() == self.hive.history_depth {
+            history.pop_front();
+        }
+        history.push_back(fitness);
+        Ok(())
+    }
+
+    /// Decides how many retries a candidate entering the field gets.
+    fn limit_for(&self, fitness: f64, field: &[Arc<CandidateOf<Ctx>>]) -> usize {
+        let (worst, best) = fitness_range(field.iter().map(|c| c.fitness));
+        let explorations = self.explorations.load(Ordering::Relaxed);
+        let success_rate = if explorations > 0 {
+            Some(self.successes.load(Ordering::Relaxed) as f64 / explorations as f64)
+        } else {
+            None
+        };
+        self.hive.limit(fitness, worst, best, success_rate, self.retries.load(Ordering::Relaxed))
+    }
+
+    fn choose(&self,
+              current_working: &[Arc<CandidateOf<Ctx>>],
+              rng: &mut XorShiftRng,
+              buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>)
+              -> AbcResult<usize> {
+        if let Selection::Tournament(size) = self.hive.selection {
+            return self.tournament(current_working, size, rng, &mut buffers.entrants);
+        }
+
+        let round = self.lifetime_round()?;
+        let mut fitnesses = mem::take(&mut buffers.weights);
+        fitnesses.clear();
+        fitnesses.extend(current_working.iter().map(|candidate| candidate.fitness));
+        let fitnesses = self.hive.scale.scale(round, fitnesses);
+        let pressure = f64::from_bits(self.pressure.load(Ordering::Relaxed));
+        let fitnesses = if pressure == 1f64 {
+            fitnesses
+        } else {
+            // Collecting from the same vector reuses its space.
+            fitnesses.into_iter().map(|weight| weight.powf(pressure)).collect()
+        };
+        let fitnesses = self.hive.niche(current_working, fitnesses)?;
+        let fitnesses = self.hive.check_weights(fitnesses)?;
+
+        // Avoid observing candidates that are being scouted.
+        let entrants = &mut buffers.entrants;
+        entrants.clear();
+        entrants.extend((0..fitnesses.len()).filter(|&i| !self.is_scouting(i)));
+        let weights = entrants.iter().map(|&i| (i, fitnesses[i]));
+        let chosen = match roulette_in(weights, rng.next_f64(), &mut buffers.totals) {
+            Some(i) => i,
+
+            // If every weight is zero, pick one of the candidates at random.
+            None if !entrants.is_empty() => entrants[rng.gen_range(0, entrants.len())],
+
+            // If we are currently scouting all of the solutions, pick one at random.
+            None => rng.gen_range::<usize>(0, fitnesses.len()),
+        };
+        buffers.weights = fitnesses;
+        Ok(chosen)
+    }
+
+    /// Chooses the candidate for the worker task `n`, by the builder's
+    /// worker priority.
+    fn prioritize(&self, n: usize, rng: &mut XorShiftRng, buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>) -> AbcResult<usize> {
+        if self.hive.worker_priority == WorkerPriority::InOrder {
+            return Ok(n);
+        }
+        let explored = self.explorations.load(Ordering::Relaxed);
+        let retries = self.retries.load(Ordering::Relaxed).max(1);
+        let mut weights = mem::take(&mut buffers.weights);
+        weights.clear();
+        for (i, working) in self.working.iter().enumerate() {
+            let left = working.read_at_index(Stage::Candidate, i)?.retries().min(retries);
+            // Other threads may have visited since the count was read.
+            let stale = explored.saturating_sub(self.visited[i].load(Ordering::Relaxed)) as f64 / self.working.len() as f64;
+            let depleted = 1f64 - left as f64 / retries as f64;
+            weights.push((1f64 + stale) * (1f64 + depleted));
+        }
+
+        // Avoid visiting candidates that are being scouted.
+        let entrants = &mut buffers.entrants;
+        entrants.clear();
+        entrants.extend((0..weights.len()).filter(|&i| !self.is_scouting(i)));
+        let chosen = roulette_in(entrants.iter().map(|&i| (i, weights[i])), rng.next_f64(), &mut buffers.totals);
+        buffers.weights = weights;
+        // If every candidate is being scouted, the worker's own is skipped.
+        Ok(chosen.unwrap_or(n))
+    }
+
+    /// Chooses the fittest of `size` candidates drawn at random, listing
+    /// the entrants in `entrants`.
+    fn tournament(&self,
+                  current_working: &[Arc<CandidateOf<Ctx>>],
+                  size: usize,
+                  rng: &mut XorShiftRng,
+                  entrants: &mut Vec<usize>)
+                  -> AbcResult<usize> {
+        // Avoid observing candidates that are being scouted.
+        entrants.clear();
+        entrants.extend((0..current_working.len()).filter(|&i| !self.is_scouting(i)));
+
+        // If we are currently scouting all of the solutions, pick one at random.
+        if entrants.is_empty() {
+            return Ok(rng.gen_range::<usize>(0, current_working.len()));
+        }
+
+        let mut winner = entrants[rng.gen_range(0, entrants.len())];
+        for _ in 1..size {
+            let rival = entrants[rng.gen_range(0, entrants.len())];
+            // A NaN fitness never wins, unless every entrant has one.
+            if self.hive.fitter(&current_working[rival], &current_working[winner]) {
+                winner = rival;
+            }
+        }
+        Ok(winner)
+    }
+
+    /// For quick ABC, finds the fittest candidate within the radius of the
+    /// one at `index`. Otherwise, or if the context doesn't measure
+    /// distance, returns `index`.
+    fn best_neighbor(&self, field: &[Arc<CandidateOf<Ctx>>], index: usize) -> AbcResult<usize> {
+        let radius = match self.hive.quick {
+            Some(radius) if field.len() > 1 => radius,
+            _ => return Ok(index),
+        };
+        let context = self.hive.context()?;
+        let distances = catch_panic(Stage::Distance, Some(index), || {
+            field.iter()
+                 .map(|c| context.distance(&field[index].solution, &c.solution))
+                 .collect::<Option<Vec<f64>>>()
+        })?;
+        let distances = match distances {
+            Some(distances) => distances,
+            None => return Ok(index),
+        };
+        let within = radius * distances.iter().sum::<f64>() / (field.len() - 1) as f64;
+        Ok((0..field.len()).filter(|&j| distances[j] <= within && !self.is_scouting(j))
+                           .fold(index, |best, j| if self.hive.fitter(&field[j], &field[best]) { j } else { best }))
+    }
+
+    /// Whether the candidate at `n` is being replaced by a scout.
+    fn is_scouting(&self, n: usize) -> bool {
+        self.scouting[n].load(Ordering::Acquire)
+    }
+
+    fn execute(&self, task: &Task, rng: &mut XorShiftRng, buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>) -> AbcResult<()> {
+        let result = self.attempt(task, rng, buffers);
+        self.isolate(result, rng)
+    }
+
+    /// Carries out `task`, passing on any failure.
+    fn attempt(&self, task: &Task, rng: &mut XorShiftRng, buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>) -> AbcResult<()> {
+        if let Task::Worker(n) = *task {
+            if let Some(partners) = self.partners()? {
+                let n = self.prioritize(n, rng, buffers)?;
+                // If the worker's candidate is in the middle of being replaced, just skip it.
+                if self.is_scouting(n) {
+                    return Ok(());
+                }
+                self.snapshot_partners(n, partners, rng, buffers)?;
+                let result = self.work_on(&buffers.working, 0, n, rng);
+                buffers.working.clear();
+                return result;
+            }
+        }
+        let mut current_working = mem::take(&mut buffers.working);
+        let result = self.snapshot_into(&mut current_working)
+                         .and_then(|()| self.perform(task, &mut current_working, rng, buffers));
+        // Let go of the candidates, but keep the space for the next task.
+        current_working.clear();
+        buffers.working = current_working;
+        result
+    }
+
+    /// Carries out `task` on a snapshot of the field.
+    fn perform(&self,
+               task: &Task,
+               current_working: &mut [Arc<CandidateOf<Ctx>>],
+               rng: &mut XorShiftRng,
+               buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>)
+               -> AbcResult<()> {
+        let index = match *task {
+            Task::Worker(n) => {
+                let n = self.prioritize(n, rng, buffers)?;
+                // If the worker's candidate is in the middle of being replaced, just skip it.
+                if self.is_scouting(n) {
+                    return Ok(());
+                }
+                match self.hive.opposition {
+                    Some(rate) if rate > 0f64 && rng.next_f64() < rate => self.jump(current_working, n)?,
+                    _ => {}
+                }
+                n
+            }
+            Task::Observer(_) => {
+                let chosen = self.choose(current_working, rng, buffers)?;
+                self.best_neighbor(current_working, chosen)?
+            }
+            Task::Scout(n) => return self.scout_expired(current_working, n, rng),
+        };
+        self.work_on(current_working, index, index, rng)
+    }
+
+    /// Starts the builder's schedule of tasks afresh.
+    fn schedule(&self) -> Box<dyn TaskSchedule> {
+        let (workers, observers) = (self.hive.workers, self.observers.load(Ordering::Relaxed));
+        match self.hive.schedule {
+            Some(ref make_schedule) => make_schedule(workers, observers),
+            None => Box::new(TaskCycle::with_spec(workers, observers, self.hive.cycle.clone())),
+        }
+    }
+
+    /// Creates a task generator that respects the builder's stopping rules.
+    fn task_generator(&self) -> AbcResult<TaskGenerator> {
+        let mut tasks = TaskGenerator::new(self.schedule()).track_evaluations(self.evaluations.clone());
+        tasks.improve(self.get()?.fitness);
+        if let Some(budget) = self.hive.time_budget {
+            tasks = tasks.until(TimeLimit(budget));
+        }
+        if let Some(budget) = self.hive.evaluation_budget {
+            tasks = tasks.until(MaxEvaluations(budget));
+        }
+        if let Some((rounds, epsilon)) = self.hive.stagnation {
+            tasks = tasks.until(Stagnation::new(rounds, epsilon));
+        }
+        if let Some(ref make_condition) = self.hive.stop_condition {
+            tasks = tasks.until(make_condition());
+        }
+        Ok(tasks)
+    }
+
+    fn run(&self, tasks: TaskGenerator) -> AbcResult<()> {
+        self.install(tasks)?;
+        self.run_installed(false)
+    }
+
+    /// Sets the task generator for the next run.
+    ///
+    /// This is kept separate from running, so that a run started on another
+    /// thread can be stopped before that thread gets going.
+    fn install(&self, tasks: TaskGenerator) -> AbcResult<()> {
+        let mut guard = self.tasks.lock_at(Stage::Tasks)?;
+        // Drops anything left queued by a run that failed.
+        while self.steal().is_some() {}
+        self.scout_tasks.store(tasks.has_scouts(), Ordering::Relaxed);
+        *guard = Some(tasks);
+        Ok(())
+    }
+
+    /// Runs the installed tasks. With `whole_rounds`, or when the builder
+    /// needs it, threads finish each round before starting the next.
+    fn run_installed(&self, whole_rounds: bool) -> AbcResult<()> {
+        let _span = trace_span!(INFO, "run", executor = ?self.hive.executor).entered();
+        let whole_rounds = whole_rounds || self.hive.synchronous || self.hive.round_end.is_some();
+        let result = self.notify_context(|context| context.on_start()).and_then(|()| {
+            // Time spent between runs doesn't count toward the first round.
+            self.stats.lock_at(Stage::Stats)?.round_started = Some(Instant::now());
+            let result = match self.hive.executor {
+                _ if self.hive.deterministic => self.run_generations(),
+                Executor::Threads | Executor::Queued if whole_rounds => self.run_generations(),
+                Executor::Threads | Executor::Queued => self.run_threads(),
+                Executor::CurrentThread => self.run_generations(),
+                #[cfg(feature = "rayon")]
+                Executor::Rayon => self.run_rayon(),
+            };
+            // The context hears that the run stopped, even if it failed.
+            result.and(self.notify_context(|context| context.on_stop()))
+        });
+
+        // Returns `Ok(())` only if the run finished cleanly, and the task
+        // cycle is successfully cleared away.
+        //
+        // We avoid `?` because we want the cleanup to execute unconditionally.
+        result.and(self.tasks
+                       .lock_at(Stage::Tasks)
+                       .map(|mut tasks_guard| {
+                           if let Some(tasks) = tasks_guard.take() {
+                               self.rounds.fetch_add(tasks.round(), Ordering::Relaxed);
+                           }
+                       }))
+    }
+
+    /// Calls one of the context's lifecycle hooks.
+    fn notify_context<F: FnOnce(&Ctx)>(&self, hook: F) -> AbcResult<()> {
+        let context = self.hive.context()?;
+        catch_panic(Stage::Context, None, || hook(&context))
+    }
+
+    /// Draws a fresh RNG for each thread from the hive's RNG.
+    fn thread_rngs(&self) -> AbcResult<Vec<XorShiftRng>> {
+        let mut rng = self.rng.lock_at(Stage::Rng)?;
+        Ok((0..self.threads.load(Ordering::Relaxed)).map(|_| rng.gen()).collect())
+    }
+
+    fn run_threads(&self) -> AbcResult<()> {
+        // Each pass runs until the run ends, or the number of threads
+        // changes; in that case, the threads regroup for another pass.
+        loop {
+            self.regroup.store(false, Ordering::Relaxed);
+            self.run_threads_once()?;
+            if !self.regroup.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+        }
+    }
+
+    #[allow(clippy::manual_try_fold)]
+    fn run_threads_once(&self) -> AbcResult<()> {
+        let mut handles: Vec<ScopedJoinHandle<AbcResult<()>>> = Vec::new();
+        let thread_rngs = self.thread_rngs()?;
+        self.running.store(thread_rngs.len(), Ordering::Relaxed);
+        let queued = self.hive.executor == Executor::Queued;
+        let span = Span::current();
+
+        scope(|scope| {
+            for mut thread_rng in thread_rngs {
+                let span = &span;
+                handles.push(scope.spawn(move || {
+                    let mut buffers = Buffers::new();
+                    span.in_scope(|| loop {
+                        if self.regroup.load(Ordering::Relaxed) {
+                            return Ok(());
+                        }
+                        let task = if queued { self.next_queued_task()? } else { self.next_task()? };
+                        match task {
+                            Some(t) => {
+                                if let Err(err) = self.execute(&t, &mut thread_rng, &mut buffers) {
+                                    // Wind down the other threads, so that
+                                    // the error can be reported promptly.
+                                    self.stop().unwrap_or(());
+                                    return Err(err);
+                                }
+                            }
+                            None => return Ok(()),
+                        };
+                    })
+                }));
+            }
+
+            // Returns `Ok(())` only if all threads join cleanly. We avoid `?`
+            // so that every thread is joined.
+            handles.drain(..)
+                   .fold(Ok(()), |result, handle| result.and(handle.join()))
+        })
+    }
+
+    /// Runs one round at a time, in synchronous mode in two phases: the
+    /// workers, then the observers.
+    fn run_generations(&self) -> AbcResult<()> {
+        let mut thread_states = Vec::new();
+        loop {
+            let _span = trace_span!(DEBUG, "round", round = self.lifetime_round()? + 1).entered();
+            let (round, ended) = self.claim_round()?;
+            if round.is_empty() {
+                return Ok(());
+            }
+            if thread_states.len() != self.threads.load(Ordering::Relaxed) {
+                thread_states = self.thread_rngs()?.into_iter().map(|rng| (rng, Buffers::new())).collect();
+                self.running.store(thread_states.len(), Ordering::Relaxed);
+            }
+            let split = self.hive.synchronous || self.hive.deterministic;
+            let parts = if split { phases(&round) } else { vec![&round[..]] };
+            if self.hive.deterministic {
+                let (number, mut offset) = (self.lifetime_round()?, 0);
+                for part in parts {
+                    self.run_deterministic_phase(part, number, offset)?;
+                    offset += part.len();
+                }
+            } else {
+                for part in parts {
+                    self.run_phase(part, &mut thread_states)?;
+                }
+            }
+            if let Some(number) = ended {
+                self.end_round(self.rounds.load(Ordering::Relaxed) + number)?;
+            }
+        }
+    }
+
+    /// Works through `tasks` on scoped threads, one per RNG and set of
+    /// buffers, and waits for them all to finish. With
+    /// `Executor::CurrentThread`, this works through them in turn with the
+    /// first instead.
+    #[allow(clippy::manual_try_fold)]
+    fn run_phase(&self,
+                 tasks: &[Task],
+                 thread_states: &mut [ThreadState<Ctx>])
+                 -> AbcResult<()> {
+        if self.hive.executor == Executor::CurrentThread {
+            let (ref mut rng, ref mut buffers) = thread_states[0];
+            return tasks.iter().try_for_each(|task| self.execute(task, rng, buffers));
+        }
+        let next = AtomicUsize::new(0);
+        let span = Span::current();
+        let mut handles: Vec<ScopedJoinHandle<AbcResult<()>>> = Vec::new();
+
+        scope(|scope| {
+            for &mut (ref mut thread_rng, ref mut buffers) in thread_states.iter_mut() {
+                let (next, span) = (&next, &span);
+                handles.push(scope.spawn(move || {
+                    span.in_scope(|| {
+                        while let Some(task) = tasks.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            self.execute(task, thread_rng, buffers)?;
+                        }
+                        Ok(())
+                    })
+                }));
+            }
+
+            handles.drain(..)
+                   .fold(Ok(()), |result, handle| result.and(handle.join()))
+        })
+    }
+
+    /// Works through `tasks` in deterministic mode, as the tasks numbered
+    /// from `offset` in `round`.
+    ///
+    /// Every task explores the field as it stood at the start of the phase,
+    /// on the hive's threads, then the results are applied in task order.
+    #[allow(clippy::manual_try_fold)]
+    fn run_deterministic_phase(&self, tasks: &[Task], round: usize, offset: usize) -> AbcResult<()> {
+        let rngs = (0..tasks.len()).map(|i| Mutex::new(self.hive.task_rng(round, offset + i))).collect::<Vec<_>>();
+
+        // Jumping to an opposite changes the field, so it is settled first.
+        let mut current_working = self.current_working()?;
+        if let Some(rate) = self.hive.opposition.filter(|&rate| rate > 0f64) {
+            for (task, rng) in tasks.iter().zip(&rngs) {
+                if let Task::Worker(n) = *task {
+                    if rng.lock_at(Stage::Rng)?.next_f64() < rate {
+                        self.jump(&mut current_working, n)?;
+                    }
+                }
+            }
+        }
+        let current_working = &current_working[..];
+
+        let visits = tasks.iter().map(|_| Mutex::new(None)).collect::<Vec<_>>();
+        let visit = |i: usize, buffers: &mut Buffers<Ctx::Solution, Ctx::Fitness>| -> AbcResult<()> {
+            let mut rng = rngs[i].lock_at(Stage::Rng)?;
+            let n = match tasks[i] {
+                Task::Worker(n) => self.prioritize(n, &mut rng, buffers)?,
+                Task::Observer(_) => {
+                    let chosen = self.choose(current_working, &mut rng, buffers)?;
+                    self.best_neighbor(current_working, chosen)?
+                }
+                // Scouts only change the field, so they wait for the settling.
+                Task::Scout(_) => return Ok(()),
+            };
+            // Panics that the hive may tolerate are settled in task order
+            // along with everything else.
+            let variants = match self.visit(current_working, n, n, &mut rng) {
+                Err(err) if isolable(&err).is_none() => return Err(err),
+                result => result,
+            };
+            *visits[i].lock_at_index(Stage::Candidate, n)? = Some((n, variants));
+            Ok(())
+        };
+        if self.hive.executor == Executor::CurrentThread {
+            let mut buffers = Buffers::new();
+            (0..tasks.len()).try_for_each(|i| visit(i, &mut buffers))?;
+        } else {
+            let next = AtomicUsize::new(0);
+            let span = Span::current();
+            let threads = self.threads.load(Ordering::Relaxed).min(tasks.len());
+            scope(|scope| {
+                let handles = (0..threads).map(|_| {
+                                              let (next, span, visit) = (&next, &span, &visit);
+                                              scope.spawn(move || {
+                                                  span.in_scope(|| {
+                                                      let mut buffers = Buffers::new();
+                                                      let mut i = next.fetch_add(1, Ordering::Relaxed);
+                                                      while i < tasks.len() {
+                                                          visit(i, &mut buffers)?;
+                                                          i = next.fetch_add(1, Ordering::Relaxed);
+                                                      }
+                                                      Ok(())
+                                                  })
+                                              })
+                                          })
+                                          .collect::<Vec<ScopedJoinHandle<AbcResult<()>>>>();
+                handles.into_iter()
+                       .fold(Ok(()), |result, handle| result.and(handle.join()))
+            })?;
+        }
+
+        for ((task, visit), rng) in tasks.iter().zip(visits).zip(rngs) {
+            let mut rng = rng.into_inner().at(Stage::Rng)?;
+            if let Task::Scout(n) = *task {
+                let result = self.scout_expired(current_working, n, &mut rng);
+                self.isolate(result, &mut rng)?;
+            } else if let Some((n, variants)) = visit.into_inner().at(Stage::Candidate)? {
+                let result = variants.and_then(|variants| self.settle(current_working, n, n, variants, &mut rng));
+                self.isolate(result, &mut rng)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Claims the rest of the current round, along with the number of the
+    /// next round if this one ends.
+    fn claim_round(&self) -> AbcResult<(Vec<Task>, Option<usize>)> {
+        let mut guard = self.tasks.lock_at(Stage::Tasks)?;
+        Ok(match guard.as_mut() {
+            Some(gen) => {
+                let number = gen.round();
+                let round = gen.next_round();
+                (round, if gen.round() > number { Some(gen.round()) } else { None })
+            }
+            None => (Vec::new(), None),
+        })
+    }
+
+    /// Claims the next task, recording statistics if it ends a round.
+    fn next_task(&self) -> AbcResult<Option<Task>> {
+        let (task, ended) = {
+            let mut guard = self.tasks.lock_at(Stage::Tasks)?;
+            match guard.as_mut() {
+                Some(gen) => {
+                    let round = gen.round();
+                    let task = gen.next();
+                    (task, if gen.round() > round { Some(gen.round()) } else { None })
+                }
+                None => (None, None),
+            }
+        };
+        if let Some(round) = ended {
+            self.claimed_round(round)?;
+        }
+        Ok(task)
+    }
+
+    /// Claims the next task from the queue, queueing up the next round if
+    /// it has run dry.
+    fn next_queued_task(&self) -> AbcResult<Option<Task>> {
+        let claimed = match self.steal() {
+            Some(claimed) => Some(claimed),
+            None => {
+                let mut guard = self.tasks.lock_at(Stage::Tasks)?;
+                // Another thread may have queued the next round while this
+                // one waited for the lock.
+                match (self.steal(), guard.as_mut()) {
+                    (Some(claimed), _) => Some(claimed),
+                    (None, Some(gen)) => {
+                        let round = gen.next_round();
+                        let (last, number) = (round.len().saturating_sub(1), gen.round());
+                        let mut claimed = round.into_iter()
+                                               .enumerate()
+                                               .map(|(i, task)| (task, if i == last { Some(number) } else { None }));
+                        let first = claimed.next();
+                        for rest in claimed {
+                            self.queue.push(rest);
+                        }
+                        first
+                    }
+                    (None, None) => None,
+                }
+            }
+        };
+        match claimed {
+            Some((task, ended)) => {
+                if let Some(round) = ended {
+                    self.claimed_round(round)?;
+                }
+                Ok(Some(task))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Takes a task from the queue, if there are any left.
+    fn steal(&self) -> Option<(Task, Option<usize>)> {
+        steal_task(&self.queue)
+    }
+
+    /// Ends a round once its last task has been claimed, and decides whether
+    /// the threads need to regroup.
+    fn claimed_round(&self, round: usize) -> AbcResult<()> {
+        // The round's last task has only been claimed, not finished, but
+        // waiting for it would hold up the other threads.
+        self.end_round(self.rounds.load(Ordering::Relaxed) + round)?;
+        if self.threads.load(Ordering::Relaxed) != self.running.load(Ordering::Relaxed) {
+            self.regroup.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Summarizes the working candidates at the end of a round.
+    fn record_round(&self, round: usize) -> AbcResult<RoundStats> {
+        let current_working = self.current_working()?;
+        let fitnesses = current_working.iter().map(|c| c.fitness).collect::<Vec<_>>();
+        let diversity = self.hive.diversity(&current_working)?;
+        let tasks = self.tasks.lock_at(Stage::Tasks)?.as_ref().map_or(0, TaskGenerator::tasks_per_round);
+        let stats = {
+            // Read the counters under the lock, so that they never run
+            // backwards if two rounds are recorded at once.
+            let mut stats_guard = self.stats.lock_at(Stage::Stats)?;
+            let evaluations = self.evaluations.load(Ordering::Relaxed);
+            let scouts = self.scouts.load(Ordering::Relaxed);
+            let explorations = self.explorations.load(Ordering::Relaxed);
+            let successes = self.successes.load(Ordering::Relaxed);
+            let panics = self.panics.load(Ordering::Relaxed);
+            let mut stats = RoundStats::new(round,
+                                            &fitnesses,
+                                            scouts - stats_guard.scouts,
+                                            evaluations - stats_guard.evaluations);
+            stats.explorations = explorations - stats_guard.explorations;
+            stats.improvements = successes - stats_guard.successes;
+            stats.panics = panics - stats_guard.panics;
+            stats.diversity = diversity;
+            stats_guard.evaluations = evaluations;
+            stats_guard.scouts = scouts;
+            stats_guard.explorations = explorations;
+            stats_guard.successes = successes;
+            stats_guard.panics = panics;
+            stats_guard.rounds.push(stats.clone());
+            if let Some(started) = stats_guard.round_started {
+                let now = Instant::now();
+                stats_guard.timings.push(RoundTiming {
+                    round,
+                    duration: now - started,
+                    tasks,
+                });
+                stats_guard.round_started = Some(now);
+            }
+            stats
+        };
+        trace_event!(DEBUG,
+                     round,
+                     best = stats.best,
+                     mean = stats.mean,
+                     worst = stats.worst,
+                     scouts = stats.scouts,
+                     evaluations = stats.evaluations,
+                     "round complete");
+        if let Some(mutex) = self.stats_sender.as_ref() {
+            // A dropped receiver doesn't mean the run should end.
+            mutex.lock_at(Stage::Stream)?.send(stats.clone()).unwrap_or(());
+        }
+        self.flush_streams()?;
+        if self.event_sender.is_some() {
+            let best = current_working.into_iter()
+                                      .fold(None, |best: Option<Arc<CandidateOf<Ctx>>>, next| {
+                                          match best {
+                                              Some(best) if !self.hive.fitter(&next, &best) => Some(best),
+                                              _ => Some(next),
+                                          }
+                                      });
+            if let Some(best) = best {
+                self.send_event(HiveEvent::Round {
+                    stats: stats.clone(),
+                    best,
+                })?;
+            }
+        }
+
+        // Every observer hears about the round, even if an earlier one has
+        // already asked to stop.
+        let mut stop = false;
+        {
+            let mut hooks_guard = self.hive.hooks.lock_at(Stage::HiveObserver)?;
+            for hook in hooks_guard.iter_mut() {
+                stop |= catch_panic(Stage::HiveObserver, None, || hook.on_round_complete(&stats))?;
+            }
+        }
+        if stop {
+            self.stop()?;
+        }
+        self.report_progress(round)?;
+        Ok(stats)
+    }
+
+    /// Tells the progress hook, if there is one, how the run is going.
+    fn report_progress(&self, round: usize) -> AbcResult<()> {
+        let mutex = match self.hive.progress {
+            Some(ref mutex) => mutex,
+            None => return Ok(()),
+        };
+        // The initial population is recorded before there is a run.
+        let state = match self.tasks.lock_at(Stage::Tasks)?.as_ref() {
+            Some(tasks) => tasks.state(),
+            None => return Ok(()),
+        };
+        let planned = self.planned_rounds.load(Ordering::Relaxed);
+        let progress = Progress::new(round.saturating_sub(self.rounds.load(Ordering::Relaxed)),
+                                     if planned > 0 { Some(planned) } else { None },
+                                     self.best_fitness(),
+                                     state.evaluations,
+                                     state.elapsed);
+        let mut report = mutex.lock_at(Stage::HiveObserver)?;
+        catch_panic(Stage::HiveObserver, None, || report(&progress))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn split_rng(&self) -> XorShiftRng {
+        match self.rng.lock() {
+            Ok(mut rng) => rng.gen(),
+            Err(_) => thread_rng().gen(),
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn run_rayon(&self) -> AbcResult<()> {
+        // As with threads, a new pool is started whenever the number of
+        // threads changes.
+        loop {
+            self.regroup.store(false, Ordering::Relaxed);
+            self.run_rayon_once()?;
+            if !self.regroup.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn run_rayon_once(&self) -> AbcResult<()> {
+        use rayon::prelude::*;
+
+        let threads = self.threads.load(Ordering::Relaxed);
+        let pool = rayon::ThreadPoolBuilder::new()
+                       .num_threads(threads)
+                       .build()
+                       .expect("failed to start the rayon thread pool");
+
+        pool.install(|| {
+            loop {
+                let span = trace_span!(DEBUG, "round", round = self.lifetime_round()? + 1);
+                let _entered = span.clone().entered();
+                let (round, ended) = self.claim_round()?;
+                if round.is_empty() {
+                    return Ok(());
+                }
+
+                // Rayon may split the round any number of ways, so each split
+                // seeds its own RNG from the hive's.
+                let parts = if self.hive.synchronous { phases(&round) } else { vec![&round[..]] };
+                for phase in parts {
+                    phase.par_iter()
+                         .try_for_each_init(|| (self.split_rng(), Buffers::new()),
+                                            |&mut (ref mut rng, ref mut buffers), task| {
+                                                span.in_scope(|| self.execute(task, rng, buffers))
+                                            })?;
+                }
+                if let Some(number) = ended {
+                    self.end_round(self.rounds.load(Ordering::Relaxed) + number)?;
+                    if self.threads.load(Ordering::Relaxed) != threads {
+                        self.regroup.store(true, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                }
+            }
+        })
+    }
+
+    /// Runs for a fixed number of rounds, then return the best solution found.
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok` with a `Candidate`.
+    pub fn run_for_rounds(&self, rounds: usize) -> AbcResult<CandidateOf<Ctx>> {
+        let tasks = self.task_generator()?.max_rounds(rounds);
+        self.planned_rounds.store(rounds, Ordering::Relaxed);
+        let result = self.run(tasks);
+        self.planned_rounds.store(0, Ordering::Relaxed);
+        result?;
+        self.get().map(|guard| (**guard).clone())
+    }
+
+    /// Runs exactly one round on the calling thread (and the hive's worker
+    /// threads), and returns its summary.
+    ///
+    /// Every task of the round finishes before this returns, so the summary
+    /// describes the whole round. This makes it easy to drive the hive from
+    /// an outer loop, for instance to interleave it with another optimizer;
+    /// see also [`rounds`](#method.rounds). The builder's stopping rules,
+    /// which apply to whole runs, are left to the caller. If the hive is
+    /// [stopped](#method.stop) partway through, the summary describes the
+    /// last round that ended.
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok` with a `RoundSummary`.
+    pub fn step(&self) -> AbcResult<RoundSummary<Ctx::Solution, Ctx::Fitness>> {
+        let tasks = TaskGenerator::new(self.schedule())
+                        .track_evaluations(self.evaluations.clone())
+                        .max_rounds(1);
+        self.install(tasks)?;
+        self.run_installed(true)?;
+        let last = self.stats.lock_at(Stage::Stats)?.rounds.last().cloned();
+        let stats = match last {
+            Some(stats) => stats,
+            None => {
+                // Only a hive resumed from a snapshot, and stopped before the
+                // round ended, has nothing recorded.
+                let fitnesses = self.current_working()?.iter().map(|c| c.fitness).collect::<Vec<_>>();
+                RoundStats::new(self.lifetime_round()?, &fitnesses, 0, 0)
+            }
+        };
+        Ok(RoundSummary {
+            stats,
+            best: (**self.get()?).clone(),
+        })
+    }
+
+    /// Returns an endless iterator that runs one round for each item, as
+    /// [`step`](#method.step) does. It ends after an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 5).build().unwrap();
+    /// for summary in hive.rounds().take(20) {
+    ///     let summary = summary.unwrap();
+    ///     assert!(summary.best.fitness >= summary.stats.best);
+    /// }
+    /// # }
+    /// ```
+    pub fn rounds(&self) -> Rounds<'_, Ctx> {
+        Rounds {
+            hive: self,
+            done: false,
+        }
+    }
+
+    /// Runs until `duration` has elapsed, then return the best solution found.
+    ///
+    /// As with [`set_time_budget`](struct.HiveBuilder.html#method.set_time_budget),
+    /// tasks that are underway when time runs out are allowed to finish. If
+    /// the builder also set a budget, the hive stops at whichever comes first.
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok` with a `Candidate`.
+    pub fn run_for_duration(&self, duration: Duration) -> AbcResult<CandidateOf<Ctx>> {
+        let tasks = self.task_generator()?.until(TimeLimit(duration));
+        self.run(tasks)?;
+        self.get().map(|guard| (**guard).clone())
+    }
+
+    /// Runs until `condition` is met, then returns the best solution found.
+    ///
+    /// The condition applies alongside any stopping rules set on the
+    /// builder; whichever is met first stops the run.
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok` with a `Candidate`.
+    pub fn run_with_condition<C>(&self, condition: C) -> AbcResult<CandidateOf<Ctx>>
+        where C: StopCondition + 'static
+    {
+        let tasks = self.task_generator()?.until(condition);
+        self.run(tasks)?;
+        self.get().map(|guard| (**guard).clone())
+    }
+
+    /// Runs until the best solution satisfies `target`, then returns it.
+    ///
+    /// The target is checked against the current best before running, and
+    /// again each time the hive finds an improvement. Since the hive may
+    /// never find a satisfactory solution, consider combining this with a
+    /// [time budget](struct.HiveBuilder.html#method.set_time_budget).
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok` with a `Candidate`.
+    pub fn run_until<F>(&self, target: F) -> AbcResult<CandidateOf<Ctx>>
+        where F: Fn(&CandidateOf<Ctx>) -> bool + Send + Sync + 'static
+    {
+        {
+            let best_guard = self.get()?;
+            if target(&best_guard) {
+                return Ok((**best_guard).clone());
+            }
+            *self.target.write_at(Stage::Stopping)? = Some(Box::new(target));
+        }
+
+        let result = self.task_generator().and_then(|tasks| self.run(tasks));
+        *self.target.write_at(Stage::Stopping)? = None;
+        result?;
+        self.get().map(|guard| (**guard).clone())
+    }
+
+    /// Runs until the best solution's fitness reaches `fitness`, then returns it.
+    ///
+    /// This is a shorthand for [`run_until`](#method.run_until).
+    pub fn run_until_fitness(&self, fitness: f64) -> AbcResult<CandidateOf<Ctx>> {
+        self.run_until(move |candidate| candidate.fitness >= fitness)
+    }
+
+    /// Run indefinitely.
+    ///
+    /// The run ends once the hive is stopped, or a stopping rule set on the
+    /// builder is met. A [`CancelToken`](stop/struct.CancelToken.html) given
+    /// to [`set_stop_condition`](struct.HiveBuilder.html#method.set_stop_condition)
+    /// lets the application end it from its own shutdown handling.
+    ///
+    /// If one of the `Context` methods panics, or a worker thread panics while
+    /// working, this will return `Err(abc::Error)` describing the failure.
+    /// Otherwise, it will return `Ok(())`.
+    pub fn run_forever(&self) -> AbcResult<()> {
+        let tasks = self.task_generator()?;
+        self.run(tasks)
+    }
+
+    /// Stops a running hive.
+    ///
+    /// If a worker thread has panicked, this returns `Err(abc::Error)`.
+    pub fn stop(&self) -> AbcResult<()> {
+        stop_tasks(&self.tasks, &self.queue)
+    }
+
+    /// Each new best candidate will be sent to `sender`, in place of any
+    /// subscribers so far.
+    ///
+    /// This is kept in a separate function so that the hive can be borrowed
+    /// while running.
+    pub fn set_sender(&mut self, sender: Sender<Arc<CandidateOf<Ctx>>>) {
+        self.set_stream_sender(StreamSender::Unbounded(sender));
+    }
+
+    fn set_stream_sender(&mut self, mut sender: BestSender<Ctx::Solution, Ctx::Fitness>) {
+        if let Ok(best_guard) = self.best.lock() {
+            sender.send(best_guard.clone()).unwrap_or(());
+        }
+        if let Ok(senders) = self.senders.get_mut() {
+            *senders = vec![sender];
+        }
+    }
+
+    /// Each new best candidate will also be sent to `sender`, starting with
+    /// the current best, alongside any other subscribers.
+    ///
+    /// Subscribers can be added while the hive is running. Once a
+    /// subscriber's receiver is dropped, it is forgotten; once every
+    /// subscriber's is, the run stops, as with [`stream`](#method.stream).
+    pub fn add_sender(&self, sender: Sender<Arc<CandidateOf<Ctx>>>) -> AbcResult<()> {
+        self.add_stream_sender(StreamSender::Unbounded(sender))
+    }
+
+    /// Subscribes to the new best candidates on a channel of their own,
+    /// buffered as set by
+    /// [`HiveBuilder::set_stream_buffer`](struct.HiveBuilder.html#method.set_stream_buffer).
+    ///
+    /// See [`add_sender`](#method.add_sender).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 10).build().unwrap();
+    /// let (log, monitor) = (hive.subscribe().unwrap(), hive.subscribe().unwrap());
+    /// let best = hive.run_for_rounds(20).unwrap();
+    /// assert_eq!(log.try_iter().last().unwrap().fitness, best.fitness);
+    /// assert_eq!(monitor.try_iter().last().unwrap().fitness, best.fitness);
+    /// # }
+    /// ```
+    pub fn subscribe(&self) -> AbcResult<Receiver<Arc<CandidateOf<Ctx>>>> {
+        let (sender, receiver) = stream::open(self.hive.stream_buffer);
+        self.add_stream_sender(sender)?;
+        Ok(receiver)
+    }
+
+    fn add_stream_sender(&self, mut sender: BestSender<Ctx::Solution, Ctx::Fitness>) -> AbcResult<()> {
+        // Holding the best candidate keeps any improvement from slipping
+        // past the new subscriber.
+        let best_guard = self.best.lock_at(Stage::Best)?;
+        if sender.send(best_guard.clone()).is_ok() {
+            self.senders.lock_at(Stage::Stream)?.push(sender);
+        }
+        Ok(())
+    }
+
+    /// Each new best candidate, and a summary of each round, will be sent to
+    /// `sender`.
+    ///
+    /// This is kept in a separate function so that the hive can be borrowed
+    /// while running.
+    pub fn set_event_sender(&mut self, sender: Sender<HiveEvent<Ctx::Solution, Ctx::Fitness>>) {
+        self.set_event_stream_sender(StreamSender::Unbounded(sender));
+    }
+
+    fn set_event_stream_sender(&mut self, mut sender: StreamSender<HiveEvent<Ctx::Solution, Ctx::Fitness>>) {
+        if let Ok(best_guard) = self.best.lock() {
+            sender.send(HiveEvent::NewBest(best_guard.clone())).unwrap_or(());
+        }
+        self.event_sender = Some(Mutex::new(sender));
+    }
+
+    /// Returns the non-dominated solutions found so far, in no particular order.
+    ///
+    /// This is empty unless the context scores several objectives (see
+    /// [`evaluate_objectives`](trait.Context.html#method.evaluate_objectives)).
+    pub fn pareto_front(&self) -> AbcResult<Vec<ParetoMember<Ctx::Solution>>> {
+        Ok(self.pareto.lock_at(Stage::Pareto)?.members().to_vec())
+    }
+
+    /// Each change to the Pareto front will be sent to `sender`.
+    ///
+    /// The whole front is sent each time, starting with the current front.
+    pub fn set_pareto_sender(&mut self, sender: Sender<Vec<ParetoMember<Ctx::Solution>>>) {
+        if let Ok(pareto_guard) = self.pareto.lock() {
+            sender.send(pareto_guard.members().to_vec()).unwrap_or(());
+        }
+        self.pareto_sender = Some(Mutex::new(sender));
+    }
+
+    /// Returns the statistics recorded after each round so far.
+    ///
+    /// The first entry describes the initial population (unless the hive was
+    /// resumed from a snapshot), and one is added as each round ends. With
+    /// the default executor, a round ends once its last task has been
+    /// claimed, so that task's result may only show up in the next round.
+    pub fn stats(&self) -> AbcResult<Vec<RoundStats>> {
+        Ok(self.stats.lock_at(Stage::Stats)?.rounds.clone())
+    }
+
+    /// The statistics for each round will be sent to `sender` as it ends.
+    pub fn set_stats_sender(&mut self, sender: Sender<RoundStats>) {
+        self.stats_sender = Some(Mutex::new(sender));
+    }
+
+    /// Returns the total number of fitness evaluations the hive has made,
+    /// including those for the initial population.
+    pub fn evaluations(&self) -> usize {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    /// Whether `a` is fitter than `b`, as the hive's objective compares them.
+    pub(crate) fn fitter(&self, a: &CandidateOf<Ctx>, b: &CandidateOf<Ctx>) -> bool {
+        self.hive.fitter(a, b)
+    }
+
+    /// Spends `evaluations` fitness evaluations on random search, on this
+    /// thread, making and scoring solutions as the hive does, for
+    /// [`compare`](compare/fn.compare.html). Returns the best candidate, and
+    /// each rise in the best fitness, counting a round for each round's
+    /// worth of tasks in the hive's task schedule.
+    pub(crate) fn random_search(&self, evaluations: usize) -> AbcResult<(CandidateOf<Ctx>, Vec<Improvement>)> {
+        let mut rng = self.hive.new_rng();
+        let per_round = self.schedule().tasks_per_round().max(1);
+        let started = Instant::now();
+        let (mut best, mut improvements, mut spent) = (None::<CandidateOf<Ctx>>, Vec::new(), 0);
+        // Draws at least one solution, even without a budget.
+        loop {
+            let solution = {
+                let context = self.hive.context()?;
+                catch_panic(Stage::Make, None, || context.make_with_rng(&mut rng))?
+            };
+            let (candidate, _) = self.hive.evaluate(None, solution)?;
+            spent += self.hive.samples();
+            if best.as_ref().is_none_or(|best| self.hive.fitter(&candidate, best)) {
+                improvements.push(Improvement {
+                    round: spent / per_round,
+                    fitness: candidate.fitness,
+                    evaluations: spent,
+                    elapsed: started.elapsed(),
+                });
+                best = Some(candidate);
+            }
+            if spent >= evaluations {
+                return Ok((best.expect("random search keeps its first solution"), improvements));
+            }
+        }
+    }
+
+    /// Returns the parameters in use, as tuned by any
+    /// [`ParameterController`](tuning/trait.ParameterController.html).
+    pub fn parameters(&self) -> Parameters {
+        Parameters {
+            workers: self.working.len(),
+            observers: self.observers.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            pressure: f64::from_bits(self.pressure.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Puts a controller's parameters into effect from the next round.
+    fn set_parameters(&self, parameters: Parameters) -> AbcResult<()> {
+        self.retries.store(parameters.retries, Ordering::Relaxed);
+        if parameters.pressure.is_finite() && parameters.pressure >= 0f64 {
+            self.pressure.store(parameters.pressure.to_bits(), Ordering::Relaxed);
+        }
+        self.observers.store(parameters.observers, Ordering::Relaxed);
+        if let Some(tasks) = self.tasks.lock_at(Stage::Tasks)?.as_mut() {
+            tasks.set_observers(parameters.observers);
+        }
+        Ok(())
+    }
+
+    /// Returns the total number of tasks whose exploration or evaluation
+    /// panicked or timed out, and which the hive kept running through. See
+    /// [`set_panic_tolerance`](struct.HiveBuilder.html#method.set_panic_tolerance).
+    pub fn panic_count(&self) -> usize {
+        self.panics.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of candidates abandoned and replaced by scouts.
+    pub fn scout_count(&self) -> usize {
+        self.scouts.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times each candidate has been abandoned, by index.
+    pub fn scout_counts(&self) -> Vec<usize> {
+        self.scout_counts.iter().map(|count| count.load(Ordering::Relaxed)).collect()
+    }
+
+    /// Returns the latest fitnesses of the working candidate's slot at
+    /// `index`, oldest first, up to the depth set by
+    /// [`HiveBuilder::set_slot_history`](struct.HiveBuilder.html#method.set_slot_history).
+    ///
+    /// Each entry is the fitness of the candidate in the slot after it
+    /// changed, so the history traces the food source's trajectory, across
+    /// any abandonments, for plotting. Without a depth, it is always empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not the index of a working candidate.
+    pub fn slot_history(&self, index: usize) -> AbcResult<Vec<f64>> {
+        let history = self.slot_history[index].lock_at_index(Stage::Candidate, index)?;
+        Ok(history.iter().cloned().collect())
+    }
+
+    /// Returns every rise in the best fitness so far, in the order they
+    /// happened, starting with the best of the initial population.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 10).build().unwrap();
+    /// let best = hive.run_for_rounds(50).unwrap();
+    /// let history = hive.improvement_history().unwrap();
+    /// assert!(history.windows(2).all(|pair| pair[0].fitness < pair[1].fitness));
+    /// assert_eq!(history.last().unwrap().fitness, best.fitness);
+    /// # }
+    /// ```
+    pub fn improvement_history(&self) -> AbcResult<Vec<Improvement>> {
+        Ok(self.improvements.lock_at(Stage::Stats)?.clone())
+    }
+
+    /// Returns how long each round has taken, in the order they ended.
+    ///
+    /// Rounds are timed from the end of the one before, or from the start
+    /// of their run, so time between runs isn't counted. Rounds that a run
+    /// stopped partway through aren't recorded. In free-running threaded
+    /// mode, a round ends once its last task is claimed, rather than
+    /// finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 10).build().unwrap();
+    /// hive.run_for_rounds(20).unwrap();
+    /// let timings = hive.timings().unwrap();
+    /// assert_eq!(timings.len(), 20);
+    /// println!("{} tasks per second in the last round", timings[19].tasks_per_second());
+    /// # }
+    /// ```
+    pub fn timings(&self) -> AbcResult<Vec<RoundTiming>> {
+        Ok(self.stats.lock_at(Stage::Stats)?.timings.clone())
+    }
+
+    /// Returns every scouting event so far, in the order they happened.
+    ///
+    /// To be told of scouting events as they happen, register a
+    /// [`HiveObserver`](trait.HiveObserver.html) with an `on_scout` callback.
+    pub fn scout_history(&self) -> AbcResult<Vec<ScoutEvent>> {
+        Ok(self.scout_history.lock_at(Stage::Stats)?.clone())
+    }
+
+    /// Changes the number of threads the hive runs on.
+    ///
+    /// This may be called while the hive is running, from another thread or
+    /// a [`HiveObserver`](trait.HiveObserver.html). The change takes effect
+    /// at the end of the current round: the threads finish the tasks they
+    /// have claimed, then regroup with the new number. Otherwise, it applies
+    /// from the next run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is 0.
+    pub fn set_threads(&self, threads: usize) {
+        if threads == 0 {
+            panic!("Hive must run on at least one thread.");
+        }
+        self.threads.store(threads, Ordering::Relaxed);
+    }
+
+    /// Returns the number of threads the hive runs on, as set by
+    /// [`HiveBuilder::set_threads`](struct.HiveBuilder.html#method.set_threads)
+    /// or [`set_threads`](#method.set_threads).
+    pub fn threads(&self) -> usize {
+        self.threads.load(Ordering::Relaxed)
+    }
+
+    /// Grows the colony by `n` working candidates.
+    ///
+    /// The new candidates are generated and evaluated as the initial
+    /// population was, one at a time on this thread. Since this borrows the
+    /// hive mutably, it can only be called between runs; the next run uses
+    /// the larger colony. The number of observers is unchanged.
+    pub fn add_workers(&mut self, n: usize) -> AbcResult<()> {
+        let mut rng = self.rng.get_mut().at(Stage::Rng)?.clone();
+        for _ in 0..n {
+            let index = self.working.len();
+            let (mut candidate, objectives) = self.hive.new_candidate(index, &mut rng)?;
+            candidate.provenance = Some(self.provenance(index, Origin::Initial, 0)?);
+            let candidate = Arc::new(candidate);
+            let evaluations = if self.hive.opposition.is_some() { 2 } else { 1 };
+            self.evaluations.fetch_add(evaluations * self.hive.samples(), Ordering::Relaxed);
+            self.consider_objectives(&candidate.solution, objectives)?;
+            self.consider_elite(&candidate)?;
+            self.consider_improvement(&candidate)?;
+
+            let limit = self.limit_for(candidate.fitness, &self.current_working()?);
+            self.slot_history.push(Mutex::new(self.hive.new_history(candidate.fitness)));
+            self.working.push(RwLock::new(WorkingCandidate::new(candidate, limit)));
+            self.scout_counts.push(AtomicUsize::new(0));
+            self.scouting.push(AtomicBool::new(false));
+            self.visited.push(AtomicUsize::new(self.explorations.load(Ordering::Relaxed)));
+            if let Some(ref mut chaos) = self.chaos {
+                chaos.push(Mutex::new(ChaoticSequence::random(self.hive.chaos.unwrap_or_default(), &mut rng)));
+            }
+        }
+        *self.rng.get_mut().at(Stage::Rng)? = rng;
+        self.hive.workers = self.working.len();
+        Ok(())
+    }
+
+    /// Shrinks the colony by removing its `n` least fit working candidates,
+    /// and returns them.
+    ///
+    /// The remaining candidates keep their order, but move down to fill the
+    /// gaps, so indices (as in [`scout_counts`](#method.scout_counts)) change.
+    /// The best candidate found so far and the elite archive are unaffected.
+    /// Like [`add_workers`](#method.add_workers), this can only be called
+    /// between runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would leave no working candidates.
+    pub fn remove_worst(&mut self, n: usize) -> AbcResult<Vec<CandidateOf<Ctx>>> {
+        if n >= self.working.len() {
+            panic!("Hive must keep at least one working candidate.");
+        }
+        let current_working = self.current_working()?;
+        let mut order = (0..current_working.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| self.hive.objective.compare(&current_working[a], &current_working[b]));
+        let mut doomed = order[..n].to_vec();
+        doomed.sort();
+        // Let go of the snapshot, so the removed candidates aren't shared.
+        drop(current_working);
+
+        let mut removed = Vec::with_capacity(n);
+        for &index in doomed.iter().rev() {
+            let candidate = self.working.remove(index).into_inner().at_index(Stage::Candidate, index)?.candidate;
+            removed.push(Arc::try_unwrap(candidate).unwrap_or_else(|candidate| (*candidate).clone()));
+            self.scout_counts.remove(index);
+            self.scouting.remove(index);
+            self.visited.remove(index);
+            self.slot_history.remove(index);
+            if let Some(ref mut chaos) = self.chaos {
+                chaos.remove(index);
+            }
+        }
+        self.hive.workers = self.working.len();
+        Ok(removed)
+    }
+
+    /// Returns the current round of a running hive.
+    ///
+    /// If a worker thread has panicked and poisoned the task generator lock,
+    /// `get_round` will return `Err(abc::Error)`. If the hive has not been
+    /// run, `get_round` will return `Ok(None)`.
+    ///
+    /// If the hive is running, this will return `Ok(Some(n))`. `n` will start
+    /// at 0, and increment each time every task in the round has been claimed
+    /// (though not necessarily completed) by a worker thread.
+    pub fn get_round(&self) -> AbcResult<Option<usize>> {
+        current_round(&self.tasks)
+    }
+
+    /// Returns a handle that can stop the hive and watch its progress from
+    /// other threads.
+    ///
+    /// The handle can be cloned, and doesn't borrow the hive, so a hive
+    /// running on one thread can be controlled from any number of others
+    /// without scoping them. As with [`stop`](#method.stop), stopping only
+    /// affects a run that is under way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use std::thread;
+    /// use abc::HiveBuilder;
+    /// use abc::vector::BoundedVecContext;
+    ///
+    /// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+    /// let hive = HiveBuilder::new(context, 10).build().unwrap();
+    /// let controller = hive.controller();
+    /// let watcher = thread::spawn(move || {
+    ///     while controller.get_round().unwrap().unwrap_or(0) < 10 {
+    ///         thread::yield_now();
+    ///     }
+    ///     controller.stop().unwrap();
+    ///     controller.best().unwrap().fitness
+    /// });
+    /// hive.run_forever().unwrap();
+    /// assert!(hive.best_fitness() >= watcher.join().unwrap());
+    /// # }
+    /// ```
+    pub fn controller(&self) -> HiveController<Ctx::Solution, Ctx::Fitness> {
+        HiveController {
+            tasks: self.tasks.clone(),
+            queue: self.queue.clone(),
+            best: self.best.clone(),
+        }
+    }
+
+    /// Returns a guard for the hive's context.
+    ///
+    /// As with [`get`](#method.get), the guard should be dropped promptly,
+    /// since a [round-end hook](struct.HiveBuilder.html#method.on_round_end)
+    /// waits for it.
+    pub fn context(&self) -> AbcResult<RwLockReadGuard<'_, Ctx>> {
+        self.hive.context()
+    }
+}
+
+impl<Ctx: Context + 'static> Hive<Ctx> {
+    /// Runs indefinitely in the background, providing a stream of results.
+    ///
+    /// This method consumes the hive, which runs until it is stopped through
+    /// the returned [`StreamHandle`](struct.StreamHandle.html), or the
+    /// receiver is dropped and the next improvement finds it gone. The
+    /// `mpsc::Receiver` receives a `Candidate` each time the hive improves on
+    /// its best solution. The candidates are shared with the hive rather
+    /// than cloned. How unread candidates are buffered is set by
+    /// [`HiveBuilder::set_stream_buffer`](struct.HiveBuilder.html#method.set_stream_buffer).
+    pub fn stream(mut self) -> (StreamHandle<Ctx>, BestReceiver<Ctx::Solution, Ctx::Fitness>) {
+        let (sender, receiver) = stream::open(self.hive.stream_buffer);
+        self.set_stream_sender(sender);
+        (self.run_streaming(), receiver)
+    }
+
+    /// Runs indefinitely in the background, providing a stream of events.
+    ///
+    /// This is like [`stream`](#method.stream), except that the receiver also
+    /// gets a summary at the end of each round. See
+    /// [`HiveEvent`](enum.HiveEvent.html).
+    pub fn stream_events(mut self) -> (StreamHandle<Ctx>, Receiver<EventOf<Ctx>>) {
+        let (sender, receiver) = stream::open(self.hive.stream_buffer);
+        self.set_event_stream_sender(sender);
+        (self.run_streaming(), receiver)
+    }
+
+    /// Runs on a background thread for a stream.
+    fn run_streaming(self) -> StreamHandle<Ctx> {
+        // The tasks are installed up front, so that the handle can stop the
+        // run before the thread gets going. Any error is reported by `join`.
+        let installed = self.task_generator().and_then(|tasks| self.install(tasks));
+        let hive = Arc::new(self);
+        let runner = hive.clone();
+        StreamHandle {
+            hive,
+            thread: spawn(move || installed.and_then(|()| runner.run_installed(false))),
+        }
+    }
+
+    /// Runs indefinitely on a background thread, until the handle is stopped.
+    ///
+    /// Unlike [`stream`](#method.stream), this keeps hold of the thread, so
+    /// that the run can be stopped deterministically with
+    /// [`HiveHandle::stop_and_join`](struct.HiveHandle.html#method.stop_and_join).
+    pub fn spawn(self) -> AbcResult<HiveHandle<Ctx>> {
+        let tasks = self.task_generator()?;
+        self.install(tasks)?;
+
+        let hive = Arc::new(self);
+        let runner = hive.clone();
+        Ok(HiveHandle {
+            hive,
+            thread: Some(spawn(move || runner.run_installed(false))),
+        })
+    }
+}
+
+/// An iterator that runs a hive one round at a time.
+///
+/// Created by [`Hive::rounds`](struct.Hive.html#method.rounds).
+pub struct Rounds<'a, Ctx: Context + 'a> {
+    hive: &'a Hive<Ctx>,
+    done: bool,
+}
+
+impl<'a, Ctx: Context> Iterator for Rounds<'a, Ctx> {
+    type Item = AbcResult<RoundSummary<Ctx::Solution, Ctx::Fitness>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.hive.step();
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+/// Controls a hive running on a background thread.
+///
+/// Created by [`Hive::spawn`](struct.Hive.html#method.spawn). Dropping the
+/// handle stops the hive without waiting for its thread.
+pub struct HiveHandle<Ctx: Context + 'static> {
+    hive: Arc<Hive<Ctx>>,
+    thread: Option<JoinHandle<AbcResult<()>>>,
+}
+
+impl<Ctx: Context + 'static> HiveHandle<Ctx> {
+    /// Get a reference to the running hive, e.g. to check its best candidate.
+    pub fn hive(&self) -> &Hive<Ctx> {
+        &self.hive
+    }
+
+    /// Stops the hive, waits for its threads to finish, and returns the best
+    /// solution found.
+    ///
+    /// If one of the `Context` methods panicked during the run, this will
+    /// return `Err(abc::Error)` describing the failure.
+    pub fn stop_and_join(mut self) -> AbcResult<CandidateOf<Ctx>> {
+        self.hive.stop()?;
+        if let Some(thread) = self.thread.take() {
+            thread.join().map_err(|payload| thread_panic(Stage::Tasks, None, payload))??;
+        }
+        self.hive.get().map(|guard| (**guard).clone())
+    }
+}
+
+impl<Ctx: Context + 'static> Drop for HiveHandle<Ctx> {
+    fn drop(&mut self) {
+        self.hive.stop().unwrap_or(())
+    }
+}
+
+/// Controls a hive that is streaming its results from a background thread.
+///
+/// Created by [`Hive::stream`](struct.Hive.html#method.stream) and
+/// [`Hive::stream_events`](struct.Hive.html#method.stream_events). Unlike a
+/// [`HiveHandle`](struct.HiveHandle.html), dropping this handle leaves the
+/// hive running, until its receiver is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::HiveBuilder;
+/// use abc::vector::BoundedVecContext;
+///
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+/// let (handle, receiver) = HiveBuilder::new(context, 10).build().unwrap().stream();
+/// let first = receiver.recv().unwrap();
+/// handle.stop().unwrap();
+/// let best = handle.join().unwrap();
+/// assert!(best.fitness >= first.fitness);
+/// # }
+/// ```
+pub struct StreamHandle<Ctx: Context + 'static> {
+    hive: Arc<Hive<Ctx>>,
+    thread: JoinHandle<AbcResult<()>>,
+}
+
+impl<Ctx: Context + 'static> StreamHandle<Ctx> {
+    /// Stops the hive promptly, without waiting for its thread.
+    pub fn stop(&self) -> AbcResult<()> {
+        self.hive.stop()
+    }
+
+    /// Returns the round under way, counting from 0 over the hive's
+    /// lifetime.
+    pub fn round(&self) -> AbcResult<usize> {
+        self.hive.lifetime_round()
+    }
+
+    /// Waits for the hive's thread to finish, and returns the best solution
+    /// found.
+    ///
+    /// The thread only finishes once the hive has been stopped, or its
+    /// receiver dropped. If one of the `Context` methods panicked during the
+    /// run, this will return `Err(abc::Error)` describing the failure.
+    pub fn join(self) -> AbcResult<CandidateOf<Ctx>> {
+        self.thread.join().map_err(|payload| thread_panic(Stage::Tasks, None, payload))??;
+        self.hive.get().map(|guard| (**guard).clone())
+    }
+}
+
+/// A view of a hive's best candidate, which follows it as it improves.
+///
+/// Created by [`Hive::watch_best`](struct.Hive.html#method.watch_best).
+pub struct BestWatch<S: Clone + Send + Sync + 'static, F = f64> {
+    best: Arc<Mutex<Arc<Candidate<S, F>>>>,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> BestWatch<S, F> {
+    /// Returns the best candidate found so far.
+    pub fn latest(&self) -> AbcResult<Arc<Candidate<S, F>>> {
+        Ok(self.best.lock_at(Stage::Best)?.clone())
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Clone for BestWatch<S, F> {
+    fn clone(&self) -> BestWatch<S, F> {
+        BestWatch { best: self.best.clone() }
+    }
+}
+
+/// A handle that controls a hive from other threads.
+///
+/// Created by [`Hive::controller`](struct.Hive.html#method.controller).
+pub struct HiveController<S: Clone + Send + Sync + 'static, F = f64> {
+    tasks: Arc<Mutex<Option<TaskGenerator>>>,
+    queue: Arc<TaskQueue>,
+    best: Arc<Mutex<Arc<Candidate<S, F>>>>,
+}
+
+impl<S: Clone + Send + Sync + 'static, F> HiveController<S, F> {
+    /// Stops the hive's run, if it has one under way.
+    ///
+    /// If a worker thread has panicked, this returns `Err(abc::Error)`.
+    pub fn stop(&self) -> AbcResult<()> {
+        stop_tasks(&self.tasks, &self.queue)
+    }
+
+    /// Returns the current round of the hive's run, as
+    /// [`Hive::get_round`](struct.Hive.html#method.get_round) does.
+    pub fn get_round(&self) -> AbcResult<Option<usize>> {
+        current_round(&self.tasks)
+    }
+
+    /// Returns the best candidate found so far.
+    pub fn best(&self) -> AbcResult<Arc<Candidate<S, F>>> {
+        Ok(self.best.lock_at(Stage::Best)?.clone())
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static, F> Clone for HiveController<S, F> {
+    fn clone(&self) -> HiveController<S, F> {
+        HiveController {
+            tasks: self.tasks.clone(),
+            queue: self.queue.clone(),
+            best: self.best.clone(),
+        }
+    }
+}
+
+/// Returns the index of the candidate whose exploration or evaluation
+/// panicked or timed out, if that is why a task failed.
+fn isolable(err: &Error) -> Option<usize> {
+    match *err {
+        Error::Panicked { stage: Stage::Explore, index, .. } |
+        Error::Panicked { stage: Stage::EvaluateFitness, index, .. } |
+        Error::TimedOut { index, .. } => index,
+        _ => None,
+    }
+}
+
+/// Whether an evaluation failed in a way that might pass if it were tried
+/// again.
+fn transient(err: &Error) -> bool {
+    matches!(*err, Error::Panicked { stage: Stage::EvaluateFitness, .. } | Error::TimedOut { .. })
+}
+
+/// Stops the task generator, and drops any tasks queued from it.
+fn stop_tasks(tasks: &Mutex<Option<TaskGenerator>>, queue: &TaskQueue) -> AbcResult<()> {
+    let mut tasks_guard = tasks.lock_at(Stage::Tasks)?;
+    if let Some(tasks) = tasks_guard.as_mut() {
+        tasks.stop();
+    }
+    while steal_task(queue).is_some() {}
+    Ok(())
+}
+
+/// Returns the task generator's round, if there is a run.
+fn current_round(tasks: &Mutex<Option<TaskGenerator>>) -> AbcResult<Option<usize>> {
+    let tasks_guard = tasks.lock_at(Stage::Tasks)?;
+    Ok(tasks_guard.as_ref().map(|tasks| tasks.round()))
+}
+
+/// Takes a task from the queue, if there are any left.
+fn steal_task(queue: &TaskQueue) -> Option<(Task, Option<usize>)> {
+    loop {
+        match queue.steal() {
+            Steal::Success(claimed) => return Some(claimed),
+            Steal::Empty => return None,
+            Steal::Retry => {}
+        }
+    }
+}
+
+impl<Ctx: Context> Debug for Hive<Ctx>
+    where Ctx::Solution: Debug
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for mutex in &self.working {
+            let working = mutex.read().unwrap();
+            writeln!(f, "..{:?}..", working.candidate)?;
+        }
+        let best_candidate = self.get().unwrap();
+        write!(f, ">>{:?}<<", *best_candidate)
+    }
+}
+
+impl<Ctx: Context> Drop for Hive<Ctx> {
+    fn drop(&mut self) {
+        self.stop().unwrap_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use colony::{Phase, TaskSchedule};
+    use context::Evaluation;
+    use vector::BoundedVecContext;
+
+    // The context most tests share: integer solutions drawn from 0..100 and
+    // explored in steps of up to 10 either way, each its own fitness. Tests
+    // that need something else swap in their own hooks.
+    struct Ctx {
+        // Turns a draw from 0..100 into a fresh solution.
+        make: fn(i32) -> i32,
+        fitness: fn(&Ctx, i32) -> f64,
+        // Turns a step from -10..10 away from `field[n]` into a variant.
+        explore: fn(&Field<i32>, usize, i32) -> i32,
+        objectives: Option<fn(i32) -> Vec<f64>>,
+        score: Option<fn(i32) -> f64>,
+        partners: Option<usize>,
+        // Whether to give each thread a count of its evaluations.
+        scratch: bool,
+        // A goal for the fitness to chase, which tests can move.
+        target: i32,
+        calls: AtomicUsize,
+        starts: AtomicUsize,
+        stops: AtomicUsize,
+        scratches: AtomicUsize,
+    }
+
+    impl Default for Ctx {
+        fn default() -> Ctx {
+            Ctx {
+                make: |x| x,
+                fitness: |_, solution| solution as f64,
+                explore: |field, n, step| field[n].solution + step,
+                objectives: None,
+                score: None,
+                partners: None,
+                scratch: false,
+                target: 0,
+                calls: AtomicUsize::new(0),
+                starts: AtomicUsize::new(0),
+                stops: AtomicUsize::new(0),
+                scratches: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl Context for Ctx {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            self.make_with_rng(&mut thread_rng())
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            (self.fitness)(self, *solution)
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
+            self.explore_with_rng(field, n, &mut thread_rng())
+        }
+
+        fn make_with_rng<R: Rng>(&self, rng: &mut R) -> i32 {
+            (self.make)(rng.gen_range(0, 100))
+        }
+
+        fn explore_with_rng<R: Rng>(&self, field: &[Arc<Candidate<i32>>], n: usize, rng: &mut R) -> i32 {
+            (self.explore)(field, n, rng.gen_range(-10, 10))
+        }
+
+        fn evaluate(&self, solution: &i32) -> Evaluation {
+            let fitness = self.evaluate_fitness(solution);
+            match self.score {
+                Some(score) => Evaluation::with_score(fitness, score(*solution)),
+                None => Evaluation::new(fitness),
+            }
+        }
+
+        fn evaluate_objectives(&self, solution: &i32) -> Option<Vec<f64>> {
+            self.objectives.map(|objectives| objectives(*solution))
+        }
+
+        fn partners(&self) -> Option<usize> {
+            self.partners
+        }
+
+        fn on_start(&self) {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_stop(&self) {
+            self.stops.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn init_scratch(&self) -> Scratch {
+            if !self.scratch {
+                return Scratch::empty();
+            }
+            self.scratches.fetch_add(1, Ordering::SeqCst);
+            Scratch::new(0usize)
+        }
+
+        fn evaluate_with_scratch(&self, solution: &i32, scratch: &mut Scratch) -> Evaluation {
+            if self.scratch {
+                *scratch.get_mut::<usize>().unwrap() += 1;
+            }
+            self.evaluate(solution)
+        }
+    }
+
+    // Chases a target that tests move between rounds.
+    fn moving() -> Ctx {
+        Ctx { fitness: |ctx, solution| 1000f64 - (solution - ctx.target).abs() as f64, ..Ctx::default() }
+    }
+
+    fn seeded(seed: u64) -> Hive<Ctx> {
+        HiveBuilder::new(Ctx::default(), 5).set_threads(1).set_rng_seed(seed).build().unwrap()
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let (first, second) = (seeded(7), seeded(7));
+        let first_best = first.run_for_rounds(20).unwrap();
+        let second_best = second.run_for_rounds(20).unwrap();
+        assert_eq!(first_best.solution, second_best.solution);
+        assert_eq!(first.current_working().unwrap().iter().map(|c| c.solution).collect::<Vec<_>>(),
+                   second.current_working().unwrap().iter().map(|c| c.solution).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn streams_share_the_best_candidate() {
+        let mut hive = seeded(7);
+        let (sender, receiver) = channel();
+        hive.set_sender(sender);
+        hive.run_for_rounds(20).unwrap();
+        let last = receiver.try_iter().last().unwrap();
+        assert!(Arc::ptr_eq(&last, &hive.best_arc().unwrap()));
+    }
+
+    #[test]
+    fn snapshots_share_candidates() {
+        let hive = seeded(7);
+        let (first, second) = (hive.current_working().unwrap(), hive.current_working().unwrap());
+        assert!(first.iter().zip(&second).all(|(a, b)| Arc::ptr_eq(a, b)));
+    }
+
+    #[test]
+    fn pareto_front_is_non_dominated() {
+        use pareto::dominates;
+        let two_goals = Ctx {
+            fitness: |_, _| 1f64,
+            // Conflicting objectives, with a dominated region below 50.
+            objectives: Some(|solution| {
+                let x = solution as f64;
+                vec![x, if x < 50f64 { 0f64 } else { 100f64 - x }]
+            }),
+            ..Ctx::default()
+        };
+        let hive = HiveBuilder::new(two_goals, 5).set_pareto_capacity(8).build().unwrap();
+        hive.run_for_rounds(20).unwrap();
+        let front = hive.pareto_front().unwrap();
+        assert!(!front.is_empty() && front.len() <= 8);
+        for a in &front {
+            assert!(front.iter().all(|b| !dominates(&b.objectives, &a.objectives)));
+        }
+    }
+
+    struct Guided;
+
+    impl Context for Guided {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            thread_rng().gen_range(0, 100)
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            *solution as f64
+        }
+
+        fn explore(&self, _: &[Arc<Candidate<i32>>], _: usize) -> i32 {
+            unreachable!()
+        }
+
+        fn explore_guided<R: Rng>(&self, _: &[Arc<Candidate<i32>>], _: usize, best: &Candidate<i32>, _: &mut R) -> i32 {
+            best.solution + 1
+        }
+    }
+
+    #[test]
+    fn explore_sees_global_best() {
+        let hive = HiveBuilder::new(Guided, 4).set_threads(1).build().unwrap();
+        let start = hive.get().unwrap().solution;
+        // Every variant improves on the global best, so the best rises by one
+        // per task: four workers and four observers per round.
+        assert_eq!(hive.run_for_rounds(3).unwrap().solution, start + 24);
+    }
+
+    struct Lexicographic;
+
+    impl Context for Lexicographic {
+        type Solution = i32;
+        type Fitness = (i32, i32);
+
+        fn make(&self) -> i32 {
+            0
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> (i32, i32) {
+            (solution / 10, solution % 10)
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32, (i32, i32)>>], n: usize) -> i32 {
+            cmp::min(field[n].solution + 1, 99)
+        }
+    }
+
+    #[test]
+    fn ties_are_broken_by_raw_fitness() {
+        // Within each ten, the first element (and so the `f64` fitness) is
+        // tied, and only the second element tells the variants apart.
+        let hive = HiveBuilder::new(Lexicographic, 4).set_threads(1).set_retries(1000).build().unwrap();
+        let best = hive.run_for_rounds(50).unwrap();
+        assert_eq!((best.solution, best.raw_fitness, best.fitness), (99, (9, 9), 9.0));
+        let elites = hive.top_k().unwrap().iter().take(2).map(|c| c.solution).collect::<Vec<_>>();
+        assert_eq!(elites, vec![99, 98]);
+    }
+
+    #[derive(Clone, Debug)]
+    struct Tagged {
+        cost: u32,
+        tag: String,
+    }
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Tagged) -> bool {
+            self.cost == other.cost
+        }
+    }
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Tagged) -> Option<cmp::Ordering> {
+            self.cost.partial_cmp(&other.cost)
+        }
+    }
+
+    impl Fitness for Tagged {
+        fn to_f64(&self) -> f64 {
+            self.cost as f64
+        }
+    }
+
+    struct Distance;
+
+    impl Context for Distance {
+        type Solution = i32;
+        type Fitness = Tagged;
+
+        fn make(&self) -> i32 {
+            thread_rng().gen_range(-100, 100)
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> Tagged {
+            Tagged {
+                cost: solution.unsigned_abs(),
+                tag: format!("x = {}", solution),
+            }
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32, Tagged>>], n: usize) -> i32 {
+            field[n].solution - field[n].solution.signum()
+        }
+    }
+
+    #[test]
+    fn fitness_carries_metadata() {
+        let hive = HiveBuilder::new(Distance, 4).set_threads(2).set_objective(Objective::Minimize).build().unwrap();
+        let best = hive.run_for_rounds(30).unwrap();
+        assert_eq!(best.solution, 0);
+        assert_eq!(best.raw_fitness.tag, "x = 0");
+        assert_eq!(best.fitness, 1.0);
+    }
+
+    #[test]
+    fn best_fitness_follows_best() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(3).build().unwrap();
+        assert_eq!(hive.best_fitness(), hive.get().unwrap().fitness);
+        let best = hive.run_for_rounds(20).unwrap();
+        assert_eq!(hive.best_fitness(), best.fitness);
+    }
+
+    #[test]
+    fn synchronous_rounds() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(3).set_retries(1000).set_synchronous(true).build().unwrap();
+        hive.run_for_rounds(10).unwrap();
+        assert_eq!(hive.snapshot().unwrap().round, 10);
+        // Each round finishes before the next starts, so every round has
+        // all ten of its evaluations.
+        assert!(hive.stats().unwrap()[1..].iter().all(|stats| stats.evaluations == 10));
+    }
+
+    #[test]
+    fn cycles_weight_the_phases() {
+        let cycle = || CycleSpec::new(vec![Phase::Workers, Phase::Observers, Phase::Observers, Phase::Scouts]);
+        let hive = HiveBuilder::new(Ctx::default(), 5)
+            .set_threads(3)
+            .set_retries(1000)
+            .set_synchronous(true)
+            .set_cycle(cycle())
+            .build()
+            .unwrap();
+        hive.run_for_rounds(10).unwrap();
+        assert!(hive.stats().unwrap()[1..].iter().all(|stats| stats.evaluations == 15));
+
+        let determined = |threads| {
+            let hive = HiveBuilder::new(Ctx::default(), 5)
+                .set_threads(threads)
+                .set_retries(2)
+                .set_rng_seed(5)
+                .set_deterministic(true)
+                .set_cycle(cycle())
+                .build()
+                .unwrap();
+            let best = hive.run_for_rounds(10).unwrap();
+            assert!(hive.scout_count() > 0);
+            (best.solution, hive.scout_counts())
+        };
+        assert_eq!(determined(1), determined(3));
+    }
+
+    // Sends out the workers alone, without any observers.
+    struct WorkersOnly {
+        workers: usize,
+        handed_out: usize,
+    }
+
+    impl TaskSchedule for WorkersOnly {
+        fn next_task(&mut self) -> Task {
+            self.handed_out += 1;
+            Task::Worker((self.handed_out - 1) % self.workers)
+        }
+
+        fn round(&self) -> usize {
+            self.handed_out / self.workers
+        }
+
+        fn tasks_per_round(&self) -> usize {
+            self.workers
+        }
+    }
+
+    #[test]
+    fn custom_schedules_drive_the_run() {
+        let hive = HiveBuilder::new(Ctx::default(), 4)
+            .set_threads(2)
+            .set_retries(1000)
+            .set_synchronous(true)
+            .set_task_schedule(|workers, _| WorkersOnly { workers, handed_out: 0 })
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        assert!(hive.stats().unwrap()[1..].iter().all(|stats| stats.explorations == 4));
+        assert!(hive.timings().unwrap().iter().all(|timing| timing.tasks == 4));
+        // Each run starts its schedule afresh.
+        hive.run_for_rounds(5).unwrap();
+        assert_eq!(hive.snapshot().unwrap().round, 10);
+        // Random search counts its rounds by the same schedule.
+        let (_, improvements) = hive.random_search(40).unwrap();
+        assert!(improvements.iter().all(|improvement| improvement.round == improvement.evaluations / 4));
+    }
+
+    #[test]
+    fn deterministic_runs_match_across_threads() {
+        let run = |threads: usize| {
+            let hive = HiveBuilder::new(Ctx::default(), 6)
+                .set_threads(threads)
+                .set_retries(3)
+                .set_rng_seed(11)
+                .set_deterministic(true)
+                .build()
+                .unwrap();
+            let best = hive.run_for_rounds(15).unwrap();
+            let working = hive.current_working().unwrap().iter().map(|c| c.solution).collect::<Vec<_>>();
+            (best.solution, working, hive.scout_count())
+        };
+        let single = run(1);
+        assert_eq!(run(4), single);
+        assert_eq!(run(4), single);
+        assert!(HiveBuilder::new(Ctx::default(), 6).set_deterministic(true).build().is_err());
+    }
+
+    #[test]
+    fn round_end_updates_context() {
+        let rounds = Arc::new(Mutex::new(Vec::new()));
+        let seen = rounds.clone();
+        let hive = HiveBuilder::new(moving(), 4)
+            .set_threads(3)
+            .on_round_end(move |context: &mut Ctx, stats: &RoundStats| {
+                context.target += 10;
+                seen.lock().unwrap().push(stats.round);
+            })
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        assert_eq!(hive.context().unwrap().target, 50);
+        assert_eq!(*rounds.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn reevaluation_tracks_context() {
+        let hive = HiveBuilder::new(moving(), 4)
+            .set_threads(2)
+            .on_round_end(|context: &mut Ctx, _: &RoundStats| context.target += 100)
+            .set_reevaluation(1)
+            .build()
+            .unwrap();
+        hive.run_for_rounds(3).unwrap();
+        let best = hive.get().unwrap().clone();
+        assert_eq!(best.fitness, 1000f64 - (best.solution - 300).abs() as f64);
+        for w in hive.population().unwrap() {
+            assert_eq!(w.fitness, 1000f64 - (w.solution - 300).abs() as f64);
+        }
+    }
+
+    #[test]
+    fn queued_executor() {
+        use executor::Executor;
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(3).set_executor(Executor::Queued).build().unwrap();
+        let start = hive.get().unwrap().fitness;
+        assert!(hive.run_for_rounds(10).unwrap().fitness >= start);
+        assert_eq!(hive.snapshot().unwrap().round, 10);
+    }
+
+    #[test]
+    fn current_thread_executor() {
+        use executor::Executor;
+        let caller = thread::current().id();
+        let context = BoundedVecContext::uniform(1, 0.0, 1.0, move |x: &[f64]| {
+            assert_eq!(thread::current().id(), caller);
+            1.0 + x[0]
+        });
+        let hive = HiveBuilder::new(context, 5)
+            .set_threads(3)
+            .set_variants_per_visit(2)
+            .set_executor(Executor::CurrentThread)
+            .build()
+            .unwrap();
+        let start = hive.get().unwrap().fitness;
+        assert!(hive.run_for_rounds(10).unwrap().fitness >= start);
+        assert_eq!(hive.snapshot().unwrap().round, 10);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn rayon_executor() {
+        use executor::Executor;
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(3).set_executor(Executor::Rayon).build().unwrap();
+        let start = hive.get().unwrap().fitness;
+        assert!(hive.run_for_rounds(10).unwrap().fitness >= start);
+        assert_eq!(hive.snapshot().unwrap().round, 10);
+    }
+
+    struct Fragile;
+
+    impl Context for Fragile {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            0
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            if *solution > 0 {
+                panic!("too big");
+            }
+            0f64
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
+            field[n].solution + 1
+        }
+    }
+
+    #[test]
+    fn run_until_fitness_stops_at_target() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).build().unwrap();
+        let best = hive.run_until_fitness(150f64).unwrap();
+        assert!(best.fitness >= 150f64);
+        assert_eq!(hive.get_round().unwrap(), None);
+    }
+
+    #[test]
+    fn evaluation_budget_stops_run() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(1).set_evaluation_budget(100).build().unwrap();
+        assert_eq!(hive.evaluations(), 5);
+        hive.run_forever().unwrap();
+        assert!((105..=106).contains(&hive.evaluations()));
+    }
+
+    #[test]
+    fn step_runs_whole_rounds() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).set_evaluation_budget(1).build().unwrap();
+        let summary = hive.step().unwrap();
+        assert_eq!(summary.stats.round, 1);
+        assert_eq!(summary.stats.evaluations, 10);
+        assert_eq!(summary.best.fitness, hive.get().unwrap().fitness);
+
+        // The iterator carries on from there, ignoring the budget.
+        let rounds = hive.rounds().take(3).map(|s| s.unwrap().stats.round).collect::<Vec<_>>();
+        assert_eq!(rounds, vec![2, 3, 4]);
+        assert_eq!(hive.stats().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn resampling_averages_noise() {
+        let noisy = Ctx {
+            fitness: |_, solution| 100f64 + solution as f64 + thread_rng().gen_range(-5f64, 5f64),
+            ..Ctx::default()
+        };
+        let hive = HiveBuilder::new(noisy, 4)
+            .set_threads(1)
+            .set_retries(100)
+            .set_resampling(8, 2.0)
+            .build()
+            .unwrap();
+        assert_eq!(hive.evaluations(), 32);
+        hive.run_for_rounds(3).unwrap();
+        assert_eq!(hive.evaluations(), 32 + 3 * 8 * 8);
+        let best = hive.get().unwrap().clone();
+        let noise = best.noise.unwrap();
+        assert_eq!(noise.samples, 8);
+        assert!(noise.variance > 0.0);
+        // Selection is on the mean, less two standard errors.
+        assert!(best.fitness < best.raw_fitness);
+        assert!((best.raw_fitness - best.fitness - 2.0 * (noise.variance / 8.0).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn progress_is_reported() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let seen = reports.clone();
+        let hive = HiveBuilder::new(Ctx::default(), 5)
+            .set_threads(1)
+            .set_progress(move |progress: &Progress| seen.lock().unwrap().push(progress.clone()))
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        hive.run_for_rounds(2).unwrap();
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.iter().map(|p| (p.round, p.rounds)).collect::<Vec<_>>(),
+                   vec![(1, Some(5)), (2, Some(5)), (3, Some(5)), (4, Some(5)), (5, Some(5)), (1, Some(2)), (2, Some(2))]);
+        assert_eq!(reports[4].remaining, Some(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn resume_from_snapshot() {
+        let hive = seeded(3);
+        hive.run_for_rounds(4).unwrap();
+        let snapshot = hive.snapshot().unwrap();
+        assert_eq!(snapshot.round, 4);
+        assert_eq!(snapshot.working.len(), 5);
+
+        let resumed = Hive::from_snapshot(HiveBuilder::new(Ctx::default(), 2), snapshot.clone()).unwrap();
+        let resnapshot = resumed.snapshot().unwrap();
+        assert_eq!(resnapshot.working.iter().map(|w| w.solution).collect::<Vec<_>>(),
+                   snapshot.working.iter().map(|w| w.solution).collect::<Vec<_>>());
+        assert_eq!(resnapshot.best_solution, snapshot.best_solution);
+        assert_eq!(resnapshot.evaluations, snapshot.evaluations);
+
+        resumed.run_for_rounds(2).unwrap();
+        assert_eq!(resumed.snapshot().unwrap().round, 6);
+
+        let empty = HiveSnapshot { working: Vec::new(), ..snapshot };
+        assert!(Hive::from_snapshot(HiveBuilder::new(Ctx::default(), 2), empty).is_err());
+    }
+
+    #[test]
+    fn stats_are_recorded() {
+        let mut hive = seeded(5);
+        let (sender, receiver) = channel();
+        hive.set_stats_sender(sender);
+        hive.run_for_rounds(3).unwrap();
+        hive.run_for_rounds(2).unwrap();
+
+        let stats = hive.stats().unwrap();
+        assert_eq!(stats.iter().map(|s| s.round).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(stats[0].evaluations, 5);
+        assert!(stats.iter().all(|s| s.worst <= s.mean && s.mean <= s.best));
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), stats[1..].to_vec());
+    }
+
+    struct Tally {
+        counts: Arc<Mutex<(usize, usize, usize)>>,
+    }
+
+    impl HiveObserver<i32> for Tally {
+        fn on_round_complete(&mut self, stats: &RoundStats) -> bool {
+            self.counts.lock().unwrap().0 += 1;
+            stats.round >= 4
+        }
+
+        fn on_new_best(&mut self, _: &Candidate<i32>) {
+            self.counts.lock().unwrap().1 += 1;
+        }
+
+        fn on_candidate_improved(&mut self, _: usize, _: &Candidate<i32>) {
+            self.counts.lock().unwrap().2 += 1;
+        }
+    }
+
+    #[test]
+    fn observers_are_notified() {
+        let counts = Arc::new(Mutex::new((0, 0, 0)));
+        let (sender, receiver) = channel();
+        let hive = HiveBuilder::new(Ctx::default(), 5)
+                       .set_threads(1)
+                       .add_observer(Box::new(Tally { counts: counts.clone() }))
+                       .add_observer(Box::new(sender))
+                       .build()
+                       .unwrap();
+
+        // The observer stops the run after round 4, long before round 100.
+        hive.run_for_rounds(100).unwrap();
+        assert_eq!(hive.stats().unwrap().len(), 5);
+
+        let (rounds, new_bests, improved) = *counts.lock().unwrap();
+        assert_eq!(rounds, 5);
+        assert_eq!(receiver.try_iter().count(), new_bests);
+        assert!(improved >= new_bests);
+    }
+
+    #[test]
+    fn spawned_hive_stops_and_joins() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).build().unwrap();
+        let start = hive.get().unwrap().fitness;
+        let handle = hive.spawn().unwrap();
+        while handle.hive().stats().unwrap().len() < 3 {
+            ::std::thread::yield_now();
+        }
+        let best = handle.stop_and_join().unwrap();
+        assert!(best.fitness >= start);
+    }
+
+    #[test]
+    fn spawned_hive_stops_immediately() {
+        // Stopping before the thread starts must not leave it running.
+        let handle = seeded(1).spawn().unwrap();
+        handle.stop_and_join().unwrap();
+    }
+
+    #[test]
+    fn events_include_rounds() {
+        let (_, events) = seeded(2).stream_events();
+        let mut best_fitness = match events.recv().unwrap() {
+            HiveEvent::NewBest(best) => best.fitness,
+            other => panic!("expected the initial best, got {:?}", other),
+        };
+        let mut round = 0;
+        for event in events.iter() {
+            match event {
+                HiveEvent::NewBest(best) => {
+                    assert!(best.fitness > best_fitness);
+                    best_fitness = best.fitness;
+                }
+                HiveEvent::Round { stats, best } => {
+                    assert_eq!(stats.round, round + 1);
+                    assert_eq!(best.fitness, stats.best);
+                    round = stats.round;
+                    if round == 3 {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_stream() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_stream_buffer(StreamBuffer::Bounded(2)).build().unwrap();
+        let mut fitness = f64::NEG_INFINITY;
+        for best in hive.stream().1.iter().take(4) {
+            assert!(best.fitness > fitness);
+            fitness = best.fitness;
+        }
+    }
+
+    #[test]
+    fn stream_handle_stops_the_run() {
+        let (handle, receiver) = HiveBuilder::new(Ctx::default(), 5).build().unwrap().stream();
+        let first = receiver.recv().unwrap();
+        let round = handle.round().unwrap();
+        handle.stop().unwrap();
+        assert!(handle.round().unwrap() >= round);
+        let best = handle.join().unwrap();
+        assert!(best.fitness >= first.fitness);
+        assert!(receiver.iter().all(|candidate| candidate.fitness <= best.fitness));
+    }
+
+    #[test]
+    fn fitter_candidates_get_more_patience() {
+        let hive = HiveBuilder::new(Ctx::default(), 5)
+                       .set_limit_strategy(LimitStrategy::Fitness { min: 2, max: 10 })
+                       .build()
+                       .unwrap();
+        let mut working = hive.snapshot().unwrap().working;
+        working.sort_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap());
+        assert_eq!(working[0].retries, 2);
+        assert_eq!(working[4].retries, 10);
+        assert!(working.windows(2).all(|pair| pair[0].retries <= pair[1].retries));
+    }
+
+    #[test]
+    fn limit_tuned_by_success_rate() {
+        let builder = HiveBuilder::new(Ctx::default(), 5).set_limit_strategy(LimitStrategy::SuccessRate {
+            factor: 2.0,
+            min: 3,
+            max: 50,
+        });
+        assert_eq!(builder.limit(0.0, 0.0, 1.0, None, 0), 50);
+        assert_eq!(builder.limit(0.0, 0.0, 1.0, Some(0.25), 0), 8);
+        assert_eq!(builder.limit(0.0, 0.0, 1.0, Some(1.0), 0), 3);
+    }
+
+    #[test]
+    fn scouts_are_counted() {
+        // With one retry, every unsuccessful exploration leads to a scout.
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(1).set_retries(1).set_rng_seed(9).build().unwrap();
+        hive.run_for_rounds(5).unwrap();
+        let history = hive.scout_history().unwrap();
+        assert!(!history.is_empty());
+        assert_eq!(hive.scout_count(), history.len());
+        assert_eq!(hive.scout_counts().iter().sum::<usize>(), history.len());
+        assert!(history.iter().all(|event| event.index < 5 && event.round <= 5));
+        assert!(hive.stats().unwrap().iter().map(|s| s.scouts).sum::<usize>() <= history.len());
+    }
+
+    #[test]
+    fn elites_are_fittest_ever_seen() {
+        let hive = HiveBuilder::new(Ctx::default(), 3).set_threads(1).set_retries(1).set_elite_capacity(4).build().unwrap();
+        let best = hive.run_for_rounds(10).unwrap();
+        let elites = hive.top_k().unwrap();
+        assert_eq!(elites.len(), 4);
+        assert_eq!(elites[0].fitness, best.fitness);
+        assert!(elites.windows(2).all(|pair| pair[0].fitness > pair[1].fitness));
+
+        // The archive survives a snapshot.
+        let builder = HiveBuilder::new(Ctx::default(), 3).set_elite_capacity(4);
+        let resumed = Hive::from_snapshot(builder, hive.snapshot().unwrap()).unwrap();
+        let fitnesses = |elites: Vec<Candidate<i32>>| elites.iter().map(|c| c.fitness).collect::<Vec<_>>();
+        assert_eq!(fitnesses(resumed.top_k().unwrap()), fitnesses(elites));
+    }
+
+    #[test]
+    fn provenance_is_tracked() {
+        let hive = HiveBuilder::new(Ctx::default(), 4).set_threads(1).set_retries(2).build().unwrap();
+        assert!(hive.current_working().unwrap().iter().enumerate().all(|(index, candidate)| {
+            candidate.provenance == Some(Provenance { round: 0, index, origin: Origin::Initial, improvements: 0 })
+        }));
+        hive.run_for_rounds(10).unwrap();
+        for (index, candidate) in hive.current_working().unwrap().iter().enumerate() {
+            let provenance = candidate.provenance.unwrap();
+            assert_eq!(provenance.index, index);
+            assert!(provenance.round <= 10);
+            match provenance.origin {
+                Origin::Explored => assert!(provenance.improvements > 0),
+                _ => assert_eq!(provenance.improvements, 0),
+            }
+        }
+
+        let resumed = Hive::from_snapshot(HiveBuilder::new(Ctx::default(), 4), hive.snapshot().unwrap()).unwrap();
+        assert_eq!(resumed.current_working().unwrap().iter().map(|c| c.provenance).collect::<Vec<_>>(),
+                   hive.current_working().unwrap().iter().map(|c| c.provenance).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn population_has_retries() {
+        let hive = HiveBuilder::new(Ctx::default(), 4).set_threads(1).set_retries(3).build().unwrap();
+        let population = hive.population().unwrap();
+        assert_eq!(population.len(), 4);
+        assert!(population.iter().all(|w| w.retries == 3 && w.fitness == w.solution as f64));
+
+        hive.run_for_rounds(5).unwrap();
+        let population = hive.population().unwrap();
+        let working = hive.current_working().unwrap();
+        assert!(population.iter().zip(working.iter()).all(|(w, c)| w.solution == c.solution && w.retries <= 3));
+    }
+
+    #[test]
+    fn initial_solutions_are_kept() {
+        let hive = HiveBuilder::new(Ctx::default(), 4).set_initial_solutions(vec![1000, -1000]).build().unwrap();
+        let solutions = hive.current_working().unwrap().iter().map(|c| c.solution).collect::<Vec<_>>();
+        assert_eq!(&solutions[..2], &[1000, -1000]);
+        assert!(solutions[2..].iter().all(|&s| (0..100).contains(&s)));
+        assert_eq!(hive.get().unwrap().fitness, 1000.0);
+    }
+
+    #[test]
+    fn quick_abc_observes_best_neighbor() {
+        let quick = |radius| {
+            let context = BoundedVecContext::uniform(1, 0.0, 10.0, |x: &[f64]| 1.0 + x[0]);
+            HiveBuilder::new(context, 3)
+                .set_threads(1)
+                .set_initial_solutions(vec![vec![0.0], vec![1.0], vec![10.0]])
+                .set_quick_abc(radius)
+                .build()
+                .unwrap()
+        };
+        let hive = quick(1.0);
+        let field = hive.current_working().unwrap();
+        // Candidate 1 is within the mean distance from 0, but 2 isn't.
+        assert_eq!(hive.best_neighbor(&field, 0).unwrap(), 1);
+        assert_eq!(hive.best_neighbor(&field, 1).unwrap(), 1);
+        assert_eq!(hive.best_neighbor(&field, 2).unwrap(), 2);
+        assert_eq!(quick(0.0).best_neighbor(&field, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn evaluates_several_variants_per_visit() {
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |x: &[f64]| 3.0 - x[0] - x[1]);
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(2)
+            .set_retries(1000)
+            .set_synchronous(true)
+            .set_variants_per_visit(3)
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        // Each of the 8 visits in a round evaluates 3 variants, but counts as
+        // a single exploration.
+        assert_eq!(hive.snapshot().unwrap().evaluations, 4 + 5 * 8 * 3);
+        assert!(hive.stats().unwrap()[1..].iter().all(|stats| stats.explorations == 8));
+    }
+
+    #[test]
+    fn evaluator_scores_batches() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = batches.clone();
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |_: &[f64]| panic!("the context evaluated"));
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(2)
+            .set_variants_per_visit(3)
+            .set_evaluator(move |batch: &[Vec<f64>]| {
+                recorded.lock().unwrap().push(batch.len());
+                batch.iter().map(|x| 3.0 - x[0] - x[1]).collect()
+            })
+            .build()
+            .unwrap();
+        hive.run_for_rounds(3).unwrap();
+        let batches = batches.lock().unwrap();
+        assert_eq!(batches[0], 4);
+        assert!(batches[1..].iter().all(|&size| size == 3 || size == 1));
+
+        // An evaluator must score every solution it is given.
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |_: &[f64]| 1.0);
+        let result = HiveBuilder::new(context, 4).set_evaluator(|_: &[Vec<f64>]| vec![1.0]).build();
+        match result {
+            Err(Error::Panicked { stage: Stage::EvaluateFitness, .. }) => {}
+            _ => panic!("expected the evaluator to fail"),
+        }
+    }
+
+    #[test]
+    fn controller_tunes_parameters() {
+        let hive = HiveBuilder::new(Ctx::default(), 5)
+            .set_threads(1)
+            .set_synchronous(true)
+            .set_parameter_controller(|_: &RoundStats, parameters: &mut Parameters| {
+                parameters.observers = 0;
+                parameters.retries = 7;
+                parameters.pressure = f64::NAN;
+            })
+            .build()
+            .unwrap();
+        hive.run_for_rounds(3).unwrap();
+        let explorations = hive.stats().unwrap().iter().map(|s| s.explorations).collect::<Vec<_>>();
+        assert_eq!(explorations, vec![0, 10, 5, 5]);
+        assert_eq!(hive.parameters(),
+                   Parameters {
+                       workers: 5,
+                       observers: 0,
+                       retries: 7,
+                       pressure: 1.0,
+                   });
+    }
+
+    #[test]
+    fn invalid_settings_are_errors() {
+        let check = |builder: HiveBuilder<Ctx>| {
+            match builder.build() {
+                Err(Error::InvalidConfig { description }) => description,
+                Err(other) => panic!("expected an invalid configuration, got {}", other),
+                Ok(_) => panic!("expected an invalid configuration"),
+            }
+        };
+        assert!(check(HiveBuilder::new(Ctx::default(), 0)).contains("worker"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_threads(0)).contains("thread"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_observers(0).set_retries(0)).contains("retry"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_duplicate_distance(-1.0)).contains("distance"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_variants_per_visit(0)).contains("variant"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 1).set_initial_solutions(vec![1, 2])).contains("initial"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_opposition(1.5)).contains("jumping"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_selection(Selection::Tournament(0))).contains("entrant"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_resampling(0, 1.0)).contains("once"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_resampling(2, -1.0)).contains("confidence"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_quick_abc(-1.0)).contains("radius"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_elite_capacity(0)).contains("elite"));
+        assert!(check(HiveBuilder::new(Ctx::default(), 2).set_reevaluation(0)).contains("rounds"));
+
+        // No retries are allowed, as long as there are observers.
+        assert!(HiveBuilder::new(Ctx::default(), 2).set_threads(1).set_retries(0).build().is_ok());
+    }
+
+    #[test]
+    fn too_many_initial_solutions() {
+        assert!(HiveBuilder::new(Ctx::default(), 1).set_initial_solutions(vec![1, 2]).build().is_err());
+        assert!(HiveBuilder::new(Ctx::default(), 2).set_initial_solutions(vec![1, 2]).build().is_ok());
+    }
+
+    #[test]
+    fn colony_grows_and_shrinks() {
+        let mut hive = HiveBuilder::new(Ctx::default(), 4).set_threads(2).build().unwrap();
+        hive.run_for_rounds(3).unwrap();
+        hive.add_workers(3).unwrap();
+        assert_eq!(hive.population().unwrap().len(), 7);
+        assert_eq!(hive.scout_counts().len(), 7);
+        hive.run_for_rounds(3).unwrap();
+
+        let removed = hive.remove_worst(5).unwrap();
+        assert_eq!(removed.len(), 5);
+        let kept = hive.current_working().unwrap();
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|k| removed.iter().all(|r| r.fitness <= k.fitness)));
+        hive.run_for_rounds(3).unwrap();
+        assert_eq!(hive.snapshot().unwrap().working.len(), 2);
+    }
+
+    #[test]
+    fn threads_change_between_rounds() {
+        let hive = HiveBuilder::new(Ctx::default(), 4).set_threads(1).build().unwrap();
+        let done = AtomicBool::new(false);
+        let changed = crossbeam::scope(|scope| {
+            let (hive, done) = (&hive, &done);
+            let handle = scope.spawn(move || {
+                // Wait for the run to get going, then add threads.
+                while !done.load(Ordering::Relaxed) {
+                    if hive.get_round().unwrap().unwrap_or(0) >= 2 {
+                        hive.set_threads(3);
+                        return true;
+                    }
+                    thread::yield_now();
+                }
+                false
+            });
+            hive.run_for_rounds(5000).unwrap();
+            done.store(true, Ordering::Relaxed);
+            handle.join()
+        });
+        assert_eq!(hive.stats().unwrap().len(), 5001);
+        if changed {
+            assert_eq!(hive.threads(), 3);
+            assert_eq!(hive.running.load(Ordering::Relaxed), 3);
+        }
+    }
+
+    #[test]
+    fn tournament_selection() {
+        // Negative fitnesses would upset the roulette wheel, but not a tournament.
+        let negative = Ctx {
+            make: |x| x - 100,
+            explore: |field, n, step| (field[n].solution + step).min(0),
+            ..Ctx::default()
+        };
+        let hive = HiveBuilder::new(negative, 5)
+            .set_threads(2)
+            .set_selection(Selection::Tournament(3))
+            .build()
+            .unwrap();
+        let best = hive.run_for_rounds(20).unwrap();
+        assert!(best.fitness <= 0.0);
+        assert!(best.fitness > -100.0);
+    }
+
+    #[test]
+    fn workers_favor_neglected_candidates() {
+        let hive = HiveBuilder::new(Ctx::default(), 4)
+            .set_threads(1)
+            .set_retries(1000)
+            .set_worker_priority(WorkerPriority::Stale)
+            .build()
+            .unwrap();
+        hive.explorations.store(100, Ordering::Relaxed);
+        for visited in &hive.visited[..3] {
+            visited.store(100, Ordering::Relaxed);
+        }
+        let (mut rng, mut buffers) = (hive.hive.task_rng(0, 0), Buffers::new());
+        let chosen = (0..1000).filter(|_| hive.prioritize(0, &mut rng, &mut buffers).unwrap() == 3).count();
+        assert!(chosen > 800);
+
+        let determined = |threads| {
+            let hive = HiveBuilder::new(Ctx::default(), 5)
+                .set_threads(threads)
+                .set_retries(3)
+                .set_rng_seed(2)
+                .set_deterministic(true)
+                .set_worker_priority(WorkerPriority::Stale)
+                .build()
+                .unwrap();
+            (hive.run_for_rounds(10).unwrap().solution, hive.scout_counts())
+        };
+        assert_eq!(determined(1), determined(3));
+    }
+
+    #[test]
+    fn slot_histories_follow_their_candidates() {
+        let hive = HiveBuilder::new(Ctx::default(), 3).set_threads(2).set_retries(2).set_slot_history(4).build().unwrap();
+        let initial = hive.current_working().unwrap().iter().map(|c| c.fitness).collect::<Vec<_>>();
+        assert!((0..3).all(|n| hive.slot_history(n).unwrap() == vec![initial[n]]));
+        hive.run_for_rounds(30).unwrap();
+        let current_working = hive.current_working().unwrap();
+        for (n, candidate) in current_working.iter().enumerate() {
+            let history = hive.slot_history(n).unwrap();
+            assert!(!history.is_empty() && history.len() <= 4);
+            assert_eq!(history.last(), Some(&candidate.fitness));
+        }
+        assert!((0..3).any(|n| hive.slot_history(n).unwrap().len() == 4));
+
+        let hive = HiveBuilder::new(Ctx::default(), 3).set_threads(1).build().unwrap();
+        hive.run_for_rounds(5).unwrap();
+        assert!(hive.slot_history(0).unwrap().is_empty());
+    }
+
+    struct Unruly;
+
+    impl Context for Unruly {
+        type Solution = f64;
+        type Fitness = f64;
+
+        fn make(&self) -> f64 {
+            thread_rng().gen_range(-1.0, 1.0)
+        }
+
+        fn evaluate_fitness(&self, solution: &f64) -> f64 {
+            if *solution > 0.5 { f64::NAN } else { *solution }
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<f64>>], n: usize) -> f64 {
+            field[n].solution + thread_rng().gen_range(-0.5, 0.5)
+        }
+    }
+
+    #[test]
+    fn invalid_fitness_is_reported() {
+        let result = HiveBuilder::new(Unruly, 10).set_threads(1).build().and_then(|hive| hive.run_for_rounds(50));
+        match result {
+            Err(::result::Error::InvalidFitness { index, value }) => {
+                assert!(index.unwrap() < 10);
+                assert!(value.is_nan() || value < 0.0);
+            }
+            Err(other) => panic!("expected an invalid fitness, got {}", other),
+            Ok(_) => panic!("expected an invalid fitness"),
+        }
+    }
+
+    #[test]
+    fn invalid_fitness_is_corrected() {
+        let hive = HiveBuilder::new(Unruly, 10)
+            .set_threads(2)
+            .set_fitness_policy(FitnessPolicy::Correct)
+            .build()
+            .unwrap();
+        let best = hive.run_for_rounds(50).unwrap();
+        assert!(best.fitness.is_finite());
+        assert!(best.fitness <= 0.5);
+
+        let hive = HiveBuilder::new(Unruly, 10)
+            .set_threads(2)
+            .set_fitness_policy(FitnessPolicy::Worst)
+            .build()
+            .unwrap();
+        let best = hive.run_for_rounds(50).unwrap();
+        assert!(best.fitness > f64::MIN && best.fitness <= 0.5);
+    }
+
+    #[test]
+    fn scores_are_kept() {
+        let scored = || Ctx { score: Some(|solution| -solution as f64), ..Ctx::default() };
+        let hive = HiveBuilder::new(scored(), 4).set_threads(2).build().unwrap();
+        let best = hive.run_for_rounds(5).unwrap();
+        assert_eq!(best.score, Some(-best.fitness));
+
+        let resumed = Hive::from_snapshot(HiveBuilder::new(scored(), 4), hive.snapshot().unwrap()).unwrap();
+        assert_eq!(resumed.get().unwrap().score, Some(-best.fitness));
+    }
+
+    #[test]
+    fn context_panics_are_reported() {
+        let hive = HiveBuilder::new(Fragile, 3).set_threads(2).build().unwrap();
+        match hive.run_for_rounds(5) {
+            Err(::result::Error::Panicked { stage, index, message }) => {
+                assert_eq!(stage, Stage::EvaluateFitness);
+                assert!(index.unwrap() < 3);
+                assert_eq!(message, Some("too big".to_string()));
+            }
+            other => panic!("expected a panic to be reported, got {:?}", other),
+        }
+    }
+
+    struct Odd;
+
+    impl Context for Odd {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            0
+        }
+
+        fn make_with_rng<R: Rng>(&self, rng: &mut R) -> i32 {
+            2 * rng.gen_range(0, 50)
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            if solution % 2 != 0 {
+                panic!("odd");
+            }
+            *solution as f64
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
+            field[n].solution
+        }
+
+        fn explore_with_rng<R: Rng>(&self, field: &[Arc<Candidate<i32>>], n: usize, rng: &mut R) -> i32 {
+            field[n].solution + rng.gen_range(-3, 4)
+        }
+    }
+
+    #[test]
+    fn tolerated_panics_are_scouted() {
+        let hive = HiveBuilder::new(Odd, 5).set_threads(2).set_panic_tolerance(50).build().unwrap();
+        let best = hive.run_for_rounds(20).unwrap();
+        assert_eq!(best.solution % 2, 0);
+        assert!(hive.panic_count() > 0);
+        assert!(hive.stats().unwrap().iter().map(|stats| stats.panics).sum::<usize>() <= hive.panic_count());
+        assert!(hive.scout_count() > 0);
+        // No candidate is left out of the search: each is replaced every
+        // few rounds, by a variant or a scout.
+        let population = hive.population().unwrap();
+        assert!(population.iter().all(|working| working.provenance.unwrap().round >= 10));
+
+        let determined = |threads| {
+            let hive = HiveBuilder::new(Odd, 5)
+                           .set_threads(threads)
+                           .set_panic_tolerance(50)
+                           .set_deterministic(true)
+                           .set_rng_seed(7)
+                           .build()
+                           .unwrap();
+            let best = hive.run_for_rounds(10).unwrap();
+            (best.solution, hive.panic_count(), hive.scout_counts())
+        };
+        assert_eq!(determined(1), determined(3));
+    }
+
+    #[derive(Default)]
+    struct Jittery {
+        calls: AtomicUsize,
+    }
+
+    impl Context for Jittery {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            thread_rng().gen_range(0, 100)
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            // Every third evaluation after the initial population panics,
+            // scouts included.
+            let call = self.calls.fetch_add(1, Ordering::Relaxed);
+            if call >= 4 && call.is_multiple_of(3) {
+                panic!("jitter");
+            }
+            *solution as f64
+        }
+
+        fn explore(&self, _: &[Arc<Candidate<i32>>], _: usize) -> i32 {
+            thread_rng().gen_range(0, 100)
+        }
+    }
+
+    #[test]
+    fn panicking_scouts_are_retried() {
+        let hive = HiveBuilder::new(Jittery::default(), 4)
+                       .set_threads(1)
+                       .set_retries(1)
+                       .set_panic_tolerance(1000)
+                       .build()
+                       .unwrap();
+        hive.run_for_rounds(200).unwrap();
+        assert!(hive.scout_count() > 0);
+        // Each of the 1600 tasks evaluates a solution, and most of those that
+        // panic are followed by a scout; none is skipped for a candidate left
+        // flagged by a failed scout.
+        assert!(hive.snapshot().unwrap().evaluations > 1500);
+        let population = hive.population().unwrap();
+        assert!(population.iter().all(|working| working.provenance.unwrap().round >= 190));
+    }
+
+    struct Sluggish;
+
+    impl Context for Sluggish {
+        type Solution = i32;
+        type Fitness = f64;
+
+        fn make(&self) -> i32 {
+            0
+        }
+
+        fn evaluate_fitness(&self, solution: &i32) -> f64 {
+            if *solution > 0 {
+                thread::sleep(Duration::from_secs(5));
+            }
+            -*solution as f64
+        }
+
+        fn explore(&self, field: &[Arc<Candidate<i32>>], n: usize) -> i32 {
+            field[n].solution + thread_rng().gen_range(-1, 2)
+        }
+    }
+
+    #[test]
+    fn slow_evaluations_time_out() {
+        let timeout = Duration::from_millis(20);
+        let hive = HiveBuilder::new(Sluggish, 3).set_threads(1).set_evaluation_timeout(timeout).build().unwrap();
+        match hive.run_forever() {
+            Err(::result::Error::TimedOut { index, limit }) => {
+                assert!(index.unwrap() < 3);
+                assert_eq!(limit, timeout);
+            }
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+
+        let hive = HiveBuilder::new(Sluggish, 3)
+                       .set_threads(2)
+                       .set_evaluation_timeout(timeout)
+                       .set_panic_tolerance(100)
+                       .build()
+                       .unwrap();
+        let started = Instant::now();
+        let best = hive.run_for_rounds(10).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(best.solution <= 0);
+        assert!(hive.panic_count() > 0);
+    }
+
+    #[test]
+    fn transient_failures_are_retried() {
+        let flaky = || {
+            Ctx {
+                make: |_| 0,
+                fitness: |ctx, solution| {
+                    // Every other call fails.
+                    if ctx.calls.fetch_add(1, Ordering::Relaxed) % 2 == 1 {
+                        panic!("connection reset");
+                    }
+                    solution as f64
+                },
+                ..Ctx::default()
+            }
+        };
+        let hive = HiveBuilder::new(flaky(), 4)
+                       .set_threads(1)
+                       .set_evaluation_retries(1, Duration::from_millis(1))
+                       .build()
+                       .unwrap();
+        hive.run_for_rounds(10).unwrap();
+        assert_eq!(hive.panic_count(), 0);
+
+        // Without retries, even the initial population fails.
+        let unretried = HiveBuilder::new(flaky(), 4).set_threads(1).build();
+        assert!(unretried.and_then(|hive| hive.run_for_rounds(10)).is_err());
+    }
+
+    #[test]
+    fn repeated_panics_end_the_run() {
+        let hive = HiveBuilder::new(Fragile, 3).set_threads(1).set_panic_tolerance(3).build().unwrap();
+        match hive.run_for_rounds(5) {
+            Err(::result::Error::Panicked { stage: Stage::EvaluateFitness, .. }) => {}
+            other => panic!("expected a panic to be reported, got {:?}", other),
+        }
+        assert_eq!(hive.panic_count(), 3);
+    }
+
+    #[test]
+    fn lifecycle_hooks_are_called() {
+        let hive = HiveBuilder::new(Ctx { scratch: true, ..Ctx::default() }, 8).set_threads(3).build().unwrap();
+        hive.run_for_rounds(10).unwrap();
+        hive.run_for_rounds(10).unwrap();
+        let context = hive.context().unwrap();
+        assert_eq!(context.starts.load(Ordering::SeqCst), 2);
+        assert_eq!(context.stops.load(Ordering::SeqCst), 2);
+        let scratches = context.scratches.load(Ordering::SeqCst);
+        assert!((1..=3).contains(&scratches), "made {} scratch states", scratches);
+    }
+
+    // Explores twelve candidates, two partners at a time.
+    fn partnered() -> Ctx {
+        Ctx {
+            explore: |field, n, step| {
+                // Observers still see the whole field.
+                assert!(field.len() == 3 && n == 0 || field.len() == 12);
+                field[n].solution + step
+            },
+            partners: Some(2),
+            ..Ctx::default()
+        }
+    }
+
+    #[test]
+    fn workers_explore_with_partners() {
+        let hive = HiveBuilder::new(partnered(), 12).set_threads(2).build().unwrap();
+        let start = hive.get().unwrap().fitness;
+        assert!(hive.run_for_rounds(30).unwrap().fitness > start);
+    }
+
+    #[test]
+    fn partnered_workers_keep_their_priority() {
+        let hive = HiveBuilder::new(partnered(), 12)
+            .set_threads(1)
+            .set_retries(1000)
+            .set_rng_seed(4)
+            .set_worker_priority(WorkerPriority::Stale)
+            .build()
+            .unwrap();
+        assert!(hive.partners().unwrap().is_some());
+        hive.explorations.store(100_000, Ordering::Relaxed);
+        for visited in &hive.visited[1..] {
+            visited.store(100_000, Ordering::Relaxed);
+        }
+        let (mut rng, mut buffers) = (hive.hive.task_rng(0, 0), Buffers::new());
+        hive.attempt(&Task::Worker(1), &mut rng, &mut buffers).unwrap();
+        // The worker for candidate 1 went to the neglected candidate 0.
+        assert_eq!(hive.visited[0].load(Ordering::Relaxed), 100_001);
+        assert_eq!(hive.visited[1].load(Ordering::Relaxed), 100_000);
+        hive.run_for_rounds(10).unwrap();
+    }
+
+    #[test]
+    fn watches_follow_the_best() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).build().unwrap();
+        let watch = hive.watch_best();
+        let start = watch.latest().unwrap().fitness;
+        let handle = hive.spawn().unwrap();
+        let polled = watch.clone();
+        let poller = thread::spawn(move || (0..20).map(|_| polled.latest().unwrap().fitness).collect::<Vec<_>>());
+        thread::sleep(Duration::from_millis(20));
+        let best = handle.stop_and_join().unwrap();
+        assert!(poller.join().unwrap().windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(best.fitness > start);
+        assert_eq!(watch.latest().unwrap().fitness, best.fitness);
+    }
+
+    #[test]
+    fn controllers_stop_a_borrowed_hive() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).build().unwrap();
+        let controller = hive.controller();
+        assert_eq!(controller.get_round().unwrap(), None);
+        let watched = controller.clone();
+        let watcher = thread::spawn(move || {
+            while watched.get_round().unwrap().unwrap_or(0) < 5 {
+                thread::yield_now();
+            }
+            watched.stop().unwrap();
+        });
+        hive.run_forever().unwrap();
+        watcher.join().unwrap();
+        assert!(hive.stats().unwrap().len() >= 5);
+        assert_eq!(controller.get_round().unwrap(), None);
+        assert_eq!(controller.best().unwrap().fitness, hive.best_fitness());
+    }
+
+    #[test]
+    fn subscribers_come_and_go() {
+        let hive = HiveBuilder::new(Ctx::default(), 5).set_threads(2).build().unwrap();
+        let (kept, dropped) = (hive.subscribe().unwrap(), hive.subscribe().unwrap());
+        drop(dropped);
+        let best = hive.run_for_rounds(30).unwrap();
+        assert_eq!(hive.stats().unwrap().len(), 31);
+        assert_eq!(kept.try_iter().last().unwrap().fitness, best.fitness);
+
+        // With every receiver gone, the next improvement ends the run.
+        drop(kept);
+        hive.run_for_rounds(1_000).unwrap();
+        assert!(hive.stats().unwrap().len() < 1_031);
     }
 }
@@ -0,0 +1,300 @@
+//! Spreads out the initial population.
+//!
+//! By default, each initial candidate is made independently by
+//! [`Context::make_with_rng`](../trait.Context.html#method.make_with_rng).
+//! Independent random points tend to clump, leaving parts of the search space
+//! unvisited, which hurts most when the colony is small. An
+//! [`InitStrategy`](trait.InitStrategy.html) set with
+//! [`HiveBuilder::set_init_strategy`](../struct.HiveBuilder.html#method.set_init_strategy)
+//! generates the whole population at once instead, so that it can cover the
+//! space evenly.
+//!
+//! The strategies in this module sample the unit hypercube and scale the
+//! points to the bounds of a [`Bounded`](trait.Bounded.html) context, such as
+//! [`BoundedVecContext`](../vector/struct.BoundedVecContext.html).
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{HiveBuilder, Objective};
+//! use abc::init::LatinHypercube;
+//! use abc::vector::BoundedVecContext;
+//!
+//! let context = BoundedVecContext::uniform(3, -5.0, 5.0, |x: &[f64]| {
+//!     x.iter().map(|xi| xi * xi).sum::<f64>()
+//! });
+//! let hive = HiveBuilder::new(context, 8)
+//!     .set_objective(Objective::Minimize)
+//!     .set_init_strategy(LatinHypercube)
+//!     .build()
+//!     .unwrap();
+//! assert!(hive.run_for_rounds(20).unwrap().raw_fitness < 75.0);
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::Rng;
+
+use context::Context;
+
+/// Rule for generating the initial population.
+pub trait InitStrategy<Ctx: Context>: Send + Sync {
+    /// Generates `count` solutions.
+    ///
+    /// Strategies that need randomness should draw it from `rng`, so that
+    /// seeded runs stay reproducible.
+    fn initialize(&self, context: &Ctx, count: usize, rng: &mut dyn Rng) -> Vec<Ctx::Solution>;
+}
+
+/// A context whose solutions are vectors of `f64` within per-dimension bounds.
+pub trait Bounded: Context<Solution = Vec<f64>> {
+    /// Returns the lowest and highest allowed values in each dimension. The
+    /// two slices must have the same length.
+    fn bounds(&self) -> (&[f64], &[f64]);
+}
+
+/// Scales points in the unit hypercube to the context's bounds.
+fn scale<Ctx: Bounded>(context: &Ctx, points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let (lower, upper) = context.bounds();
+    points.into_iter()
+          .map(|point| {
+              point.iter()
+                   .enumerate()
+                   .map(|(j, u)| lower[j] + u * (upper[j] - lower[j]))
+                   .collect()
+          })
+          .collect()
+}
+
+/// Latin hypercube sampling.
+///
+/// Each dimension is divided into `count` equal strata, and each stratum
+/// holds exactly one point, at a random position within it. The strata are
+/// matched up across dimensions by independent random permutations, so every
+/// dimension is covered evenly however many there are.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatinHypercube;
+
+impl<Ctx: Bounded> InitStrategy<Ctx> for LatinHypercube {
+    fn initialize(&self, context: &Ctx, count: usize, mut rng: &mut dyn Rng) -> Vec<Vec<f64>> {
+        let dimensions = context.bounds().0.len();
+        let mut points = vec![Vec::with_capacity(dimensions); count];
+        let mut strata = (0..count).collect::<Vec<_>>();
+        for _ in 0..dimensions {
+            (&mut rng).shuffle(&mut strata);
+            for (point, &stratum) in points.iter_mut().zip(strata.iter()) {
+                point.push((stratum as f64 + rng.next_f64()) / count as f64);
+            }
+        }
+        scale(context, points)
+    }
+}
+
+/// The Halton sequence.
+///
+/// Dimension *j* follows the van der Corput sequence in the *j*th prime
+/// base. The sequence is shifted by a random offset in each dimension
+/// (modulo 1), so that different runs start from different points.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Halton;
+
+impl<Ctx: Bounded> InitStrategy<Ctx> for Halton {
+    fn initialize(&self, context: &Ctx, count: usize, rng: &mut dyn Rng) -> Vec<Vec<f64>> {
+        let bases = primes(context.bounds().0.len());
+        let shifts = bases.iter().map(|_| rng.next_f64()).collect::<Vec<_>>();
+        let points = (1..count + 1)
+            .map(|i| {
+                bases.iter()
+                     .zip(shifts.iter())
+                     .map(|(&base, shift)| (radical_inverse(i, base) + shift).fract())
+                     .collect()
+            })
+            .collect();
+        scale(context, points)
+    }
+}
+
+/// Returns the first `n` primes.
+fn primes(n: usize) -> Vec<usize> {
+    let mut primes = Vec::with_capacity(n);
+    let mut candidate = 2;
+    while primes.len() < n {
+        if primes.iter().take_while(|&&p| p * p <= candidate).all(|&p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// Mirrors the digits of `i` in `base` about the radix point.
+fn radical_inverse(mut i: usize, base: usize) -> f64 {
+    let mut result = 0f64;
+    let mut scale = 1f64 / base as f64;
+    while i > 0 {
+        result += (i % base) as f64 * scale;
+        i /= base;
+        scale /= base as f64;
+    }
+    result
+}
+
+/// Primitive polynomials and initial direction numbers for the second and
+/// later dimensions of the Sobol sequence, after Joe and Kuo: the degree
+/// *s*, the coefficients *a* of the middle terms, and *m*<sub>1</sub>, …,
+/// *m*<sub>*s*</sub>.
+const SOBOL_DIRECTIONS: [(u32, u32, &[u32]); 15] = [(1, 0, &[1]),
+                                                    (2, 1, &[1, 3]),
+                                                    (3, 1, &[1, 3, 1]),
+                                                    (3, 2, &[1, 1, 1]),
+                                                    (4, 1, &[1, 1, 3, 3]),
+                                                    (4, 4, &[1, 3, 5, 13]),
+                                                    (5, 2, &[1, 1, 5, 5, 17]),
+                                                    (5, 4, &[1, 1, 5, 5, 5]),
+                                                    (5, 7, &[1, 1, 7, 11, 19]),
+                                                    (5, 11, &[1, 1, 5, 1, 1]),
+                                                    (5, 13, &[1, 1, 1, 3, 11]),
+                                                    (5, 14, &[1, 3, 5, 5, 31]),
+                                                    (6, 1, &[1, 3, 3, 9, 7, 49]),
+                                                    (6, 13, &[1, 1, 1, 15, 21, 21]),
+                                                    (6, 16, &[1, 3, 1, 13, 27, 49])];
+
+/// The most dimensions supported by [`Sobol`](struct.Sobol.html).
+pub const SOBOL_MAX_DIMENSIONS: usize = 16;
+
+/// The Sobol sequence.
+///
+/// Points are generated in Gray code order. Each dimension is scrambled by
+/// a random digital shift (an exclusive or with a random 32-bit word), which
+/// keeps the sequence's even coverage while letting different runs start
+/// from different points. The coverage is most even when the number of
+/// points is a power of two.
+///
+/// Solutions may have up to [`SOBOL_MAX_DIMENSIONS`](constant.SOBOL_MAX_DIMENSIONS.html)
+/// dimensions; beyond that, [`Halton`](struct.Halton.html) is the
+/// alternative.
+///
+/// # Errors
+///
+/// Building a hive fails with [`Error::Panicked`](../enum.Error.html#variant.Panicked)
+/// if the context has too many dimensions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sobol;
+
+impl<Ctx: Bounded> InitStrategy<Ctx> for Sobol {
+    fn initialize(&self, context: &Ctx, count: usize, rng: &mut dyn Rng) -> Vec<Vec<f64>> {
+        let dimensions = context.bounds().0.len();
+        if dimensions > SOBOL_MAX_DIMENSIONS {
+            panic!("Sobol initialization supports at most {} dimensions.", SOBOL_MAX_DIMENSIONS);
+        }
+        let directions = (0..dimensions).map(sobol_directions).collect::<Vec<_>>();
+        let mut x = (0..dimensions).map(|_| rng.next_u32()).collect::<Vec<_>>();
+        let mut points = Vec::with_capacity(count);
+        for i in 0..count {
+            points.push(x.iter().map(|&xj| xj as f64 / 4_294_967_296f64).collect());
+            // Step to the next point in Gray code order.
+            let bit = (i as u32).trailing_ones() as usize;
+            for (xj, v) in x.iter_mut().zip(directions.iter()) {
+                *xj ^= v[bit];
+            }
+        }
+        scale(context, points)
+    }
+}
+
+/// Computes the 32 direction numbers for dimension `j` of the Sobol sequence.
+fn sobol_directions(j: usize) -> [u32; 32] {
+    let mut v = [0u32; 32];
+    if j == 0 {
+        for (k, vk) in v.iter_mut().enumerate() {
+            *vk = 1 << (31 - k);
+        }
+        return v;
+    }
+    let (s, a, m) = SOBOL_DIRECTIONS[j - 1];
+    let s = s as usize;
+    for k in 0..32 {
+        v[k] = if k < s {
+            m[k] << (31 - k)
+        } else {
+            let mut vk = v[k - s] ^ (v[k - s] >> s);
+            for i in 1..s {
+                if (a >> (s - 1 - i)) & 1 == 1 {
+                    vk ^= v[k - i];
+                }
+            }
+            vk
+        };
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive::HiveBuilder;
+    use vector::BoundedVecContext;
+
+    fn context() -> BoundedVecContext<fn(&[f64]) -> f64> {
+        fn sum(x: &[f64]) -> f64 {
+            10.0 + x.iter().sum::<f64>()
+        }
+        BoundedVecContext::new(vec![0.0, -1.0, 2.0], vec![1.0, 1.0, 6.0], sum as fn(&[f64]) -> f64)
+    }
+
+    // Checks that every dimension has exactly one point in each of `count`
+    // equal strata.
+    fn stratified(points: &[Vec<f64>], lower: &[f64], upper: &[f64]) -> bool {
+        let count = points.len();
+        (0..lower.len()).all(|j| {
+            let mut strata = points.iter()
+                                   .map(|p| ((p[j] - lower[j]) / (upper[j] - lower[j]) * count as f64) as usize)
+                                   .collect::<Vec<_>>();
+            strata.sort();
+            strata == (0..count).collect::<Vec<_>>()
+        })
+    }
+
+    #[test]
+    fn latin_hypercube_is_stratified() {
+        let context = context();
+        let points = LatinHypercube.initialize(&context, 10, &mut rand::thread_rng());
+        assert!(stratified(&points, context.lower(), context.upper()));
+    }
+
+    #[test]
+    fn sequences_stay_in_bounds() {
+        let context = context();
+        for points in [Halton.initialize(&context, 50, &mut rand::thread_rng()),
+                       Sobol.initialize(&context, 50, &mut rand::thread_rng())] {
+            assert_eq!(points.len(), 50);
+            assert!(points.iter().all(|p| {
+                p.iter().enumerate().all(|(j, x)| *x >= context.lower()[j] && *x < context.upper()[j])
+            }));
+        }
+    }
+
+    #[test]
+    fn sobol_is_stratified() {
+        // The first 2^k points of the unshifted sequence have one point in
+        // each stratum of width 2^-k, and a digital shift preserves this.
+        let context = context();
+        let points = Sobol.initialize(&context, 16, &mut rand::thread_rng());
+        assert!(stratified(&points, context.lower(), context.upper()));
+    }
+
+    #[test]
+    fn hive_uses_strategy() {
+        let hive = HiveBuilder::new(context(), 8)
+            .set_initial_solutions(vec![vec![1.0, 1.0, 6.0]])
+            .set_init_strategy(LatinHypercube)
+            .build()
+            .unwrap();
+        let population = hive.population().unwrap();
+        assert_eq!(population[0].solution, vec![1.0, 1.0, 6.0]);
+        let rest = population[1..].iter().map(|w| w.solution.clone()).collect::<Vec<_>>();
+        assert!(stratified(&rest, &[0.0, -1.0, 2.0], &[1.0, 1.0, 6.0]));
+    }
+}
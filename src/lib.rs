@@ -3,11 +3,12 @@
 #![doc(html_root_url = "https://daviddonna.github.io/abc-rs/")]
 
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! Runs Karaboga's Artificial Bee Colony algorithm in parallel.
 //!
 //! To take advantage of this crate, the user must implement the
-//! [`Solution`](trait.Solution.html) trait for a type of their creation.
+//! [`Context`](trait.Context.html) trait for a type of their creation.
 //! A [`Hive`](struct.Hive.html) of the appropriate type can then be built,
 //! which will search the solution space for the fittest candidate.
 //!
@@ -21,8 +22,10 @@
 //! extern crate rand;
 //!
 //! use std::f32::consts::PI;
-//! use rand::{random, Closed01, thread_rng, Rng};
-//! use abc::{Context, Candidate, HiveBuilder};
+//! use std::sync::Arc;
+//! use rand::{random, Closed01, thread_rng};
+//! use abc::{Context, Candidate, HiveBuilder, Objective};
+//! use abc::util::choose_other_candidate;
 //!
 //! const SIZE: usize = 10;
 //!
@@ -40,6 +43,7 @@
 //!
 //! impl Context for SBuilder {
 //!     type Solution = [f32;SIZE];
+//!     type Fitness = f64;
 //!
 //!     fn make(&self) -> [f32;SIZE] {
 //!         let mut new = [0.0;SIZE];
@@ -54,17 +58,10 @@
 //!         let sum = solution.iter()
 //!                           .map(|x| x.powf(2.0) - self.a * (*x * 2.0 * PI).cos())
 //!                           .fold(0.0, |total, next| total + next);
-//!         let rastrigin = ((self.a * SIZE as f32) + sum) as f64;
-//!
-//!         // Minimize.
-//!         if rastrigin >= 0.0 {
-//!             1.0 / (1.0 + rastrigin)
-//!         } else {
-//!             1.0 + rastrigin.abs()
-//!         }
+//!         ((self.a * SIZE as f32) + sum) as f64
 //!     }
 //!
-//!     fn explore(&self, field: &[Candidate<[f32;SIZE]>], index: usize) -> [f32;SIZE] {
+//!     fn explore(&self, field: &[Arc<Candidate<[f32;SIZE]>>], index: usize) -> [f32;SIZE] {
 //!         // new[i] = current[i] + Φ * (current[i] - other[i]), where:
 //!         //      phi_min <= Φ <= phi_max
 //!         //      other is a solution, other than current, chosen at random
@@ -72,13 +69,11 @@
 //!         let ref current = field[index].solution;
 //!         let mut new = [0_f32;SIZE];
 //!
-//!         for i in 0..SIZE {
-//!             // Choose a different vector at random.
-//!             let mut rng = thread_rng();
-//!             let mut index2 = rng.gen_range(0, current.len() - 1);
-//!             if index2 >= index { index2 += 1; }
-//!             let ref other = field[index2].solution;
+//!         // Choose a different vector at random.
+//!         let other_index = choose_other_candidate(field, index, &mut thread_rng());
+//!         let ref other = field[other_index].solution;
 //!
+//!         for i in 0..SIZE {
 //!             let phi = random::<Closed01<f32>>().0 * (self.p_max - self.p_min) + self.p_min;
 //!             new[i] = current[i] + (phi * (current[i] - other[i]));
 //!         }
@@ -95,7 +90,8 @@
 //!         p_min: -1.0,
 //!         p_max: 1.0
 //!     };
-//!     let hive_builder = HiveBuilder::new(builder, 10);
+//!     // The hive minimizes the Rastrigin function directly.
+//!     let hive_builder = HiveBuilder::new(builder, 10).set_objective(Objective::Minimize);
 //!     let hive = hive_builder.build().unwrap();
 //!
 //!     // Once built, the hive can be run for a number of rounds.
@@ -107,29 +103,197 @@
 //!     // The algorithm doesn't guarantee improvement in any number of rounds,
 //!     // but it always keeps its all-time best.
 //!     assert!(best_after_20.fitness >= best_after_10.fitness);
+//!     assert!(best_after_20.raw_fitness <= best_after_10.raw_fitness);
 //!
 //!     // The hive can be consumed to create a Receiver object. This can be
 //!     // iterated over indefinitely, and will receive successive improvements
 //!     // on the best candidate so far.
+//!     let (handle, receiver) = hive.stream();
 //!     let mut current_best_fitness = best_after_20.fitness;
-//!     for new_best in hive.stream().iter().take(3) {
+//!     for new_best in receiver.iter().take(3) {
 //!         // The iterator will start with the best result so far; after that,
 //!         // each new candidate will be an improvement.
 //!         assert!(new_best.fitness >= current_best_fitness);
 //!         current_best_fitness = new_best.fitness;
 //!     }
+//!
+//!     // The handle stops the hive without waiting for another improvement.
+//!     handle.stop().unwrap();
+//!     assert!(handle.join().unwrap().fitness >= current_best_fitness);
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! * `std`, on by default, builds everything but the
+//!   [`colony`](colony/index.html) module,
+//!   [`Candidate`](struct.Candidate.html) and a few plain settings, which
+//!   only need `alloc`. Without it, the crate is `no_std`. Every other
+//!   feature implies it.
+//! * `serde` implements `Serialize` and `Deserialize` for
+//!   [`Candidate`](struct.Candidate.html), [`HiveSnapshot`](struct.HiveSnapshot.html),
+//!   and the other plain data types returned by the hive.
+//! * `rayon` adds [`Executor::Rayon`](enum.Executor.html#variant.Rayon), which
+//!   runs each round's tasks on a rayon thread pool.
+//! * `async` adds [`AsyncContext`](trait.AsyncContext.html) and
+//!   [`AsyncHive`](struct.AsyncHive.html), for contexts whose work is
+//!   IO-bound. The hive returns futures, which can be driven by tokio or any
+//!   other executor.
+//! * `binary` adds the [`binary`](binary/index.html) module, for searching
+//!   binary strings, as in feature selection.
+//! * `tracing` instruments the hive with [`tracing`](https://docs.rs/tracing)
+//!   spans for each run and round, and events for new bests, scouting and
+//!   each round's statistics.
+//! * `remote` adds the [`remote`](remote/index.html) module, for evaluating
+//!   fitness on worker nodes over TCP. It implies `serde`.
+//! * `process` adds the [`process`](process/index.html) module, for
+//!   evaluating fitness with a pool of external programs. It implies `serde`.
+//! * `contention` adds the [`contention`](contention/index.html) module,
+//!   which times every wait on the hive's locks, for benchmarking.
+//! * `python` adds the [`python`](python/index.html) module, which exposes
+//!   the hive to Python through pyo3, with a context of Python callables.
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate core;
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "async")]
+extern crate futures;
+
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "python")]
+extern crate pyo3;
 
+#[cfg(feature = "std")]
+#[macro_use]
+mod trace;
+#[cfg(feature = "std")]
 mod result;
+#[cfg(feature = "std")]
 mod task;
+#[cfg(feature = "std")]
 mod context;
 mod candidate;
+#[cfg(feature = "std")]
 mod hive;
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
+mod snapshot;
+#[cfg(feature = "std")]
+mod executor;
+#[cfg(feature = "std")]
+mod evaluator;
+#[cfg(feature = "std")]
+mod observer;
+#[cfg(feature = "std")]
+mod logger;
+#[cfg(feature = "std")]
+mod event;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod archipelago;
+mod limit;
+#[cfg(feature = "std")]
+mod elite;
+#[cfg(feature = "std")]
+mod niching;
+mod selection;
+mod priority;
+mod fitness;
+mod objective;
+#[cfg(feature = "async")]
+mod async_hive;
 
+pub mod colony;
+#[cfg(feature = "std")]
 pub mod scaling;
+#[cfg(feature = "std")]
+pub mod stop;
+#[cfg(feature = "std")]
+pub mod pareto;
+#[cfg(feature = "std")]
+pub mod vector;
+#[cfg(feature = "std")]
+pub mod discrete;
+#[cfg(feature = "std")]
+pub mod mixed;
+#[cfg(feature = "binary")]
+pub mod binary;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod scout;
+#[cfg(feature = "std")]
+pub mod init;
+#[cfg(feature = "std")]
+pub mod chaos;
+#[cfg(feature = "std")]
+pub mod local;
+#[cfg(feature = "std")]
+pub mod tuning;
+#[cfg(feature = "std")]
+pub mod util;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod compare;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "process")]
+pub mod process;
+#[cfg(feature = "contention")]
+pub mod contention;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod allocations;
 
-pub use result::{Error, Result};
-pub use context::Context;
-pub use candidate::Candidate;
-pub use hive::{HiveBuilder, Hive};
+#[cfg(feature = "std")]
+pub use result::{Error, Result, Stage};
+#[cfg(feature = "std")]
+pub use context::{Context, Evaluation, FnContext, Scratch};
+pub use candidate::{Candidate, Noise, Origin, Provenance};
+#[cfg(feature = "std")]
+pub use hive::{BestWatch, HiveBuilder, Hive, HiveController, HiveHandle, Rounds, StreamHandle};
+#[cfg(feature = "std")]
+pub use executor::Executor;
+#[cfg(feature = "std")]
+pub use evaluator::{ContextEvaluator, Evaluator};
+#[cfg(feature = "std")]
+pub use config::HiveConfig;
+#[cfg(feature = "std")]
+pub use observer::HiveObserver;
+#[cfg(feature = "std")]
+pub use logger::{LogFormat, RunLogger};
+#[cfg(feature = "std")]
+pub use event::HiveEvent;
+#[cfg(feature = "std")]
+pub use stream::StreamBuffer;
+#[cfg(feature = "std")]
+pub use archipelago::{Archipelago, Topology};
+pub use limit::LimitStrategy;
+#[cfg(feature = "std")]
+pub use niching::Niching;
+pub use selection::Selection;
+pub use priority::WorkerPriority;
+pub use fitness::{Fitness, FitnessPolicy, TotalFitness};
+pub use objective::Objective;
+#[cfg(feature = "std")]
+pub use snapshot::{HiveSnapshot, WorkingSnapshot};
+#[cfg(feature = "async")]
+pub use async_hive::{AsyncContext, AsyncHiveBuilder, AsyncHive};
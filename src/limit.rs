@@ -0,0 +1,45 @@
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How many times a candidate may go unimproved before it is abandoned.
+///
+/// In the ABC literature, this is the "limit". Once a candidate has been
+/// explored that many times without improvement, a scout replaces it. Each
+/// candidate's limit is decided when it enters the field, either from
+/// scouting or by improving on its predecessor.
+pub enum LimitStrategy {
+    /// Every candidate gets the same limit, set by
+    /// [`HiveBuilder::set_retries`](struct.HiveBuilder.html#method.set_retries).
+    /// This is the default.
+    #[default]
+    Fixed,
+
+    /// Fitter candidates get more patience.
+    ///
+    /// The limit is interpolated between `min` and `max`, according to where
+    /// the candidate's fitness falls between the worst and best fitness in
+    /// the field.
+    Fitness {
+        /// Limit for a candidate no fitter than the worst in the field.
+        min: usize,
+
+        /// Limit for a candidate at least as fit as the best in the field.
+        max: usize,
+    },
+
+    /// The limit is tuned online, from how often exploration succeeds.
+    ///
+    /// If a fraction *p* of explorations have improved on their candidate so
+    /// far, a candidate can expect to wait 1/*p* explorations for an
+    /// improvement. It is given `factor` times that, clamped to
+    /// [`min`, `max`]. Until the first exploration, the limit is `max`.
+    SuccessRate {
+        /// Multiple of the expected wait for an improvement.
+        factor: f64,
+
+        /// Smallest limit to give.
+        min: usize,
+
+        /// Largest limit to give.
+        max: usize,
+    },
+}
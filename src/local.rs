@@ -0,0 +1,303 @@
+//! Local search, for memetic hybrids of ABC.
+//!
+//! The bees are good at finding the right region of the search space, but
+//! slow to pin down the optimum within it. A memetic hybrid hands promising
+//! candidates to a local optimizer, such as Nelder–Mead, to polish. A
+//! [`LocalSearch`](trait.LocalSearch.html) is set with
+//! [`HiveBuilder::set_local_search`](../struct.HiveBuilder.html#method.set_local_search),
+//! along with a [`Schedule`](enum.Schedule.html) saying which candidates to
+//! refine, and when.
+//!
+//! For vector solutions, [`NelderMead`](struct.NelderMead.html) is ready to
+//! use as the local search, and [`nelder_mead`](fn.nelder_mead.html) can
+//! also be used on its own.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{Candidate, HiveBuilder};
+//! use abc::local::{Refine, Schedule};
+//! use abc::vector::BoundedVecContext;
+//!
+//! // Maximize 30 - x², refining by nudging toward 0.
+//! let context = BoundedVecContext::uniform(1, -5.0, 5.0, |x: &[f64]| 30.0 - x[0] * x[0]);
+//! let nudge = |_: &BoundedVecContext<_>, candidate: &Candidate<Vec<f64>>, _: &mut dyn FnMut(&Vec<f64>) -> f64| {
+//!     Some(vec![candidate.solution[0] / 2.0])
+//! };
+//! let hive = HiveBuilder::new(context, 4)
+//!     .set_local_search(Refine(nudge), Schedule::Best(1))
+//!     .build()
+//!     .unwrap();
+//! assert!(hive.run_for_rounds(10).unwrap().fitness > 29.0);
+//! # }
+//! ```
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use candidate::Candidate;
+use context::Context;
+use init::Bounded;
+
+/// A local optimizer, which refines a single candidate.
+pub trait LocalSearch<Ctx: Context>: Send + Sync {
+    /// Tries to improve on `candidate`.
+    ///
+    /// `fitness` evaluates a solution, returning the fitness the hive would
+    /// give it (so higher is always better, whatever the
+    /// [`Objective`](../enum.Objective.html)). Each call counts as a fitness
+    /// evaluation; an unusable fitness comes back as negative infinity. The
+    /// returned solution, if any, is evaluated once more and kept if it is
+    /// fitter than the candidate.
+    fn refine(&self,
+              context: &Ctx,
+              candidate: &Candidate<Ctx::Solution, Ctx::Fitness>,
+              fitness: &mut dyn FnMut(&Ctx::Solution) -> f64)
+              -> Option<Ctx::Solution>;
+}
+
+/// A local search given by a function.
+///
+/// The function has the same signature as
+/// [`LocalSearch::refine`](trait.LocalSearch.html#tymethod.refine).
+#[derive(Clone, Copy, Debug)]
+pub struct Refine<F>(pub F);
+
+impl<Ctx, F> LocalSearch<Ctx> for Refine<F>
+    where Ctx: Context,
+          F: Fn(&Ctx, &Candidate<Ctx::Solution, Ctx::Fitness>, &mut dyn FnMut(&Ctx::Solution) -> f64) -> Option<Ctx::Solution>
+              + Send
+              + Sync
+{
+    fn refine(&self,
+              context: &Ctx,
+              candidate: &Candidate<Ctx::Solution, Ctx::Fitness>,
+              fitness: &mut dyn FnMut(&Ctx::Solution) -> f64)
+              -> Option<Ctx::Solution> {
+        (self.0)(context, candidate, fitness)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Which candidates a [`LocalSearch`](trait.LocalSearch.html) refines, and when.
+pub enum Schedule {
+    /// Refines the fittest working candidate at the end of every *n*th
+    /// round.
+    Best(usize),
+
+    /// Refines each candidate as soon as exploration improves on it. This
+    /// can take many more evaluations than `Best`.
+    Improved,
+}
+
+/// Minimizes `f` by the Nelder–Mead simplex method, starting from `start`.
+///
+/// The initial simplex has `start` as one vertex, and each other vertex
+/// `step` further along one axis. The search stops when the values at the
+/// vertices agree to within a relative 10<sup>-12</sup>, or once
+/// `max_evaluations` calls to `f` have been made (the last iteration may
+/// make a few more). Returns the best point found, and its value.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use abc::local::nelder_mead;
+///
+/// let (x, value) = nelder_mead(|x: &[f64]| (x[0] - 1.0).powi(2) + (x[1] + 2.0).powi(2), &[0.0, 0.0], 0.5, 500);
+/// assert!((x[0] - 1.0).abs() < 1e-3 && (x[1] + 2.0).abs() < 1e-3);
+/// assert!(value < 1e-6);
+/// # }
+/// ```
+pub fn nelder_mead<F>(f: F, start: &[f64], step: f64, max_evaluations: usize) -> (Vec<f64>, f64)
+    where F: FnMut(&[f64]) -> f64
+{
+    minimize(f, start, &vec![step; start.len()], max_evaluations)
+}
+
+/// Nelder–Mead, with a separate initial step in each dimension.
+fn minimize<F>(mut f: F, start: &[f64], steps: &[f64], max_evaluations: usize) -> (Vec<f64>, f64)
+    where F: FnMut(&[f64]) -> f64
+{
+    let n = start.len();
+    let evaluations = Cell::new(0);
+    let mut evaluate = |x: &[f64]| {
+        evaluations.set(evaluations.get() + 1);
+        f(x)
+    };
+
+    let mut simplex = Vec::with_capacity(n + 1);
+    simplex.push(start.to_vec());
+    for (j, step) in steps.iter().enumerate() {
+        let mut vertex = start.to_vec();
+        vertex[j] += step;
+        simplex.push(vertex);
+    }
+    let mut values = simplex.iter().map(|x| evaluate(x)).collect::<Vec<_>>();
+
+    // Moves `from` toward (or, for negative `t`, away from) `to`.
+    let lerp = |from: &[f64], to: &[f64], t: f64| {
+        from.iter().zip(to.iter()).map(|(a, b)| a + t * (b - a)).collect::<Vec<_>>()
+    };
+
+    while evaluations.get() < max_evaluations {
+        // Sort the vertices, best first.
+        let mut order = (0..n + 1).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        let (best, worst) = (values[0], values[n]);
+        let spread = worst - best;
+        if spread.is_nan() || spread <= 1e-12 * (1f64 + best.abs()) {
+            break;
+        }
+
+        let centroid = (0..n).map(|j| simplex[..n].iter().map(|x| x[j]).sum::<f64>() / n as f64)
+                             .collect::<Vec<_>>();
+        let reflected = lerp(&centroid, &simplex[n], -1f64);
+        let reflected_value = evaluate(&reflected);
+        if reflected_value < best {
+            let expanded = lerp(&centroid, &simplex[n], -2f64);
+            let expanded_value = evaluate(&expanded);
+            if expanded_value < reflected_value {
+                simplex[n] = expanded;
+                values[n] = expanded_value;
+            } else {
+                simplex[n] = reflected;
+                values[n] = reflected_value;
+            }
+        } else if reflected_value < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = reflected_value;
+        } else {
+            let contracted = if reflected_value < worst {
+                lerp(&centroid, &reflected, 0.5)
+            } else {
+                lerp(&centroid, &simplex[n], 0.5)
+            };
+            let contracted_value = evaluate(&contracted);
+            if contracted_value < reflected_value.min(worst) {
+                simplex[n] = contracted;
+                values[n] = contracted_value;
+            } else {
+                // Shrink toward the best vertex.
+                for i in 1..n + 1 {
+                    simplex[i] = lerp(&simplex[0], &simplex[i], 0.5);
+                    values[i] = evaluate(&simplex[i]);
+                }
+            }
+        }
+    }
+
+    let best = (1..n + 1).fold(0, |best, i| if values[i] < values[best] { i } else { best });
+    (simplex.swap_remove(best), values[best])
+}
+
+/// Nelder–Mead, as a local search for [`Bounded`](../init/trait.Bounded.html)
+/// contexts.
+///
+/// The search starts from the candidate, with an initial simplex `step`
+/// times the width of the bounds in each dimension, and maximizes the
+/// fitness. Points outside the bounds are clamped before they are evaluated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NelderMead {
+    /// Size of the initial simplex, as a fraction of the width of the bounds.
+    pub step: f64,
+
+    /// Most fitness evaluations to spend on each refinement.
+    pub max_evaluations: usize,
+}
+
+impl Default for NelderMead {
+    /// A small simplex, 0.05 of the bounds, and up to 100 evaluations.
+    fn default() -> NelderMead {
+        NelderMead {
+            step: 0.05,
+            max_evaluations: 100,
+        }
+    }
+}
+
+impl<Ctx: Bounded> LocalSearch<Ctx> for NelderMead {
+    fn refine(&self,
+              context: &Ctx,
+              candidate: &Candidate<Vec<f64>, Ctx::Fitness>,
+              fitness: &mut dyn FnMut(&Vec<f64>) -> f64)
+              -> Option<Vec<f64>> {
+        let (lower, upper) = context.bounds();
+        let clamp = |x: &[f64]| {
+            x.iter().enumerate().map(|(j, xj)| xj.max(lower[j]).min(upper[j])).collect::<Vec<_>>()
+        };
+        let steps = lower.iter().zip(upper.iter()).map(|(l, u)| self.step * (u - l)).collect::<Vec<_>>();
+        let (best, value) = minimize(|x| -fitness(&clamp(x)), &candidate.solution, &steps, self.max_evaluations);
+        if -value > candidate.fitness {
+            Some(clamp(&best))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candidate::Origin;
+    use hive::HiveBuilder;
+    use vector::BoundedVecContext;
+
+    #[test]
+    fn nelder_mead_finds_minimum() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let (x, value) = nelder_mead(rosenbrock, &[-1.2, 1.0], 0.1, 2000);
+        assert!(value < 1e-8);
+        assert!((x[0] - 1.0).abs() < 1e-3 && (x[1] - 1.0).abs() < 1e-3);
+
+        // The budget is respected, give or take an iteration.
+        let mut calls = 0;
+        nelder_mead(|x: &[f64]| {
+                        calls += 1;
+                        rosenbrock(x)
+                    },
+                    &[-1.2, 1.0],
+                    0.1,
+                    50);
+        assert!((50..=53).contains(&calls));
+    }
+
+    #[test]
+    fn nelder_mead_refines_best() {
+        let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(1)
+            .set_local_search(NelderMead::default(), Schedule::Best(2))
+            .build()
+            .unwrap();
+        let best = hive.run_for_rounds(6).unwrap();
+        assert!(best.fitness > 60.0 - 1e-6);
+
+        let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| -x[0]);
+        let never = HiveBuilder::new(context, 4).set_local_search(NelderMead::default(), Schedule::Best(0));
+        assert!(never.build().is_err());
+    }
+
+    #[test]
+    fn improved_candidates_are_refined() {
+        let context = BoundedVecContext::uniform(1, -5.0, 5.0, |x: &[f64]| 30.0 - x[0] * x[0]);
+        let halve = |_: &BoundedVecContext<_>, candidate: &Candidate<Vec<f64>>, fitness: &mut dyn FnMut(&Vec<f64>) -> f64| {
+            let halved = vec![candidate.solution[0] / 2.0];
+            if fitness(&halved) > candidate.fitness { Some(halved) } else { None }
+        };
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(1)
+            .set_local_search(Refine(halve), Schedule::Improved)
+            .set_rng_seed(7)
+            .build()
+            .unwrap();
+        hive.run_for_rounds(10).unwrap();
+        let population = hive.population().unwrap();
+        assert!(population.iter().any(|w| w.provenance.is_some_and(|p| p.origin == Origin::Refined)));
+    }
+}
@@ -0,0 +1,144 @@
+use std::io::Write;
+use std::time::Instant;
+
+use observer::HiveObserver;
+use stats::RoundStats;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How a [`RunLogger`](struct.RunLogger.html) writes its records.
+pub enum LogFormat {
+    /// Comma-separated values, with a header line before the first record.
+    Csv,
+
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// The fields of each record, in order.
+const FIELDS: [&str; 8] = ["round", "best", "mean", "worst", "std_dev", "scouts", "evaluations", "elapsed"];
+
+/// An observer that writes one record per round, for experiments.
+///
+/// Each record has the round number, the best, mean and worst fitness of the
+/// working candidates and their standard deviation, the scouts and fitness
+/// evaluations during the round, and the seconds elapsed since the logger was
+/// created. The first record describes the initial population, as round 0.
+///
+/// The logger flushes after every record. If a write fails, it asks the hive
+/// to stop, rather than losing records silently. It is usually added with
+/// [`HiveBuilder::set_logger`](struct.HiveBuilder.html#method.set_logger).
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use std::io::stdout;
+/// use abc::{HiveBuilder, LogFormat};
+/// use abc::vector::BoundedVecContext;
+///
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+/// let hive = HiveBuilder::new(context, 5).set_logger(stdout(), LogFormat::Csv).build().unwrap();
+/// hive.run_for_rounds(10).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RunLogger<W> {
+    writer: W,
+    format: LogFormat,
+    started: Instant,
+    header_written: bool,
+}
+
+impl<W: Write> RunLogger<W> {
+    /// Creates a logger that writes to `writer`.
+    pub fn new(writer: W, format: LogFormat) -> RunLogger<W> {
+        RunLogger {
+            writer,
+            format,
+            started: Instant::now(),
+            header_written: false,
+        }
+    }
+
+    /// Consumes the logger, returning the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes the record for a round.
+    pub fn write_record(&mut self, stats: &RoundStats) -> ::std::io::Result<()> {
+        let values = [stats.round.to_string(),
+                      number(stats.best),
+                      number(stats.mean),
+                      number(stats.worst),
+                      number(stats.std_dev),
+                      stats.scouts.to_string(),
+                      stats.evaluations.to_string(),
+                      number(self.started.elapsed().as_secs_f64())];
+        match self.format {
+            LogFormat::Csv => {
+                if !self.header_written {
+                    writeln!(self.writer, "{}", FIELDS.join(","))?;
+                    self.header_written = true;
+                }
+                writeln!(self.writer, "{}", values.join(","))?;
+            }
+            LogFormat::JsonLines => {
+                let pairs = FIELDS.iter()
+                                  .zip(values.iter())
+                                  .map(|(field, value)| format!("\"{}\":{}", field, value))
+                                  .collect::<Vec<_>>();
+                writeln!(self.writer, "{{{}}}", pairs.join(","))?;
+            }
+        }
+        self.writer.flush()
+    }
+}
+
+/// Formats a number for either format. NaN and infinities, which JSON
+/// can't represent, are written as `null`.
+fn number(x: f64) -> String {
+    if x.is_finite() { x.to_string() } else { "null".to_string() }
+}
+
+impl<S, F, W> HiveObserver<S, F> for RunLogger<W>
+    where S: Clone + Send + Sync + 'static,
+          W: Write + Send
+{
+    fn on_round_complete(&mut self, stats: &RoundStats) -> bool {
+        self.write_record(stats).is_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(round: usize) -> RoundStats {
+        RoundStats::new(round, &[1.0, 2.0, 3.0], 1, 6)
+    }
+
+    #[test]
+    fn writes_csv() {
+        let mut logger = RunLogger::new(Vec::new(), LogFormat::Csv);
+        logger.write_record(&stats(0)).unwrap();
+        logger.write_record(&stats(1)).unwrap();
+        let text = String::from_utf8(logger.into_inner()).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "round,best,mean,worst,std_dev,scouts,evaluations,elapsed");
+        assert!(lines[2].starts_with("1,3,2,1,"));
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let mut logger = RunLogger::new(Vec::new(), LogFormat::JsonLines);
+        let mut infinite = stats(2);
+        infinite.worst = f64::NEG_INFINITY;
+        logger.write_record(&infinite).unwrap();
+        let text = String::from_utf8(logger.into_inner()).unwrap();
+        assert!(text.starts_with("{\"round\":2,\"best\":3,\"mean\":2,\"worst\":null,"));
+        assert!(text.ends_with("}\n"));
+    }
+}
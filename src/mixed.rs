@@ -0,0 +1,272 @@
+//! Ready-made context for mixed continuous, integer and categorical variables.
+//!
+//! Hyperparameter tuning and many engineering problems mix real-valued
+//! dimensions with whole numbers (a layer count, a batch size) and unordered
+//! choices (an activation function). [`MixedContext`](struct.MixedContext.html)
+//! describes each dimension with a [`Variable`](enum.Variable.html), and keeps
+//! every solution valid: integers stay whole and categories stay in range.
+//!
+//! Solutions are `Vec<f64>`, so that they work with the rest of the crate.
+//! Integer dimensions hold whole numbers, and categorical dimensions hold the
+//! index of the chosen category.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::mixed::{MixedContext, Variable};
+//!
+//! // A learning rate, a number of layers, and one of three optimizers.
+//! let variables = vec![Variable::Continuous { min: 0.0001, max: 0.1 },
+//!                      Variable::Integer { min: 1, max: 8 },
+//!                      Variable::Categorical { count: 3 }];
+//! let context = MixedContext::new(variables, |x: &[f64]| {
+//!     let optimizer_bonus = if x[2] == 1.0 { 1.0 } else { 0.0 };
+//!     10.0 - (x[0] - 0.01).abs() - (x[1] - 4.0).abs() + optimizer_bonus
+//! });
+//! let best = HiveBuilder::new(context, 10).build().unwrap().run_for_rounds(100).unwrap();
+//! assert_eq!(best.solution[1], 4.0);
+//! assert_eq!(best.solution[2], 1.0);
+//! # }
+//! ```
+
+extern crate rand;
+
+use std::sync::Arc;
+
+use self::rand::{thread_rng, Rng};
+
+use candidate::Candidate;
+use context::Context;
+use util::choose_other_candidate;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The kind and range of one dimension of a mixed solution.
+pub enum Variable {
+    /// A real number between `min` and `max`.
+    Continuous {
+        /// Lowest allowed value.
+        min: f64,
+        /// Highest allowed value.
+        max: f64,
+    },
+
+    /// A whole number between `min` and `max`, inclusive.
+    Integer {
+        /// Lowest allowed value.
+        min: i64,
+        /// Highest allowed value.
+        max: i64,
+    },
+
+    /// One of `count` unordered choices, numbered from 0.
+    Categorical {
+        /// Number of choices.
+        count: usize,
+    },
+}
+
+impl Variable {
+    /// Draws a value uniformly at random.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Variable::Continuous { min, max } => min + rng.next_f64() * (max - min),
+            Variable::Integer { min, max } => rng.gen_range(min, max + 1) as f64,
+            Variable::Categorical { count } => rng.gen_range(0, count) as f64,
+        }
+    }
+
+    /// Moves `value` to the nearest allowed value.
+    pub fn repair(&self, value: f64) -> f64 {
+        match *self {
+            Variable::Continuous { min, max } => value.max(min).min(max),
+            Variable::Integer { min, max } => value.round().max(min as f64).min(max as f64),
+            Variable::Categorical { count } => value.round().max(0f64).min((count - 1) as f64),
+        }
+    }
+
+    /// Reflects `value` through the middle of the range. Categories are
+    /// reflected by their index.
+    pub fn opposite(&self, value: f64) -> f64 {
+        match *self {
+            Variable::Continuous { min, max } => min + max - value,
+            Variable::Integer { min, max } => (min + max) as f64 - value,
+            Variable::Categorical { count } => (count - 1) as f64 - value,
+        }
+    }
+}
+
+/// Context for searching mixed continuous, integer and categorical vectors.
+///
+/// New solutions are drawn uniformly from each variable's range.
+/// Exploration changes one random dimension *j*, with a different candidate
+/// *k* from the field. A continuous or integer dimension follows Karaboga's
+/// rule,
+///
+/// <center>*v*<sub>*j*</sub> = *x*<sub>*j*</sub>
+/// + φ(*x*<sub>*j*</sub> − *x*<sub>*k*,*j*</sub>)</center>
+///
+/// with φ drawn uniformly from [-1, 1], then rounded (for integers) and
+/// clamped to the range. Since an integer the same as its partner's would
+/// never move, it steps by one in a random direction instead. Categories have
+/// no order, so a categorical dimension is redrawn from its other choices.
+pub struct MixedContext<F> {
+    variables: Vec<Variable>,
+    fitness: F,
+}
+
+impl<F> MixedContext<F>
+    where F: Fn(&[f64]) -> f64 + Send + Sync
+{
+    /// Creates a context with one dimension for each of `variables`.
+    ///
+    /// * `variables` - The kind and range of each dimension.
+    /// * `fitness` - Scores a solution (the algorithm will maximize this, and
+    ///   observers expect it to be positive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no variables, or any variable has an empty range.
+    pub fn new(variables: Vec<Variable>, fitness: F) -> MixedContext<F> {
+        if variables.is_empty() {
+            panic!("MixedContext must have at least one variable.");
+        }
+        let valid = |v: &Variable| {
+            match *v {
+                Variable::Continuous { min, max } => min <= max,
+                Variable::Integer { min, max } => min <= max,
+                Variable::Categorical { count } => count > 0,
+            }
+        };
+        if !variables.iter().all(valid) {
+            panic!("MixedContext variables must have non-empty ranges.");
+        }
+        MixedContext { variables, fitness }
+    }
+
+    /// Returns the variable for each dimension.
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+}
+
+impl<F> Context for MixedContext<F>
+    where F: Fn(&[f64]) -> f64 + Send + Sync
+{
+    type Solution = Vec<f64>;
+    type Fitness = f64;
+
+    fn make(&self) -> Vec<f64> {
+        self.make_with_rng(&mut thread_rng())
+    }
+
+    fn evaluate_fitness(&self, solution: &Vec<f64>) -> f64 {
+        (self.fitness)(solution)
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<Vec<f64>>>], index: usize) -> Vec<f64> {
+        self.explore_with_rng(field, index, &mut thread_rng())
+    }
+
+    /// Euclidean distance, with each differing category counting as 1.
+    fn distance(&self, a: &Vec<f64>, b: &Vec<f64>) -> Option<f64> {
+        let squares = self.variables
+                          .iter()
+                          .zip(a.iter().zip(b.iter()))
+                          .map(|(v, (x, y))| {
+                              match *v {
+                                  Variable::Categorical { .. } => if x == y { 0f64 } else { 1f64 },
+                                  _ => (x - y) * (x - y),
+                              }
+                          })
+                          .sum::<f64>();
+        Some(squares.sqrt())
+    }
+
+    fn opposite(&self, solution: &Vec<f64>) -> Vec<f64> {
+        self.variables.iter().zip(solution.iter()).map(|(v, x)| v.opposite(*x)).collect()
+    }
+
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        self.variables.iter().map(|v| v.sample(rng)).collect()
+    }
+
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Vec<f64>>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Vec<f64> {
+        let mut variant = field[index].solution.clone();
+
+        let partner = choose_other_candidate(field, index, rng);
+
+        let j = rng.gen_range(0, self.variables.len());
+        let variable = self.variables[j];
+        let x = variant[j];
+        variant[j] = match variable {
+            Variable::Categorical { count } if count > 1 => {
+                let choice = rng.gen_range(0, count - 1) as f64;
+                if choice >= x { choice + 1f64 } else { choice }
+            }
+            Variable::Categorical { .. } => x,
+            Variable::Integer { .. } if x == field[partner].solution[j] => {
+                variable.repair(if rng.gen() { x + 1f64 } else { x - 1f64 })
+            }
+            _ => {
+                let phi = rng.gen_range(-1_f64, 1_f64);
+                variable.repair(x + phi * (x - field[partner].solution[j]))
+            }
+        };
+        variant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> MixedContext<fn(&[f64]) -> f64> {
+        fn sum(x: &[f64]) -> f64 {
+            100.0 + x.iter().sum::<f64>()
+        }
+        MixedContext::new(vec![Variable::Continuous { min: -1.0, max: 1.0 },
+                               Variable::Integer { min: -3, max: 3 },
+                               Variable::Categorical { count: 4 }],
+                          sum as fn(&[f64]) -> f64)
+    }
+
+    fn is_valid(solution: &[f64]) -> bool {
+        solution[0].abs() <= 1.0 && solution[1].fract() == 0.0 && solution[1].abs() <= 3.0 &&
+        [0.0, 1.0, 2.0, 3.0].contains(&solution[2])
+    }
+
+    #[test]
+    fn solutions_stay_valid() {
+        let context = context();
+        let mut field = (0..4).map(|_| Arc::new(Candidate::new(context.make(), 1.0))).collect::<Vec<_>>();
+        for i in 0..200 {
+            let variant = context.explore(&field, i % 4);
+            assert!(is_valid(&variant));
+            field[i % 4] = Arc::new(Candidate::new(variant, 1.0));
+        }
+        assert!(is_valid(&context.opposite(&field[0].solution)));
+    }
+
+    #[test]
+    fn integers_move_when_matched() {
+        let context = context();
+        let field = (0..2).map(|_| Arc::new(Candidate::new(vec![0.0, 2.0, 1.0], 1.0))).collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        for _ in 0..200 {
+            let variant = context.explore(&field, 0);
+            if !seen.contains(&variant) {
+                seen.push(variant);
+            }
+        }
+        // The integer steps to 1 or 3, and the category to any other choice.
+        assert!(seen.contains(&vec![0.0, 1.0, 1.0]) && seen.contains(&vec![0.0, 3.0, 1.0]));
+        assert!(seen.contains(&vec![0.0, 2.0, 3.0]));
+    }
+}
@@ -0,0 +1,106 @@
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How observers weigh crowded candidates when choosing one to work on.
+///
+/// Left to themselves, observers favour the fittest candidates, and the
+/// whole colony soon gathers around a single optimum. Niching discounts
+/// candidates that have close neighbours, so that the colony can hold
+/// several optima of a multimodal function at once. It needs a context that
+/// measures [`distance`](trait.Context.html#method.distance), and has no
+/// effect otherwise.
+///
+/// Niching is applied to the weights from the
+/// [scaling function](scaling/index.html). The distances between every pair
+/// of working candidates are measured each time an observer chooses.
+pub enum Niching {
+    /// Observers consider fitness alone. This is the default.
+    #[default]
+    Off,
+
+    /// Fitness sharing, after Goldberg and Richardson.
+    ///
+    /// Each candidate's weight is divided by its niche count, the sum of
+    /// sh(*d*) = 1 − (*d* / `radius`)<sup>`alpha`</sup> over every candidate
+    /// within `radius` of it (itself included). Candidates with many close
+    /// neighbours are chosen less often.
+    Sharing {
+        /// Distance within which two candidates share a niche.
+        radius: f64,
+
+        /// Shape of the sharing function; 1 is the usual choice.
+        alpha: f64,
+    },
+
+    /// Crowding: each candidate's weight is multiplied by the distance to its
+    /// nearest neighbour, so isolated candidates are favoured.
+    Crowding,
+}
+
+impl Niching {
+    /// Adjusts `weights` given the `distances` between every pair of
+    /// candidates.
+    ///
+    /// If the adjusted weights are all zero (for example, because the
+    /// candidates have collapsed onto one point), `weights` is returned
+    /// unchanged.
+    pub fn weigh(&self, weights: Vec<f64>, distances: &[Vec<f64>]) -> Vec<f64> {
+        let niched = match *self {
+            Niching::Off => return weights,
+            Niching::Sharing { radius, alpha } => {
+                weights.iter()
+                       .zip(distances)
+                       .map(|(weight, row)| {
+                           let count = row.iter()
+                                          .filter(|&&d| d < radius)
+                                          .map(|&d| 1f64 - (d / radius).powf(alpha))
+                                          .sum::<f64>();
+                           if count > 0f64 { weight / count } else { *weight }
+                       })
+                       .collect::<Vec<_>>()
+            }
+            Niching::Crowding => {
+                weights.iter()
+                       .enumerate()
+                       .map(|(i, weight)| {
+                           let nearest = distances[i].iter()
+                                                     .enumerate()
+                                                     .filter(|&(j, _)| j != i)
+                                                     .fold(f64::INFINITY, |nearest, (_, &d)| nearest.min(d));
+                           if nearest.is_finite() { weight * nearest } else { *weight }
+                       })
+                       .collect::<Vec<_>>()
+            }
+        };
+        if niched.iter().sum::<f64>() > 0f64 {
+            niched
+        } else {
+            weights
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two candidates close together, and one far away.
+    fn distances() -> Vec<Vec<f64>> {
+        vec![vec![0.0, 0.5, 10.0], vec![0.5, 0.0, 10.0], vec![10.0, 10.0, 0.0]]
+    }
+
+    #[test]
+    fn sharing_discounts_neighbours() {
+        let niching = Niching::Sharing {
+            radius: 1.0,
+            alpha: 1.0,
+        };
+        assert_eq!(niching.weigh(vec![3.0, 3.0, 1.0], &distances()), vec![2.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn crowding_favours_isolated() {
+        assert_eq!(Niching::Crowding.weigh(vec![3.0, 3.0, 1.0], &distances()), vec![1.5, 1.5, 10.0]);
+        // A collapsed population keeps its weights.
+        assert_eq!(Niching::Crowding.weigh(vec![3.0, 1.0], &[vec![0.0, 0.0], vec![0.0, 0.0]]), vec![3.0, 1.0]);
+    }
+}
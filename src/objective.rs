@@ -0,0 +1,103 @@
+use core::cmp::Ordering;
+
+use candidate::Candidate;
+use fitness::{Fitness, TotalFitness};
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Whether the value from `evaluate_fitness` is to be maximized or minimized.
+///
+/// The hive always maximizes fitness, and its observers expect fitness to
+/// be positive. When minimizing, each value *f* returned by
+/// [`evaluate_fitness`](trait.Context.html#method.evaluate_fitness) is
+/// turned into a fitness with the canonical ABC transform:
+///
+/// <center>fitness = 1 / (1 + *f*) if *f* ≥ 0, or 1 + |*f*| otherwise</center>
+///
+/// Here *f* is the value [as an `f64`](trait.Fitness.html#tymethod.to_f64).
+/// The untransformed value is kept as each
+/// [`Candidate`](struct.Candidate.html)'s `raw_fitness`, by which the hive
+/// [compares](#method.compare) candidates.
+pub enum Objective {
+    /// Larger values are better, and are used as the fitness directly. This
+    /// is the default.
+    #[default]
+    Maximize,
+
+    /// Smaller values are better.
+    Minimize,
+}
+
+impl Objective {
+    /// Turns a value from `evaluate_fitness` into a fitness to be maximized.
+    pub fn fitness(&self, value: f64) -> f64 {
+        match *self {
+            Objective::Maximize => value,
+            Objective::Minimize if value >= 0f64 => 1f64 / (1f64 + value),
+            Objective::Minimize => 1f64 + value.abs(),
+        }
+    }
+
+    /// Recovers the value from `evaluate_fitness` that gave `fitness`.
+    ///
+    /// This is the inverse of [`fitness`](#method.fitness).
+    pub fn value(&self, fitness: f64) -> f64 {
+        match *self {
+            Objective::Maximize => fitness,
+            Objective::Minimize if fitness <= 1f64 => 1f64 / fitness - 1f64,
+            Objective::Minimize => 1f64 - fitness,
+        }
+    }
+
+    /// Orders two candidates from least to most fit.
+    ///
+    /// Candidates are compared by their `raw_fitness`, reversed when
+    /// minimizing. Candidates whose raw fitnesses aren't comparable, or that
+    /// were [resampled](struct.HiveBuilder.html#method.set_resampling), and
+    /// so are judged on a pessimistic estimate, are compared by `fitness`
+    /// instead, with NaN as the worst.
+    pub fn compare<S, F>(&self, a: &Candidate<S, F>, b: &Candidate<S, F>) -> Ordering
+        where S: Clone + Send + Sync + 'static,
+              F: Fitness
+    {
+        let raw = if a.noise.is_some() || b.noise.is_some() {
+            None
+        } else {
+            a.raw_fitness.partial_cmp(&b.raw_fitness)
+        };
+        match (raw, *self) {
+            (Some(order), Objective::Maximize) => order,
+            (Some(order), Objective::Minimize) => order.reverse(),
+            (None, _) => TotalFitness(a.fitness).cmp(&TotalFitness(b.fitness)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimize_round_trips() {
+        let objective = Objective::Minimize;
+        assert_eq!(objective.fitness(0.0), 1.0);
+        assert_eq!(objective.fitness(3.0), 0.25);
+        assert_eq!(objective.fitness(-2.0), 3.0);
+        for &value in &[-2.0, 0.0, 3.0] {
+            assert_eq!(objective.value(objective.fitness(value)), value);
+        }
+        assert!(objective.fitness(1.0) > objective.fitness(2.0));
+    }
+
+    #[test]
+    fn raw_fitness_decides() {
+        let a = Candidate::with_raw_fitness('a', 1.0, (1, 5));
+        let b = Candidate::with_raw_fitness('b', 1.0, (1, 7));
+        assert_eq!(Objective::Maximize.compare(&a, &b), Ordering::Less);
+        assert_eq!(Objective::Minimize.compare(&a, &b), Ordering::Greater);
+
+        let nan = Candidate::with_raw_fitness('n', f64::MIN, f64::NAN);
+        let low = Candidate::new('l', -1e9);
+        assert_eq!(Objective::Maximize.compare(&nan, &low), Ordering::Less);
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::mpsc::Sender;
+
+use candidate::Candidate;
+use stats::RoundStats;
+
+/// Callbacks for following a hive's progress.
+///
+/// Not to be confused with the observer bees, which choose candidates to
+/// work on: a `HiveObserver` only watches. Observers are registered with
+/// [`HiveBuilder::add_observer`](struct.HiveBuilder.html#method.add_observer),
+/// and each callback does nothing by default, so implementors only need to
+/// write the ones they care about.
+///
+/// Callbacks are made from the worker threads, one at a time, while the hive
+/// waits; they should return quickly. A callback that panics ends the run
+/// with an [`Error`](enum.Error.html).
+///
+/// A `Sender` is itself an observer, which sends each new best candidate.
+pub trait HiveObserver<S: Clone + Send + Sync + 'static, F = f64>: Send {
+    /// Called as each round ends. Returns `true` if the run should stop.
+    fn on_round_complete(&mut self, _stats: &RoundStats) -> bool {
+        false
+    }
+
+    /// Called each time the hive finds a new best candidate.
+    fn on_new_best(&mut self, _candidate: &Candidate<S, F>) {}
+
+    /// Called when the candidate at `index` is abandoned, with its
+    /// replacement.
+    fn on_scout(&mut self, _index: usize, _replacement: &Candidate<S, F>) {}
+
+    /// Called when exploration improves on the candidate at `index`, with
+    /// the improved candidate.
+    fn on_candidate_improved(&mut self, _index: usize, _candidate: &Candidate<S, F>) {}
+}
+
+impl<S, F> HiveObserver<S, F> for Sender<Candidate<S, F>>
+    where S: Clone + Send + Sync + 'static,
+          F: Clone + Send
+{
+    fn on_new_best(&mut self, candidate: &Candidate<S, F>) {
+        // A dropped receiver doesn't mean the run should end.
+        self.send(candidate.clone()).unwrap_or(());
+    }
+}
@@ -0,0 +1,162 @@
+//! Tracks the trade-offs between several objectives.
+//!
+//! Some problems have more than one goal, and no single solution is best at
+//! all of them. In that case, the interesting solutions are the
+//! *non-dominated* ones: those for which no other known solution is at least
+//! as good on every objective, and strictly better on at least one. Together,
+//! these form the Pareto front.
+//!
+//! A context opts into multi-objective optimization by implementing
+//! [`Context::evaluate_objectives`](../trait.Context.html#method.evaluate_objectives).
+//! The hive then keeps a [`ParetoArchive`](struct.ParetoArchive.html) of the
+//! non-dominated solutions it has found, available through
+//! [`Hive::pareto_front`](../struct.Hive.html#method.pareto_front). As with
+//! fitness, every objective is to be maximized.
+
+use std::f64;
+
+/// Returns `true` if objectives `a` dominate objectives `b`.
+///
+/// `a` dominates `b` if it is at least as good on every objective, and
+/// strictly better on at least one. All objectives are maximized.
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    let mut strictly_better = false;
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x < y {
+            return false;
+        }
+        if x > y {
+            strictly_better = true;
+        }
+    }
+    strictly_better
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// A non-dominated solution, with its objective values.
+pub struct ParetoMember<S> {
+    /// The solution.
+    pub solution: S,
+
+    /// The solution's score on each objective.
+    pub objectives: Vec<f64>,
+}
+
+#[derive(Clone, Debug)]
+/// A bounded set of mutually non-dominated solutions.
+///
+/// When the archive grows past its capacity, the member in the most crowded
+/// region of the front (by crowding distance) is discarded, so that the
+/// archive stays spread across the front.
+pub struct ParetoArchive<S> {
+    capacity: usize,
+    members: Vec<ParetoMember<S>>,
+}
+
+impl<S: Clone> ParetoArchive<S> {
+    /// Creates an empty archive that holds at most `capacity` members.
+    pub fn new(capacity: usize) -> ParetoArchive<S> {
+        ParetoArchive {
+            capacity,
+            members: Vec::new(),
+        }
+    }
+
+    /// Offers a solution to the archive.
+    ///
+    /// The solution is only cloned and added if no member dominates or
+    /// equals it; any members that it dominates are removed. Returns `true`
+    /// if the archive changed.
+    pub fn insert(&mut self, solution: &S, objectives: &[f64]) -> bool {
+        if self.members
+               .iter()
+               .any(|m| dominates(&m.objectives, objectives) || &m.objectives[..] == objectives) {
+            return false;
+        }
+        self.members.retain(|m| !dominates(objectives, &m.objectives));
+        self.members.push(ParetoMember {
+            solution: solution.clone(),
+            objectives: objectives.to_vec(),
+        });
+        if self.members.len() > self.capacity {
+            let crowded = self.most_crowded();
+            self.members.swap_remove(crowded);
+        }
+        true
+    }
+
+    /// Returns the members of the archive, in no particular order.
+    pub fn members(&self) -> &[ParetoMember<S>] {
+        &self.members
+    }
+
+    /// Returns the number of members in the archive.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the archive has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Finds the index of the member with the smallest crowding distance.
+    fn most_crowded(&self) -> usize {
+        let n = self.members.len();
+        let dimensions = self.members.iter().map(|m| m.objectives.len()).min().unwrap_or(0);
+        let mut distances = vec![0_f64; n];
+        let mut order = (0..n).collect::<Vec<_>>();
+
+        for d in 0..dimensions {
+            let value = |i: usize| self.members[i].objectives[d];
+            order.sort_by(|&i, &j| value(i).partial_cmp(&value(j)).unwrap_or(::std::cmp::Ordering::Equal));
+            let range = value(order[n - 1]) - value(order[0]);
+
+            // The extremes of each objective are always worth keeping.
+            distances[order[0]] = f64::INFINITY;
+            distances[order[n - 1]] = f64::INFINITY;
+            if range > 0_f64 {
+                for k in 1..n - 1 {
+                    distances[order[k]] += (value(order[k + 1]) - value(order[k - 1])) / range;
+                }
+            }
+        }
+
+        (0..n).fold(0, |crowded, i| if distances[i] < distances[crowded] { i } else { crowded })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domination() {
+        assert!(dominates(&[2.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn archive_keeps_front() {
+        let mut archive = ParetoArchive::new(3);
+        assert!(archive.insert(&'a', &[1.0, 1.0]));
+        assert!(archive.insert(&'b', &[2.0, 0.0]));
+        assert!(!archive.insert(&'c', &[0.5, 0.5]));
+        assert!(!archive.insert(&'d', &[1.0, 1.0]));
+        assert!(archive.insert(&'e', &[1.5, 1.5]));
+        let mut solutions = archive.members().iter().map(|m| m.solution).collect::<Vec<_>>();
+        solutions.sort();
+        assert_eq!(solutions, vec!['b', 'e']);
+
+        // Over capacity, the interior member of the most crowded region goes.
+        assert!(archive.insert(&'f', &[0.0, 3.0]));
+        assert!(archive.insert(&'g', &[1.6, 1.4]));
+        assert_eq!(archive.len(), 3);
+        let mut solutions = archive.members().iter().map(|m| m.solution).collect::<Vec<_>>();
+        solutions.sort();
+        assert_eq!(solutions.len(), 3);
+        assert!(solutions.contains(&'b') && solutions.contains(&'f'));
+    }
+}
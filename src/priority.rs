@@ -0,0 +1,21 @@
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Which candidates the workers visit.
+pub enum WorkerPriority {
+    /// Each worker visits its own candidate, once a round. This is the
+    /// default.
+    #[default]
+    InOrder,
+
+    /// Each worker task visits a candidate chosen by roulette wheel,
+    /// weighted towards those that have gone longest without a visit from a
+    /// worker or an observer, and those with the fewest retries left.
+    ///
+    /// When the observers crowd onto a few of the fittest candidates, the
+    /// workers then make up for it elsewhere, and candidates close to being
+    /// abandoned get another look first. A candidate's weight is
+    /// (1 + *s*)(1 + *d*), where *s* is the number of visits to the field
+    /// since its own last one, as a fraction of the field's size, and *d* is
+    /// the fraction of its retries used up.
+    Stale,
+}
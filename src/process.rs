@@ -0,0 +1,197 @@
+//! Evaluates fitness by running an external program.
+//!
+//! Plenty of fitness functions live in simulators and legacy tools that
+//! can't be called from Rust. A [`ProcessEvaluator`](struct.ProcessEvaluator.html)
+//! starts a pool of copies of such a program, and talks to them over their
+//! standard input and output, as an [`Evaluator`](../trait.Evaluator.html).
+//!
+//! The protocol is line-based: for each solution, the evaluator writes one
+//! line of JSON to the program's standard input, and reads back one line
+//! holding the fitness as a number. A program serves any number of
+//! solutions, one after another, until its input is closed. Requires the
+//! `process` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::process::ProcessEvaluator;
+//! use abc::vector::BoundedVecContext;
+//!
+//! // A "simulator" that scores each solution with the number of
+//! // characters in its JSON.
+//! let script = "while read line; do echo ${#line}; done";
+//! let evaluator = ProcessEvaluator::spawn("sh", &["-c", script], 2).unwrap();
+//! let context = BoundedVecContext::uniform(2, -5.0, 5.0, |_: &[f64]| unreachable!());
+//! let hive = HiveBuilder::new(context, 8).set_evaluator(evaluator).build().unwrap();
+//! assert!(hive.run_for_rounds(5).unwrap().fitness > 0.0);
+//! # }
+//! ```
+
+extern crate crossbeam;
+extern crate serde_json;
+
+use std::ffi::OsStr;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+use evaluator::Evaluator;
+
+/// One running copy of the program.
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Worker {
+    /// Sends each solution in turn, and reads back its fitness.
+    fn evaluate<S: Serialize>(&mut self, solutions: &[S]) -> io::Result<Vec<f64>> {
+        let mut line = String::new();
+        solutions.iter()
+                 .map(|solution| {
+                     serde_json::to_writer(&mut self.stdin, solution)?;
+                     self.stdin.write_all(b"\n")?;
+                     self.stdin.flush()?;
+
+                     line.clear();
+                     if self.stdout.read_line(&mut line)? == 0 {
+                         return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "fitness program exited"));
+                     }
+                     line.trim().parse().map_err(|_| {
+                         io::Error::new(io::ErrorKind::InvalidData,
+                                        format!("fitness program wrote {:?}, not a number", line.trim()))
+                     })
+                 })
+                 .collect()
+    }
+}
+
+/// Scores solutions with a pool of external processes.
+///
+/// Each batch is split evenly between the processes, which work on their
+/// shares in parallel. A process handles one solution at a time, and one
+/// share at a time, so a pool as large as the usual batch keeps every
+/// process busy. The processes are killed when the evaluator is dropped.
+pub struct ProcessEvaluator {
+    workers: Vec<Mutex<Worker>>,
+    next: AtomicUsize,
+}
+
+impl ProcessEvaluator {
+    /// Starts `processes` copies of `program`, with the arguments `args`.
+    ///
+    /// The programs inherit the working directory, environment and standard
+    /// error of this process.
+    pub fn spawn<P, A>(program: P, args: &[A], processes: usize) -> io::Result<ProcessEvaluator>
+        where P: AsRef<OsStr>,
+              A: AsRef<OsStr>
+    {
+        if processes == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no processes to start"));
+        }
+        let mut workers = Vec::with_capacity(processes);
+        for _ in 0..processes {
+            let mut child = Command::new(&program).args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+            let stdin = child.stdin.take().expect("stdin was piped");
+            let stdout = BufReader::new(child.stdout.take().expect("stdout was piped"));
+            workers.push(Mutex::new(Worker { child, stdin, stdout }));
+        }
+        Ok(ProcessEvaluator {
+            workers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the number of processes in the pool.
+    pub fn processes(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Asks the processes for the fitness of each solution, in order.
+    ///
+    /// Returns an error if a process can't be written to or exits, or if it
+    /// writes something other than a number.
+    pub fn evaluate_all<S: Serialize + Sync>(&self, solutions: &[S]) -> io::Result<Vec<f64>> {
+        if solutions.is_empty() {
+            return Ok(Vec::new());
+        }
+        let share = solutions.len().div_ceil(self.workers.len());
+        // Start each batch at a different process, so that small batches
+        // from several threads are spread over the pool.
+        let first = self.next.fetch_add(1, Ordering::Relaxed);
+        let results = crossbeam::scope(|scope| {
+            let handles = solutions.chunks(share)
+                                   .enumerate()
+                                   .map(|(i, chunk)| {
+                                       let mutex = &self.workers[(first + i) % self.workers.len()];
+                                       scope.spawn(move || {
+                                           let mut worker = mutex.lock()
+                                                                 .map_err(|_| io::Error::other("process lock was poisoned"))?;
+                                           worker.evaluate(chunk)
+                                       })
+                                   })
+                                   .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join()).collect::<Vec<_>>()
+        });
+        let mut fitnesses = Vec::with_capacity(solutions.len());
+        for result in results {
+            fitnesses.extend(result?);
+        }
+        Ok(fitnesses)
+    }
+}
+
+impl<S: Serialize + Sync> Evaluator<S> for ProcessEvaluator {
+    /// Panics if the processes fail, which the hive reports as an
+    /// [`Error`](../enum.Error.html) at the
+    /// [`EvaluateFitness`](../enum.Stage.html#variant.EvaluateFitness) stage.
+    fn evaluate(&self, solutions: &[S]) -> Vec<f64> {
+        match self.evaluate_all(solutions) {
+            Ok(fitnesses) => fitnesses,
+            Err(err) => panic!("process evaluation failed: {}", err),
+        }
+    }
+}
+
+impl Drop for ProcessEvaluator {
+    fn drop(&mut self) {
+        for mutex in &self.workers {
+            if let Ok(mut worker) = mutex.lock() {
+                worker.child.kill().unwrap_or(());
+                worker.child.wait().map(|_| ()).unwrap_or(());
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn doubler(processes: usize) -> ProcessEvaluator {
+        ProcessEvaluator::spawn("sh", &["-c", "while read x; do echo $((x * 2)); done"], processes).unwrap()
+    }
+
+    #[test]
+    fn scores_batches_in_order() {
+        let evaluator = doubler(3);
+        let solutions = (0..10).collect::<Vec<i32>>();
+        let expected = (0..10).map(|x| x as f64 * 2.0).collect::<Vec<_>>();
+        assert_eq!(evaluator.evaluate_all(&solutions).unwrap(), expected);
+        assert_eq!(evaluator.evaluate_all(&[21]).unwrap(), vec![42.0]);
+    }
+
+    #[test]
+    fn bad_output_is_an_error() {
+        let evaluator = ProcessEvaluator::spawn("sh", &["-c", "read x; echo fit"], 1).unwrap();
+        assert_eq!(evaluator.evaluate_all(&[1]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        // The process has exited since.
+        assert!(evaluator.evaluate_all(&[1]).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! Python bindings, with the `python` feature.
+//!
+//! Many of the people who could use ABC work in Python. This module wraps
+//! [`HiveBuilder`](../struct.HiveBuilder.html) and
+//! [`Hive`](../struct.Hive.html) for Python with [pyo3](https://pyo3.rs),
+//! as the `abc_rs` extension module. Its context is a
+//! [`PyContext`](struct.PyContext.html) of three Python callables, which
+//! can also be used from Rust, with a hive embedding Python:
+//!
+//! * `make()` returns a fresh random solution, which can be any Python
+//!   object.
+//! * `fitness(solution)` returns the solution's fitness, as a number.
+//! * `explore(field, n)` returns a variant of `field[n]`, where `field` is a
+//!   list of the working candidates' solutions.
+//!
+//! The extension module is built as a shared library, and renamed to
+//! `abc_rs.so` (or `abc_rs.pyd` on Windows) somewhere on Python's path:
+//!
+//! ```text
+//! cargo rustc --release --features python,pyo3/extension-module --crate-type cdylib
+//! ```
+//!
+//! ```python
+//! import random
+//! import abc_rs
+//!
+//! def make():
+//!     return [random.uniform(-5, 5) for _ in range(2)]
+//!
+//! def fitness(x):
+//!     return 1 / (1 + sum(xi * xi for xi in x))
+//!
+//! def explore(field, n):
+//!     x, other, i = field[n], random.choice(field), random.randrange(2)
+//!     y = list(x)
+//!     y[i] += random.uniform(-1, 1) * (x[i] - other[i])
+//!     return y
+//!
+//! hive = abc_rs.HiveBuilder(make, fitness, explore, 10).set_threads(4).build()
+//! solution, fitness = hive.run_for_rounds(100)
+//! ```
+//!
+//! # The GIL
+//!
+//! The hive lets go of the GIL while it runs, and its threads take it for
+//! each call to `make`, `fitness` or `explore`, so other Python threads,
+//! including ones that stop the hive, carry on meanwhile. Since only one
+//! thread holds the GIL at a time, more threads only help when the
+//! callables spend their time outside the interpreter, as in NumPy or I/O.
+//! When a callable raises an exception, the hive fails with
+//! [`Error::Panicked`](../enum.Error.html#variant.Panicked), which Python
+//! sees as a `RuntimeError`.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate abc;
+//! extern crate pyo3;
+//!
+//! use std::ffi::CString;
+//!
+//! use abc::HiveBuilder;
+//! use abc::python::PyContext;
+//! use pyo3::prelude::*;
+//! use pyo3::types::PyModule;
+//!
+//! const CALLBACKS: &str = "
+//! import random
+//!
+//! def make():
+//!     return random.uniform(-5, 5)
+//!
+//! def fitness(x):
+//!     return 1 / (1 + (x - 3) ** 2)
+//!
+//! def explore(field, n):
+//!     return field[n] + random.uniform(-1, 1) * (field[n] - random.choice(field))
+//! ";
+//!
+//! fn main() {
+//!     Python::initialize();
+//!     let context = Python::attach(|py| -> PyResult<PyContext> {
+//!         let code = CString::new(CALLBACKS).unwrap();
+//!         let file = CString::new("callbacks.py").unwrap();
+//!         let module = PyModule::from_code(py, &code, &file, &file)?;
+//!         Ok(PyContext::new(module.getattr("make")?.unbind(),
+//!                           module.getattr("fitness")?.unbind(),
+//!                           module.getattr("explore")?.unbind()))
+//!     }).unwrap();
+//!
+//!     let hive = HiveBuilder::new(context, 10).set_threads(2).build().unwrap();
+//!     // Let the hive's threads take the GIL.
+//!     let best = Python::attach(|py| py.detach(|| hive.run_for_rounds(50))).unwrap();
+//!     assert!(best.fitness > 0.99);
+//!     Python::attach(|py| {
+//!         let x = best.solution.bind(py).extract::<f64>().unwrap();
+//!         assert!((x - 3.0).abs() < 0.5);
+//!     });
+//! }
+//! ```
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use candidate::Candidate;
+use context::Context;
+use hive::{Hive, HiveBuilder};
+use objective::Objective;
+use result::Error;
+
+/// A Python object, as a solution.
+///
+/// Cloning shares the object, without taking the GIL.
+#[derive(Clone)]
+pub struct PySolution(Arc<Py<PyAny>>);
+
+impl PySolution {
+    /// Wraps a Python object.
+    pub fn new(object: Py<PyAny>) -> PySolution {
+        PySolution(Arc::new(object))
+    }
+
+    /// Returns the object, for use while holding the GIL.
+    pub fn bind<'py>(&self, py: Python<'py>) -> Bound<'py, PyAny> {
+        self.0.bind(py).clone()
+    }
+}
+
+impl Debug for PySolution {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Python::attach(|py| write!(f, "{}", self.0.bind(py)))
+    }
+}
+
+/// A context whose work is done by Python callables.
+///
+/// See the [module documentation](index.html) for what each callable is
+/// given, and returns. The callables are called with the GIL, which the
+/// hive's threads take in turn, so a hive must be run with the GIL
+/// released, as by [`Python::detach`](https://docs.rs/pyo3/*/pyo3/marker/struct.Python.html#method.detach).
+pub struct PyContext {
+    make: Py<PyAny>,
+    fitness: Py<PyAny>,
+    explore: Py<PyAny>,
+}
+
+impl PyContext {
+    /// Creates a context from the three callables.
+    pub fn new(make: Py<PyAny>, fitness: Py<PyAny>, explore: Py<PyAny>) -> PyContext {
+        PyContext {
+            make,
+            fitness,
+            explore,
+        }
+    }
+}
+
+/// Turns an exception into a panic, which the hive reports as an error.
+fn raise<T>(err: PyErr) -> T {
+    panic!("Python raised {}", err)
+}
+
+impl Context for PyContext {
+    type Solution = PySolution;
+    type Fitness = f64;
+
+    fn make(&self) -> PySolution {
+        Python::attach(|py| self.make.call0(py).map(PySolution::new).unwrap_or_else(raise))
+    }
+
+    fn evaluate_fitness(&self, solution: &PySolution) -> f64 {
+        Python::attach(|py| {
+            self.fitness
+                .bind(py)
+                .call1((solution.bind(py),))
+                .and_then(|fitness| fitness.extract::<f64>())
+                .unwrap_or_else(raise)
+        })
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<PySolution>>], n: usize) -> PySolution {
+        Python::attach(|py| {
+            PyList::new(py, field.iter().map(|candidate| candidate.solution.bind(py)))
+                .and_then(|field| self.explore.call1(py, (field, n)))
+                .map(PySolution::new)
+                .unwrap_or_else(raise)
+        })
+    }
+}
+
+/// Turns the hive's errors into Python's.
+fn to_py(err: Error) -> PyErr {
+    PyRuntimeError::new_err(err.to_string())
+}
+
+/// Pairs a candidate's solution with its fitness, for Python.
+fn to_pair(py: Python, candidate: &Candidate<PySolution>) -> (Py<PyAny>, f64) {
+    (candidate.solution.0.clone_ref(py), candidate.fitness)
+}
+
+/// `abc_rs.HiveBuilder`, which builds a hive from Python callables.
+///
+/// Each setter returns the builder, so that calls can be chained, as with
+/// [`HiveBuilder`](../struct.HiveBuilder.html).
+#[pyclass(name = "HiveBuilder", module = "abc_rs")]
+pub struct PyHiveBuilder {
+    // Taken when the hive is built.
+    builder: Mutex<Option<HiveBuilder<PyContext>>>,
+}
+
+impl PyHiveBuilder {
+    /// Applies a setting to the builder, unless it has already been built.
+    fn set<F>(slf: PyRef<Self>, update: F) -> PyResult<PyRef<Self>>
+        where F: FnOnce(HiveBuilder<PyContext>) -> HiveBuilder<PyContext>
+    {
+        {
+            let mut guard = slf.builder.lock().map_err(|_| PyRuntimeError::new_err("HiveBuilder is poisoned"))?;
+            let builder = guard.take().ok_or_else(|| PyRuntimeError::new_err("HiveBuilder has already been built"))?;
+            *guard = Some(update(builder));
+        }
+        Ok(slf)
+    }
+}
+
+#[pymethods]
+impl PyHiveBuilder {
+    #[new]
+    fn new(make: Py<PyAny>, fitness: Py<PyAny>, explore: Py<PyAny>, workers: usize) -> PyHiveBuilder {
+        PyHiveBuilder { builder: Mutex::new(Some(HiveBuilder::new(PyContext::new(make, fitness, explore), workers))) }
+    }
+
+    fn set_observers(slf: PyRef<Self>, observers: usize) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_observers(observers))
+    }
+
+    fn set_retries(slf: PyRef<Self>, retries: usize) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_retries(retries))
+    }
+
+    fn set_threads(slf: PyRef<Self>, threads: usize) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_threads(threads))
+    }
+
+    fn set_rng_seed(slf: PyRef<Self>, seed: u64) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_rng_seed(seed))
+    }
+
+    fn set_synchronous(slf: PyRef<Self>, synchronous: bool) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_synchronous(synchronous))
+    }
+
+    fn set_deterministic(slf: PyRef<Self>, deterministic: bool) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_deterministic(deterministic))
+    }
+
+    fn set_evaluation_budget(slf: PyRef<Self>, evaluations: usize) -> PyResult<PyRef<Self>> {
+        PyHiveBuilder::set(slf, |builder| builder.set_evaluation_budget(evaluations))
+    }
+
+    /// Takes `"maximize"`, the default, or `"minimize"`.
+    fn set_objective(slf: PyRef<Self>, objective: String) -> PyResult<PyRef<Self>> {
+        let objective = match objective.as_str() {
+            "maximize" => Objective::Maximize,
+            "minimize" => Objective::Minimize,
+            _ => return Err(PyRuntimeError::new_err("objective must be \"maximize\" or \"minimize\"")),
+        };
+        PyHiveBuilder::set(slf, |builder| builder.set_objective(objective))
+    }
+
+    /// Builds the hive, which makes and evaluates the initial candidates.
+    fn build(&self, py: Python) -> PyResult<PyHive> {
+        let builder = self.builder
+                          .lock()
+                          .map_err(|_| PyRuntimeError::new_err("HiveBuilder is poisoned"))?
+                          .take()
+                          .ok_or_else(|| PyRuntimeError::new_err("HiveBuilder has already been built"))?;
+        let hive = py.detach(|| builder.build()).map_err(to_py)?;
+        Ok(PyHive { hive })
+    }
+}
+
+/// `abc_rs.Hive`, which runs the search. Each run returns the best
+/// candidate so far, as a `(solution, fitness)` pair.
+#[pyclass(name = "Hive", module = "abc_rs")]
+pub struct PyHive {
+    hive: Hive<PyContext>,
+}
+
+#[pymethods]
+impl PyHive {
+    fn run_for_rounds(&self, py: Python, rounds: usize) -> PyResult<(Py<PyAny>, f64)> {
+        let best = py.detach(|| self.hive.run_for_rounds(rounds)).map_err(to_py)?;
+        Ok(to_pair(py, &best))
+    }
+
+    fn run_for_seconds(&self, py: Python, seconds: f64) -> PyResult<(Py<PyAny>, f64)> {
+        let duration = Duration::try_from_secs_f64(seconds).map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let best = py.detach(|| self.hive.run_for_duration(duration)).map_err(to_py)?;
+        Ok(to_pair(py, &best))
+    }
+
+    fn run_until_fitness(&self, py: Python, fitness: f64) -> PyResult<(Py<PyAny>, f64)> {
+        let best = py.detach(|| self.hive.run_until_fitness(fitness)).map_err(to_py)?;
+        Ok(to_pair(py, &best))
+    }
+
+    /// Stops a run under way on another thread.
+    fn stop(&self) -> PyResult<()> {
+        self.hive.stop().map_err(to_py)
+    }
+
+    fn best(&self, py: Python) -> PyResult<(Py<PyAny>, f64)> {
+        let best = self.hive.best_arc().map_err(to_py)?;
+        Ok(to_pair(py, &best))
+    }
+
+    fn evaluations(&self) -> usize {
+        self.hive.evaluations()
+    }
+}
+
+/// Initializes the `abc_rs` extension module.
+#[pymodule]
+#[pyo3(name = "abc_rs")]
+pub fn python_module(module: &Bound<PyModule>) -> PyResult<()> {
+    module.add_class::<PyHiveBuilder>()?;
+    module.add_class::<PyHive>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn python_drives_the_hive() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "abc_rs").unwrap();
+            python_module(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("abc_rs", module).unwrap();
+            let script = CString::new("
+import random
+import threading
+
+def make():
+    return [random.uniform(-5, 5) for _ in range(2)]
+
+def fitness(x):
+    return 1 / (1 + sum(xi * xi for xi in x))
+
+def explore(field, n):
+    x, other, i = field[n], random.choice(field), random.randrange(2)
+    y = list(x)
+    y[i] += random.uniform(-1, 1) * (x[i] - other[i])
+    return y
+
+builder = abc_rs.HiveBuilder(make, fitness, explore, 8).set_threads(3).set_retries(20)
+hive = builder.build()
+solution, best = hive.run_for_rounds(60)
+assert best > 0.9, best
+assert hive.best()[1] == best
+
+# Another Python thread can stop a run, since the run lets go of the GIL.
+threading.Timer(0.1, hive.stop).start()
+hive.run_for_seconds(60)
+
+try:
+    builder.build()
+    raise AssertionError('built twice')
+except RuntimeError:
+    pass
+
+def broken(x):
+    raise ValueError('no fitness')
+
+try:
+    abc_rs.HiveBuilder(make, broken, explore, 4).build()
+    raise AssertionError('no error')
+except RuntimeError as err:
+    assert 'no fitness' in str(err), err
+").unwrap();
+            py.run(&script, Some(&globals), None).unwrap();
+        });
+    }
+}
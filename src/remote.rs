@@ -0,0 +1,344 @@
+//! Evaluates fitness on other machines, over TCP.
+//!
+//! When fitness evaluation dominates the cost of a search, it can be farmed
+//! out to a cluster. Each worker node runs [`serve`](fn.serve.html) with the
+//! fitness function, and the coordinator wraps its context in a
+//! [`RemoteContext`](struct.RemoteContext.html), which generates and explores
+//! solutions locally but sends each one to a worker node to be scored.
+//!
+//! The wire protocol is newline-delimited JSON: the coordinator sends a
+//! [`Request`](enum.Request.html) and waits for the matching
+//! [`Response`](enum.Response.html) on the same connection. Requires the
+//! `remote` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use std::net::TcpListener;
+//! use std::thread;
+//! use abc::HiveBuilder;
+//! use abc::remote::{serve, RemoteContext, RemoteEvaluator};
+//! use abc::vector::BoundedVecContext;
+//!
+//! let fitness = |x: &[f64]| 1.0 / (1.0 + x.iter().map(|xi| xi * xi).sum::<f64>());
+//!
+//! // A worker node.
+//! let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+//! let address = listener.local_addr().unwrap();
+//! thread::spawn(move || serve(listener, move |x: &Vec<f64>| fitness(x)));
+//!
+//! // The coordinator.
+//! let evaluator = RemoteEvaluator::connect(&[address], 2).unwrap();
+//! let context = RemoteContext::new(BoundedVecContext::uniform(2, -5.0, 5.0, fitness), evaluator);
+//! let hive = HiveBuilder::new(context, 8).set_threads(2).build().unwrap();
+//! assert!(hive.run_for_rounds(5).unwrap().fitness > 0.0);
+//! # }
+//! ```
+
+extern crate rand;
+extern crate serde_json;
+
+use self::rand::Rng;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use candidate::Candidate;
+use context::{Context, Scratch};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Message from the coordinator to a worker node.
+pub enum Request<S> {
+    /// Asks for a solution's fitness.
+    Evaluate {
+        /// Identifies the request, to be echoed in the response.
+        id: u64,
+
+        /// The solution to score.
+        solution: S,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Message from a worker node back to the coordinator.
+pub enum Response {
+    /// The fitness of the requested solution.
+    Fitness {
+        /// The request's `id`.
+        id: u64,
+
+        /// The solution's fitness.
+        fitness: f64,
+    },
+
+    /// The fitness function panicked.
+    Failed {
+        /// The request's `id`.
+        id: u64,
+
+        /// The panic message, if any.
+        message: Option<String>,
+    },
+}
+
+/// Writes a message as one line of JSON.
+fn send<T: Serialize, W: Write>(writer: &mut W, message: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+/// Reads a message from one line of JSON.
+fn receive<T: DeserializeOwned, R: BufRead>(reader: &mut R) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line).map(Some).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Answers evaluation requests on `listener`, forever.
+///
+/// Each connection is served on its own thread, so one worker node can serve
+/// several coordinator threads (or coordinators) at once. A panic in
+/// `fitness` is reported back to the coordinator rather than ending the
+/// connection. This returns only if the listener fails.
+pub fn serve<S, F>(listener: TcpListener, fitness: F) -> io::Result<()>
+    where S: DeserializeOwned,
+          F: Fn(&S) -> f64 + Send + Sync + Clone + 'static
+{
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let fitness = fitness.clone();
+        thread::spawn(move || serve_connection(stream, fitness));
+    }
+    Ok(())
+}
+
+fn serve_connection<S, F>(stream: TcpStream, fitness: F) -> io::Result<()>
+    where S: DeserializeOwned,
+          F: Fn(&S) -> f64
+{
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = receive(&mut reader)? {
+        let response = match request {
+            Request::Evaluate { id, solution } => {
+                match ::result::catch_panic(::result::Stage::EvaluateFitness, None, || fitness(&solution)) {
+                    Ok(fitness) => Response::Fitness { id, fitness },
+                    Err(::result::Error::Panicked { message, .. }) => Response::Failed { id, message },
+                    Err(_) => Response::Failed { id, message: None },
+                }
+            }
+        };
+        send(&mut writer, &response)?;
+    }
+    Ok(())
+}
+
+/// An open connection to a worker node.
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+/// Sends solutions to worker nodes to be scored.
+///
+/// The evaluator keeps a pool of connections, and hands each request to the
+/// next connection in turn. A connection carries one request at a time, so
+/// there should be at least as many connections as the hive has threads.
+pub struct RemoteEvaluator {
+    connections: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+impl RemoteEvaluator {
+    /// Opens `per_node` connections to each of the worker nodes at `nodes`.
+    pub fn connect<A: ToSocketAddrs>(nodes: &[A], per_node: usize) -> io::Result<RemoteEvaluator> {
+        let mut connections = Vec::with_capacity(nodes.len() * per_node);
+        for node in nodes {
+            for _ in 0..per_node {
+                let writer = TcpStream::connect(node)?;
+                writer.set_nodelay(true)?;
+                let reader = BufReader::new(writer.try_clone()?);
+                connections.push(Mutex::new(Connection { reader, writer }));
+            }
+        }
+        if connections.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no worker nodes to connect to"));
+        }
+        Ok(RemoteEvaluator {
+            connections,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Asks a worker node for the fitness of `solution`.
+    ///
+    /// Returns an error if the connection fails, or if the worker node's
+    /// fitness function panicked.
+    pub fn evaluate<S: Serialize>(&self, solution: &S) -> io::Result<f64> {
+        let id = self.next.fetch_add(1, Ordering::Relaxed);
+        let mutex = &self.connections[id % self.connections.len()];
+        let mut connection = mutex.lock().map_err(|_| io::Error::other("connection lock was poisoned"))?;
+        let id = id as u64;
+
+        send(&mut connection.writer, &Request::Evaluate { id, solution })?;
+        match receive(&mut connection.reader)? {
+            Some(Response::Fitness { id: reply, fitness }) if reply == id => Ok(fitness),
+            Some(Response::Failed { id: reply, message }) if reply == id => {
+                Err(io::Error::other(message.unwrap_or_else(|| "remote fitness function panicked".to_string())))
+            }
+            Some(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "response did not match the request")),
+            None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "worker node closed the connection")),
+        }
+    }
+}
+
+/// Wraps a context, so that fitness is evaluated by worker nodes.
+///
+/// Solutions are still generated and explored by the wrapped context, as are
+/// the objectives of a multi-objective context. If remote evaluation fails,
+/// `evaluate_fitness` panics, which the hive reports as an
+/// [`Error`](../enum.Error.html) at the
+/// [`EvaluateFitness`](../enum.Stage.html#variant.EvaluateFitness) stage.
+/// Worker nodes send back `f64` fitnesses, so the wrapped context's
+/// fitness must be an `f64` too.
+pub struct RemoteContext<Ctx> {
+    context: Ctx,
+    evaluator: RemoteEvaluator,
+}
+
+impl<Ctx: Context> RemoteContext<Ctx> {
+    /// Evaluates the solutions of `context` with `evaluator`.
+    pub fn new(context: Ctx, evaluator: RemoteEvaluator) -> RemoteContext<Ctx> {
+        RemoteContext { context, evaluator }
+    }
+
+    /// Get a reference to the wrapped context.
+    pub fn context(&self) -> &Ctx {
+        &self.context
+    }
+}
+
+impl<Ctx> Context for RemoteContext<Ctx>
+    where Ctx: Context<Fitness = f64>,
+          Ctx::Solution: Serialize
+{
+    type Solution = Ctx::Solution;
+    type Fitness = f64;
+
+    fn make(&self) -> Ctx::Solution {
+        self.context.make()
+    }
+
+    fn evaluate_fitness(&self, solution: &Ctx::Solution) -> f64 {
+        match self.evaluator.evaluate(solution) {
+            Ok(fitness) => fitness,
+            Err(err) => panic!("remote evaluation failed: {}", err),
+        }
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<Ctx::Solution>>], index: usize) -> Ctx::Solution {
+        self.context.explore(field, index)
+    }
+
+    // `evaluate` and `evaluate_with_scratch` are left to their defaults, so
+    // that the fitness comes from the worker nodes.
+
+    fn evaluate_objectives(&self, solution: &Ctx::Solution) -> Option<Vec<f64>> {
+        self.context.evaluate_objectives(solution)
+    }
+
+    fn distance(&self, a: &Ctx::Solution, b: &Ctx::Solution) -> Option<f64> {
+        self.context.distance(a, b)
+    }
+
+    fn opposite(&self, solution: &Ctx::Solution) -> Ctx::Solution {
+        self.context.opposite(solution)
+    }
+
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Ctx::Solution {
+        self.context.make_with_rng(rng)
+    }
+
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Ctx::Solution>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Ctx::Solution {
+        self.context.explore_with_rng(field, index, rng)
+    }
+
+    fn explore_guided<R: Rng>(&self,
+                              field: &[Arc<Candidate<Ctx::Solution>>],
+                              index: usize,
+                              best: &Candidate<Ctx::Solution>,
+                              rng: &mut R)
+                              -> Ctx::Solution {
+        self.context.explore_guided(field, index, best, rng)
+    }
+
+    fn partners(&self) -> Option<usize> {
+        self.context.partners()
+    }
+
+    fn on_start(&self) {
+        self.context.on_start()
+    }
+
+    fn on_stop(&self) {
+        self.context.on_stop()
+    }
+
+    fn init_scratch(&self) -> Scratch {
+        self.context.init_scratch()
+    }
+
+    fn explore_with_scratch<R: Rng>(&self,
+                                    field: &[Arc<Candidate<Ctx::Solution>>],
+                                    index: usize,
+                                    best: &Candidate<Ctx::Solution>,
+                                    rng: &mut R,
+                                    scratch: &mut Scratch)
+                                    -> Ctx::Solution {
+        self.context.explore_with_scratch(field, index, best, rng, scratch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worker<F>(fitness: F) -> ::std::net::SocketAddr
+        where F: Fn(&i32) -> f64 + Send + Sync + Clone + 'static
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, fitness));
+        address
+    }
+
+    #[test]
+    fn round_trip() {
+        let evaluator = RemoteEvaluator::connect(&[worker(|x: &i32| *x as f64 * 2.0)], 2).unwrap();
+        assert_eq!(evaluator.evaluate(&21).unwrap(), 42.0);
+        assert_eq!(evaluator.evaluate(&-1).unwrap(), -2.0);
+    }
+
+    #[test]
+    fn remote_panics_are_errors() {
+        let evaluator = RemoteEvaluator::connect(&[worker(|_: &i32| panic!("no"))], 1).unwrap();
+        let err = evaluator.evaluate(&1).unwrap_err();
+        assert_eq!(err.to_string(), "no");
+        // The connection survives the panic.
+        assert_eq!(evaluator.evaluate(&2).unwrap_err().to_string(), "no");
+    }
+}
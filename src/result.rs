@@ -1,44 +1,374 @@
 use std::result;
-use std::sync::PoisonError;
+use std::time::Duration;
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::any::Any;
 use std::fmt;
 use std::error;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Identifies the part of the hive's operation that failed.
+pub enum Stage {
+    /// Generating a fresh solution with `Context::make`.
+    Make,
+
+    /// Generating a variant of a solution with `Context::explore`.
+    Explore,
+
+    /// Scoring a solution with `Context::evaluate_fitness`.
+    EvaluateFitness,
+
+    /// Reading or replacing one of the working candidates.
+    Candidate,
+
+    /// Reading or replacing the best candidate found so far.
+    Best,
+
+    /// Claiming the next task from the task generator.
+    Tasks,
+
+    /// Tracking which candidates are in the middle of being scouted.
+    ///
+    /// No longer used, since each candidate now carries an atomic flag,
+    /// which can't fail.
+    Scouting,
+
+    /// Sending an improved candidate to a stream.
+    Stream,
+
+    /// Seeding the worker threads' random number generators.
+    Rng,
+
+    /// Measuring the distance between solutions with `Context::distance`.
+    Distance,
+
+    /// Checking whether the run should stop.
+    Stopping,
+
+    /// Updating the archive of non-dominated solutions.
+    Pareto,
+
+    /// Updating the archive of the fittest candidates.
+    Elites,
+
+    /// Recording the statistics for a round.
+    Stats,
+
+    /// Calling back a user-supplied `HiveObserver`.
+    HiveObserver,
+
+    /// Generating a replacement for an abandoned candidate with a `ScoutStrategy`.
+    ScoutStrategy,
+
+    /// Generating the initial population with an `InitStrategy`.
+    InitStrategy,
+
+    /// Running `Context::opposite`.
+    Opposite,
+
+    /// Refining a candidate with a `LocalSearch`.
+    LocalSearch,
+
+    /// Reading the context, or updating it with a round-end hook.
+    Context,
+
+    /// Checking the builder's settings when the hive is built.
+    Config,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Stage::Make => "Context::make",
+            Stage::Explore => "Context::explore",
+            Stage::EvaluateFitness => "Context::evaluate_fitness",
+            Stage::Candidate => "working candidate",
+            Stage::Best => "best candidate",
+            Stage::Tasks => "task generator",
+            Stage::Scouting => "scouting set",
+            Stage::Stream => "stream sender",
+            Stage::Rng => "random number generator",
+            Stage::Distance => "Context::distance",
+            Stage::Stopping => "stopping rule",
+            Stage::Pareto => "Pareto archive",
+            Stage::Elites => "elite archive",
+            Stage::Stats => "round statistics",
+            Stage::HiveObserver => "HiveObserver callback",
+            Stage::ScoutStrategy => "ScoutStrategy::scout",
+            Stage::InitStrategy => "InitStrategy::initialize",
+            Stage::Opposite => "Context::opposite",
+            Stage::LocalSearch => "LocalSearch::refine",
+            Stage::Context => "context",
+            Stage::Config => "hive configuration",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Debug)]
 /// Unifies the errors thrown by a hive's operation.
 ///
-/// The only errors expected within the hive code are associated with getting
-/// read and/or write locks on aspects of the hive's data. These errors occur
-/// if a thread panics while holding the lock -- a situation that we do not
-/// particularly expect.
+/// There are two ways for a running hive to fail. Either one of the
+/// `Context` methods panics while a worker thread is running it, or a thread
+/// panics while holding a lock on some of the hive's data, poisoning that
+/// lock. Since the `Context` methods are run outside of any lock, the latter
+/// is a situation that we do not particularly expect. Before that, building
+/// a hive fails if its settings make no sense.
 ///
-/// Nevertheless, this `Error` represents a panic, most likely in one of the
-/// hive's worker threads.
-pub struct Error;
+/// Both of the first two variants record the [`Stage`](enum.Stage.html) that failed and, if
+/// the failure concerned a particular candidate, that candidate's index.
+pub enum Error {
+    /// A user-supplied `Context` method panicked.
+    Panicked {
+        /// The `Context` method that panicked.
+        stage: Stage,
+
+        /// Index of the candidate being generated or explored, if any.
+        index: Option<usize>,
+
+        /// The panic message, if the payload was a string.
+        message: Option<String>,
+    },
+
+    /// A lock was poisoned by a thread that panicked while holding it.
+    Poisoned {
+        /// The data protected by the poisoned lock.
+        stage: Stage,
+
+        /// Index of the candidate protected by the lock, if any.
+        index: Option<usize>,
+    },
 
-impl error::Error for Error {
-    fn description(&self) -> &str {
-        "One of the hive's workers panicked."
+    /// A fitness was NaN or infinite, or an observer's weight was NaN,
+    /// infinite or negative. See [`FitnessPolicy`](enum.FitnessPolicy.html).
+    InvalidFitness {
+        /// Index of the candidate with the invalid value, if known.
+        index: Option<usize>,
+
+        /// The invalid fitness or weight.
+        value: f64,
+    },
+
+    /// A fitness evaluation outlasted the
+    /// [evaluation timeout](struct.HiveBuilder.html#method.set_evaluation_timeout).
+    TimedOut {
+        /// Index of the candidate being evaluated, if any.
+        index: Option<usize>,
+
+        /// The timeout that was exceeded.
+        limit: Duration,
+    },
+
+    /// The builder's settings make no sense, such as a hive with no workers.
+    InvalidConfig {
+        /// What is wrong with the settings.
+        description: String,
+    },
+}
+
+impl Error {
+    /// Returns the stage of the hive's operation that failed.
+    pub fn stage(&self) -> Stage {
+        match *self {
+            Error::Panicked { stage, .. } |
+            Error::Poisoned { stage, .. } => stage,
+            Error::InvalidFitness { .. } |
+            Error::TimedOut { .. } => Stage::EvaluateFitness,
+            Error::InvalidConfig { .. } => Stage::Config,
+        }
+    }
+
+    /// Returns the index of the candidate involved in the failure, if known.
+    pub fn index(&self) -> Option<usize> {
+        match *self {
+            Error::Panicked { index, .. } |
+            Error::Poisoned { index, .. } |
+            Error::InvalidFitness { index, .. } |
+            Error::TimedOut { index, .. } => index,
+            Error::InvalidConfig { .. } => None,
+        }
     }
 
-    fn cause(&self) -> Option<&error::Error> {
-        None
+    fn from_panic(stage: Stage, index: Option<usize>, payload: Box<dyn Any + Send>) -> Error {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => Some(*message),
+            Err(payload) => payload.downcast_ref::<&str>().map(|message| message.to_string()),
+        };
+        Error::Panicked {
+            stage,
+            index,
+            message,
+        }
     }
 }
 
+impl error::Error for Error {}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "One of the hive's workers panicked.")
+        match *self {
+            Error::Panicked { stage, index, ref message } => {
+                write!(f, "{} panicked", stage)?;
+                if let Some(index) = index {
+                    write!(f, " on candidate {}", index)?;
+                }
+                match *message {
+                    Some(ref message) => write!(f, ": {}", message),
+                    None => Ok(()),
+                }
+            }
+            Error::Poisoned { stage, index } => {
+                write!(f, "lock on the {}", stage)?;
+                if let Some(index) = index {
+                    write!(f, " {}", index)?;
+                }
+                write!(f, " was poisoned by a panicking thread")
+            }
+            Error::InvalidFitness { index, value } => {
+                write!(f, "invalid fitness {}", value)?;
+                match index {
+                    Some(index) => write!(f, " for candidate {}", index),
+                    None => Ok(()),
+                }
+            }
+            Error::TimedOut { index, limit } => {
+                write!(f, "{} timed out after {:?}", Stage::EvaluateFitness, limit)?;
+                match index {
+                    Some(index) => write!(f, " on candidate {}", index),
+                    None => Ok(()),
+                }
+            }
+            Error::InvalidConfig { ref description } => write!(f, "invalid configuration: {}", description),
+        }
     }
 }
 
-// Each type of guard (mutex, read, and write) has its own parameterized form
-// of the PoisonError. Since they all amount to the same thing for our purposes,
-// we abstract over them with T.
-impl<T> From<PoisonError<T>> for Error {
-    fn from(_: PoisonError<T>) -> Error {
-        Error
+/// Attaches a [`Stage`](enum.Stage.html) to the error from a poisoned lock.
+///
+/// Each type of guard (mutex, read, and write) has its own parameterized form
+/// of the PoisonError. Since they all amount to the same thing for our
+/// purposes, we abstract over them with G.
+pub trait LockResultExt<G> {
+    /// Records that the lock protects data for `stage`.
+    fn at(self, stage: Stage) -> Result<G>;
+
+    /// Records that the lock protects data for `stage` and candidate `index`.
+    fn at_index(self, stage: Stage, index: usize) -> Result<G>;
+}
+
+impl<G> LockResultExt<G> for result::Result<G, PoisonError<G>> {
+    fn at(self, stage: Stage) -> Result<G> {
+        self.map_err(|_| {
+            Error::Poisoned {
+                stage,
+                index: None,
+            }
+        })
+    }
+
+    fn at_index(self, stage: Stage, index: usize) -> Result<G> {
+        self.map_err(|_| {
+            Error::Poisoned {
+                stage,
+                index: Some(index),
+            }
+        })
+    }
+}
+
+/// Takes a mutex for `stage`, attaching the stage to any poisoning error.
+///
+/// With the `contention` feature, the wait is also timed; see the
+/// [`contention`](../contention/index.html) module.
+pub trait MutexExt<T: ?Sized> {
+    /// Locks the mutex, which protects data for `stage`.
+    fn lock_at(&self, stage: Stage) -> Result<MutexGuard<'_, T>>;
+
+    /// Locks the mutex, which protects data for `stage` and candidate `index`.
+    fn lock_at_index(&self, stage: Stage, index: usize) -> Result<MutexGuard<'_, T>>;
+}
+
+impl<T: ?Sized> MutexExt<T> for Mutex<T> {
+    fn lock_at(&self, stage: Stage) -> Result<MutexGuard<'_, T>> {
+        timed(stage, || self.lock()).at(stage)
+    }
+
+    fn lock_at_index(&self, stage: Stage, index: usize) -> Result<MutexGuard<'_, T>> {
+        timed(stage, || self.lock()).at_index(stage, index)
+    }
+}
+
+/// Takes a read-write lock for `stage`, as `MutexExt` takes a mutex.
+pub trait RwLockExt<T: ?Sized> {
+    /// Locks for reading data for `stage`.
+    fn read_at(&self, stage: Stage) -> Result<RwLockReadGuard<'_, T>>;
+
+    /// Locks for reading data for `stage` and candidate `index`.
+    fn read_at_index(&self, stage: Stage, index: usize) -> Result<RwLockReadGuard<'_, T>>;
+
+    /// Locks for writing data for `stage`.
+    fn write_at(&self, stage: Stage) -> Result<RwLockWriteGuard<'_, T>>;
+
+    /// Locks for writing data for `stage` and candidate `index`.
+    fn write_at_index(&self, stage: Stage, index: usize) -> Result<RwLockWriteGuard<'_, T>>;
+}
+
+impl<T: ?Sized> RwLockExt<T> for RwLock<T> {
+    fn read_at(&self, stage: Stage) -> Result<RwLockReadGuard<'_, T>> {
+        timed(stage, || self.read()).at(stage)
+    }
+
+    fn read_at_index(&self, stage: Stage, index: usize) -> Result<RwLockReadGuard<'_, T>> {
+        timed(stage, || self.read()).at_index(stage, index)
+    }
+
+    fn write_at(&self, stage: Stage) -> Result<RwLockWriteGuard<'_, T>> {
+        timed(stage, || self.write()).at(stage)
+    }
+
+    fn write_at_index(&self, stage: Stage, index: usize) -> Result<RwLockWriteGuard<'_, T>> {
+        timed(stage, || self.write()).at_index(stage, index)
     }
 }
 
-/// Encodes the possibility of a thread panicking and corruping a mutex.
+#[cfg(feature = "contention")]
+use contention::timed;
+
+/// Without the `contention` feature, locks aren't timed.
+#[cfg(not(feature = "contention"))]
+#[inline(always)]
+fn timed<T, F: FnOnce() -> T>(_: Stage, acquire: F) -> T {
+    acquire()
+}
+
+/// Runs a user-supplied `Context` method, converting a panic into an `Error`.
+pub fn catch_panic<T, F>(stage: Stage, index: Option<usize>, f: F) -> Result<T>
+    where F: FnOnce() -> T
+{
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| Error::from_panic(stage, index, payload))
+}
+
+/// Describes the panic of a thread that has been joined.
+pub fn thread_panic(stage: Stage, index: Option<usize>, payload: Box<dyn Any + Send>) -> Error {
+    Error::from_panic(stage, index, payload)
+}
+
+/// Awaits a future from a user-supplied `AsyncContext`, converting a panic
+/// into an `Error`.
+#[cfg(feature = "async")]
+pub fn catch_future_panic<'a, T, F>(stage: Stage,
+                                    index: Option<usize>,
+                                    future: F)
+                                    -> ::futures::future::BoxFuture<'a, Result<T>>
+    where F: ::futures::Future<Output = T> + Send + 'a
+{
+    use futures::FutureExt;
+    AssertUnwindSafe(future)
+        .catch_unwind()
+        .map(move |result| result.map_err(|payload| Error::from_panic(stage, index, payload)))
+        .boxed()
+}
+
+/// Encodes the possibility of a panic in the hive or in one of its workers.
 pub type Result<T> = result::Result<T, Error>;
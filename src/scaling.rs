@@ -32,9 +32,65 @@
 //! the fitnesses vector, you can mutate and return the same vector. Since the
 //! actual storage portion of a `Vec` is is heap-allocated, the scaling function
 //! should be reasonably well-behaved with respect to memory.
+//!
+//! Scaling can also change as the search goes on. Anything implementing
+//! [`ScalingFn`](trait.ScalingFn.html) is told the current round, so that,
+//! for example, selection pressure can increase over time. Closures that
+//! take the round as well as the fitnesses can be wrapped in
+//! [`Scheduled`](struct.Scheduled.html):
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::scaling::Scheduled;
+//!
+//! // Raise the fitnesses to a power that grows with the round.
+//! Scheduled(|round: usize, fitnesses: Vec<f64>| {
+//!     let k = 1_f64 + round as f64 / 100_f64;
+//!     fitnesses.iter().map(|fitness| fitness.powf(k)).collect::<Vec<_>>()
+//! });
+//! # }
+//! ```
+
+use fitness::TotalFitness;
 
 /// Transform a set of fitnesses into weights for observers' random choices.
-pub type ScalingFunction = Fn(Vec<f64>) -> Vec<f64> + Send + Sync + 'static;
+pub type ScalingFunction = dyn Fn(Vec<f64>) -> Vec<f64> + Send + Sync + 'static;
+
+/// Transforms fitnesses into weights, with knowledge of the round.
+///
+/// This is implemented for every `Fn(Vec<f64>) -> Vec<f64>`, including
+/// [`ScalingFunction`](type.ScalingFunction.html), which ignore the round.
+pub trait ScalingFn: Send + Sync {
+    /// Scales `fitnesses` during `round`, counting from 0 over the hive's
+    /// lifetime.
+    fn scale(&self, round: usize, fitnesses: Vec<f64>) -> Vec<f64>;
+}
+
+impl<F> ScalingFn for F
+    where F: Fn(Vec<f64>) -> Vec<f64> + Send + Sync + ?Sized
+{
+    fn scale(&self, _round: usize, fitnesses: Vec<f64>) -> Vec<f64> {
+        self(fitnesses)
+    }
+}
+
+impl ScalingFn for Box<dyn ScalingFn> {
+    fn scale(&self, round: usize, fitnesses: Vec<f64>) -> Vec<f64> {
+        (**self).scale(round, fitnesses)
+    }
+}
+
+/// Scaling by a closure that takes the round, then the fitnesses.
+#[derive(Clone, Copy, Debug)]
+pub struct Scheduled<F>(pub F);
+
+impl<F> ScalingFn for Scheduled<F>
+    where F: Fn(usize, Vec<f64>) -> Vec<f64> + Send + Sync
+{
+    fn scale(&self, round: usize, fitnesses: Vec<f64>) -> Vec<f64> {
+        (self.0)(round, fitnesses)
+    }
+}
 
 /// Chooses solutions in direct proportion to their fitness.
 ///
@@ -88,7 +144,7 @@ pub fn power_rank(k: f64) -> Box<ScalingFunction> {
         let mut with_indices = fitnesses.iter().enumerate().collect::<Vec<_>>();
 
         // Sort by fitness, ascending. After this, we can ignore fitness.
-        with_indices.sort_by(|&(_, f1), &(_, f2)| f1.partial_cmp(f2).unwrap());
+        with_indices.sort_by_key(|&(_, &f)| TotalFitness(f));
 
         // The rank of solution i now corresponds to the index in with_indices
         // of (i, fitness_i). But we want the original index to be the index,
@@ -103,3 +159,172 @@ pub fn power_rank(k: f64) -> Box<ScalingFunction> {
         ranks
     })
 }
+
+/// Chooses solutions according to a Boltzmann distribution.
+///
+/// scaled<sub>*i*</sub> = exp(fitness<sub>*i*</sub> / *T*)
+///
+/// The temperature *T* sets how strongly fitter solutions are favoured: at
+/// high temperatures, the choice is nearly uniform, and as *T* approaches
+/// zero, the fittest solution is almost always chosen. Unlike
+/// [proportionate](fn.proportionate.html) scaling, this depends only on
+/// differences in fitness, so it can tell apart solutions whose fitnesses
+/// are large but close together. The fitnesses are shifted by the largest
+/// before exponentiating, which leaves the probabilities unchanged but
+/// avoids overflow.
+pub fn boltzmann(temperature: f64) -> Box<ScalingFunction> {
+    Box::new(move |fitnesses: Vec<f64>| boltzmann_weights(fitnesses, temperature))
+}
+
+/// Chooses solutions by how far they stand above the crowd.
+///
+/// This is Goldberg's sigma truncation:
+///
+/// scaled<sub>*i*</sub> = max(fitness<sub>*i*</sub> − (μ − *c*σ), 0)
+///
+/// where μ and σ are the mean and standard deviation of the fitnesses.
+/// Solutions more than *c* standard deviations below the mean are never
+/// chosen. Because the baseline moves with the population, selection
+/// pressure stays meaningful even when every fitness is close to the same
+/// large value, which proportionate scaling handles poorly. A *c* between 1
+/// and 3 is typical. If every fitness is the same, each solution is equally
+/// likely to be chosen.
+pub fn sigma(c: f64) -> Box<ScalingFunction> {
+    Box::new(move |mut fitnesses: Vec<f64>| {
+        let n = fitnesses.len() as f64;
+        let mean = fitnesses.iter().sum::<f64>() / n;
+        let std_dev = (fitnesses.iter().map(|f| (f - mean) * (f - mean)).sum::<f64>() / n).sqrt();
+        let baseline = mean - c * std_dev;
+        for f in &mut fitnesses {
+            *f = if std_dev > 0_f64 { (*f - baseline).max(0_f64) } else { 1_f64 };
+        }
+        fitnesses
+    })
+}
+
+/// Boltzmann scaling, with a temperature that cools as the rounds go by.
+///
+/// *T*<sub>*r*</sub> = `initial` × `cooling`<sup>*r*</sup>
+///
+/// With `cooling` below 1, observers explore broadly at first, and settle
+/// on the fittest solutions as the search matures. See
+/// [boltzmann](fn.boltzmann.html).
+pub fn annealed_boltzmann(initial: f64, cooling: f64) -> Box<dyn ScalingFn> {
+    Box::new(Scheduled(move |round: usize, fitnesses: Vec<f64>| {
+        let temperature = initial * cooling.powf(round as f64);
+        boltzmann_weights(fitnesses, temperature.max(f64::MIN_POSITIVE))
+    }))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Names one of the scaling functions in this module, with its parameters.
+///
+/// Unlike the functions themselves, this is plain data, so it can be part of
+/// a [`HiveConfig`](../struct.HiveConfig.html) loaded from a file.
+pub enum Scaling {
+    /// [Proportionate](fn.proportionate.html) scaling. This is the default.
+    #[default]
+    Proportionate,
+
+    /// [Power](fn.power.html) scaling.
+    Power {
+        /// The exponent.
+        k: f64,
+    },
+
+    /// [Rank](fn.rank.html) scaling.
+    Rank,
+
+    /// [Power rank](fn.power_rank.html) scaling.
+    PowerRank {
+        /// The exponent.
+        k: f64,
+    },
+
+    /// [Boltzmann](fn.boltzmann.html) scaling.
+    Boltzmann {
+        /// The temperature.
+        temperature: f64,
+    },
+
+    /// [Sigma](fn.sigma.html) truncation.
+    Sigma {
+        /// Standard deviations below the mean at which weights reach 0.
+        c: f64,
+    },
+
+    /// [Annealed Boltzmann](fn.annealed_boltzmann.html) scaling.
+    AnnealedBoltzmann {
+        /// The temperature in round 0.
+        initial: f64,
+
+        /// The factor by which the temperature falls each round.
+        cooling: f64,
+    },
+}
+
+impl Scaling {
+    /// Returns the scaling function.
+    pub fn build(&self) -> Box<dyn ScalingFn> {
+        match *self {
+            Scaling::Proportionate => Box::new(proportionate()),
+            Scaling::Power { k } => Box::new(power(k)),
+            Scaling::Rank => Box::new(rank()),
+            Scaling::PowerRank { k } => Box::new(power_rank(k)),
+            Scaling::Boltzmann { temperature } => Box::new(boltzmann(temperature)),
+            Scaling::Sigma { c } => Box::new(sigma(c)),
+            Scaling::AnnealedBoltzmann { initial, cooling } => annealed_boltzmann(initial, cooling),
+        }
+    }
+}
+
+fn boltzmann_weights(mut fitnesses: Vec<f64>, temperature: f64) -> Vec<f64> {
+    let max = fitnesses.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    for f in &mut fitnesses {
+        *f = ((*f - max) / temperature).exp();
+    }
+    fitnesses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sigma_truncates() {
+        // Mean 5, standard deviation 2, so the baseline is 3.
+        let scaled = sigma(1_f64)(vec![2_f64, 4_f64, 4_f64, 4_f64, 5_f64, 5_f64, 7_f64, 9_f64]);
+        assert_eq!(scaled, vec![0_f64, 1_f64, 1_f64, 1_f64, 2_f64, 2_f64, 4_f64, 6_f64]);
+        assert_eq!(sigma(2_f64)(vec![3_f64, 3_f64]), vec![1_f64, 1_f64]);
+    }
+
+    #[test]
+    fn boltzmann_depends_on_differences() {
+        let near = boltzmann(1_f64)(vec![1000_f64, 1001_f64]);
+        let far = boltzmann(1_f64)(vec![0_f64, 1_f64]);
+        assert_eq!(near, far);
+        assert_eq!(near[1], 1_f64);
+        assert!((near[0] - (-1_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn annealing_sharpens() {
+        let scale = annealed_boltzmann(1_f64, 0.5);
+        let early = scale.scale(0, vec![0_f64, 1_f64]);
+        let late = scale.scale(10, vec![0_f64, 1_f64]);
+        assert!(late[0] < early[0]);
+        assert_eq!(scale.scale(100_000, vec![0_f64, 1_f64]), vec![0_f64, 1_f64]);
+    }
+
+    #[test]
+    fn plain_functions_ignore_round() {
+        let scale: Box<dyn ScalingFn> = Box::new(power(2_f64));
+        assert_eq!(scale.scale(7, vec![1_f64, 3_f64]), vec![1_f64, 9_f64]);
+    }
+
+    #[test]
+    fn power_rank_ranks_nan_last() {
+        assert_eq!(power_rank(1_f64)(vec![2_f64, f64::NAN, 1_f64]), vec![3_f64, 1_f64, 2_f64]);
+    }
+}
@@ -0,0 +1,305 @@
+//! Decides where abandoned candidates start over.
+//!
+//! When a candidate runs out of retries, a scout replaces it. By default,
+//! the replacement is a fresh random solution from
+//! [`Context::make_with_rng`](../trait.Context.html#method.make_with_rng),
+//! but a [`ScoutStrategy`](trait.ScoutStrategy.html) set with
+//! [`HiveBuilder::set_scouting`](../struct.HiveBuilder.html#method.set_scouting)
+//! can choose differently. Random restarts keep the search broad, while the
+//! other strategies in this module trade some of that breadth for a faster
+//! return to promising regions.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::scout::Opposition;
+//! use abc::vector::BoundedVecContext;
+//!
+//! let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| {
+//!     1.0 / (1.0 + x.iter().map(|xi| xi * xi).sum::<f64>())
+//! });
+//! let hive = HiveBuilder::new(context, 10)
+//!     .set_scouting(Opposition(|context: &BoundedVecContext<_>, x: &Vec<f64>| context.opposite(x)))
+//!     .build()
+//!     .unwrap();
+//! assert!(hive.run_for_rounds(20).unwrap().fitness > 0.0);
+//! # }
+//! ```
+
+extern crate rand;
+
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use self::rand::Rng;
+use self::rand::distributions::{IndependentSample, Normal};
+
+use candidate::Candidate;
+use context::Context;
+use init::Bounded;
+
+/// Rule for replacing an abandoned candidate.
+pub trait ScoutStrategy<Ctx: Context>: Send + Sync {
+    /// Generates a replacement for the candidate at `index` in `field`.
+    ///
+    /// `elites` holds the best candidates found so far, fittest first; it is
+    /// never empty. Strategies that need randomness should draw it from
+    /// `rng`, so that seeded runs stay reproducible.
+    fn scout(&self,
+             context: &Ctx,
+             field: &[Arc<Candidate<Ctx::Solution, Ctx::Fitness>>],
+             index: usize,
+             elites: &[Candidate<Ctx::Solution, Ctx::Fitness>],
+             rng: &mut dyn Rng)
+             -> Ctx::Solution;
+}
+
+/// Restarts from a fresh random solution.
+///
+/// This is the classic ABC scout, and the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Random;
+
+impl<Ctx: Context> ScoutStrategy<Ctx> for Random {
+    fn scout(&self,
+             context: &Ctx,
+             _field: &[Arc<Candidate<Ctx::Solution, Ctx::Fitness>>],
+             _index: usize,
+             _elites: &[Candidate<Ctx::Solution, Ctx::Fitness>],
+             mut rng: &mut dyn Rng)
+             -> Ctx::Solution {
+        context.make_with_rng(&mut rng)
+    }
+}
+
+/// Restarts from the opposite of the abandoned solution.
+///
+/// In opposition-based learning, the opposite of a point *x* within bounds
+/// [*a*, *b*] is *a* + *b* − *x*. An abandoned candidate has exhausted its
+/// neighbourhood, so its opposite is a jump to the far side of the search
+/// space. The wrapped function computes the opposite of a solution; for
+/// [`BoundedVecContext`](../vector/struct.BoundedVecContext.html), it can
+/// defer to [`opposite`](../vector/struct.BoundedVecContext.html#method.opposite).
+#[derive(Clone, Copy, Debug)]
+pub struct Opposition<F>(pub F);
+
+impl<Ctx, F> ScoutStrategy<Ctx> for Opposition<F>
+    where Ctx: Context,
+          F: Fn(&Ctx, &Ctx::Solution) -> Ctx::Solution + Send + Sync
+{
+    fn scout(&self,
+             context: &Ctx,
+             field: &[Arc<Candidate<Ctx::Solution, Ctx::Fitness>>],
+             index: usize,
+             _elites: &[Candidate<Ctx::Solution, Ctx::Fitness>],
+             _rng: &mut dyn Rng)
+             -> Ctx::Solution {
+        (self.0)(context, &field[index].solution)
+    }
+}
+
+/// Restarts from a variant of the best candidate found so far.
+///
+/// The variant comes from
+/// [`Context::explore_with_rng`](../trait.Context.html#method.explore_with_rng),
+/// with the abandoned candidate as the only other member of the field. This
+/// concentrates the search around the global best, at the cost of diversity.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MutateBest;
+
+impl<Ctx: Context> ScoutStrategy<Ctx> for MutateBest {
+    fn scout(&self,
+             context: &Ctx,
+             field: &[Arc<Candidate<Ctx::Solution, Ctx::Fitness>>],
+             index: usize,
+             elites: &[Candidate<Ctx::Solution, Ctx::Fitness>],
+             mut rng: &mut dyn Rng)
+             -> Ctx::Solution {
+        let pair = [Arc::new(elites[0].clone()), field[index].clone()];
+        context.explore_with_rng(&pair, 0, &mut rng)
+    }
+}
+
+/// Restarts from a variant of one of the elite candidates, chosen at random.
+///
+/// Like [`MutateBest`](struct.MutateBest.html), but spreads the restarts
+/// across several good regions rather than just one. The elites are those
+/// kept in the hive's archive; see
+/// [`Hive::top_k`](../struct.Hive.html#method.top_k).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Elite;
+
+impl<Ctx: Context> ScoutStrategy<Ctx> for Elite {
+    fn scout(&self,
+             context: &Ctx,
+             field: &[Arc<Candidate<Ctx::Solution, Ctx::Fitness>>],
+             index: usize,
+             elites: &[Candidate<Ctx::Solution, Ctx::Fitness>],
+             mut rng: &mut dyn Rng)
+             -> Ctx::Solution {
+        let elite = (&mut rng).gen_range(0, elites.len());
+        let pair = [Arc::new(elites[elite].clone()), field[index].clone()];
+        context.explore_with_rng(&pair, 0, &mut rng)
+    }
+}
+
+/// Moves the abandoned candidate by a Lévy flight.
+///
+/// Each dimension takes a step drawn from a Lévy distribution with
+/// exponent `beta`, by Mantegna's algorithm, scaled by `scale` times the
+/// width of the bounds; the result is clamped to the bounds. Most steps are
+/// small, keeping what the candidate had found, but a few are long jumps,
+/// which is reported to recover faster than a full restart on many
+/// problems. The context must be [`Bounded`](../init/trait.Bounded.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Levy {
+    /// Exponent of the distribution, between 0.3 and 1.99; smaller values
+    /// make long jumps more likely.
+    pub beta: f64,
+
+    /// Size of a typical step, as a fraction of the width of the bounds.
+    pub scale: f64,
+}
+
+impl Default for Levy {
+    /// The usual choices: `beta` is 1.5, and `scale` is 0.01.
+    fn default() -> Levy {
+        Levy {
+            beta: 1.5,
+            scale: 0.01,
+        }
+    }
+}
+
+impl Levy {
+    /// Draws one step, by Mantegna's algorithm.
+    fn step(&self, mut rng: &mut dyn Rng) -> f64 {
+        let beta = self.beta;
+        let sigma = (gamma(1f64 + beta) * (PI * beta / 2f64).sin() /
+                     (gamma((1f64 + beta) / 2f64) * beta * 2f64.powf((beta - 1f64) / 2f64)))
+                        .powf(1f64 / beta);
+        let u = Normal::new(0f64, sigma).ind_sample(&mut rng);
+        let v = Normal::new(0f64, 1f64).ind_sample(&mut rng);
+        u / v.abs().powf(1f64 / beta)
+    }
+}
+
+impl<Ctx: Bounded> ScoutStrategy<Ctx> for Levy {
+    fn scout(&self,
+             context: &Ctx,
+             field: &[Arc<Candidate<Vec<f64>, Ctx::Fitness>>],
+             index: usize,
+             _elites: &[Candidate<Vec<f64>, Ctx::Fitness>],
+             rng: &mut dyn Rng)
+             -> Vec<f64> {
+        let (lower, upper) = context.bounds();
+        field[index].solution
+                    .iter()
+                    .enumerate()
+                    .map(|(j, x)| {
+                        let moved = x + self.scale * (upper[j] - lower[j]) * self.step(rng);
+                        // A huge step may overflow, which counts as leaving the bounds.
+                        if moved.is_nan() { *x } else { moved.max(lower[j]).min(upper[j]) }
+                    })
+                    .collect()
+    }
+}
+
+/// The gamma function, by the Lanczos approximation, for positive `x`.
+fn gamma(x: f64) -> f64 {
+    const G: f64 = 7f64;
+    const COEFFICIENTS: [f64; 9] = [0.999_999_999_999_809_9,
+                                    676.520_368_121_885_1,
+                                    -1_259.139_216_722_402_8,
+                                    771.323_428_777_653_1,
+                                    -176.615_029_162_140_6,
+                                    12.507_343_278_686_905,
+                                    -0.138_571_095_265_720_12,
+                                    9.984_369_578_019_572e-6,
+                                    1.505_632_735_149_311_6e-7];
+    if x < 0.5 {
+        // Reflection formula.
+        return PI / ((PI * x).sin() * gamma(1f64 - x));
+    }
+    let x = x - 1f64;
+    let t = x + G + 0.5;
+    let sum = COEFFICIENTS[1..]
+                  .iter()
+                  .enumerate()
+                  .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1f64));
+    (2f64 * PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive::HiveBuilder;
+    use vector::BoundedVecContext;
+
+    struct Origin;
+
+    impl<Ctx: Context<Solution = Vec<f64>>> ScoutStrategy<Ctx> for Origin {
+        fn scout(&self,
+                 _context: &Ctx,
+                 field: &[Arc<Candidate<Vec<f64>, Ctx::Fitness>>],
+                 index: usize,
+                 _elites: &[Candidate<Vec<f64>, Ctx::Fitness>],
+                 _rng: &mut dyn Rng)
+                 -> Vec<f64> {
+            vec![0.0; field[index].solution.len()]
+        }
+    }
+
+    #[test]
+    fn custom_strategy_replaces_abandoned() {
+        let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 10.0 - x[0] - x[1]);
+        let hive = HiveBuilder::new(context, 5)
+            .set_threads(1)
+            .set_retries(1)
+            .set_scouting(Origin)
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        let history = hive.scout_history().unwrap();
+        assert!(!history.is_empty());
+        assert!(history.iter().all(|event| event.replacement_fitness == 10.0));
+    }
+
+    #[test]
+    fn gamma_matches_factorials() {
+        assert!((gamma(5.0) - 24.0).abs() < 1e-9);
+        assert!((gamma(0.5) - PI.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn levy_flights_stay_in_bounds() {
+        let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 10.0 - x[0] - x[1]);
+        let elites = [Candidate::new(vec![4.9, -4.9], 10.0)];
+        let field = [Arc::new(elites[0].clone())];
+        let strategy = Levy {
+            beta: 1.5,
+            scale: 1.0,
+        };
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let moved = strategy.scout(&context, &field, 0, &elites, &mut rng);
+            assert!(moved.iter().all(|x| x.abs() <= 5.0));
+        }
+    }
+
+    #[test]
+    fn elites_stay_in_bounds() {
+        let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 10.0 - x[0] - x[1]);
+        let hive = HiveBuilder::new(context, 5)
+            .set_threads(2)
+            .set_retries(1)
+            .set_scouting(Elite)
+            .build()
+            .unwrap();
+        let best = hive.run_for_rounds(20).unwrap();
+        assert!(best.solution.iter().all(|x| x.abs() <= 5.0));
+        assert!(hive.scout_count() > 0);
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How observers choose which candidate to work on.
+pub enum Selection {
+    /// Roulette-wheel selection: each candidate is chosen with likelihood
+    /// proportionate to its weight, from the
+    /// [scaling function](scaling/index.html) and any
+    /// [niching](enum.Niching.html). This is the default.
+    #[default]
+    Roulette,
+
+    /// *k*-way tournament selection: *k* candidates are drawn at random (with
+    /// replacement), and the fittest of them is chosen.
+    ///
+    /// Only the order of the fitnesses matters, so this is insensitive to
+    /// their scale and sign, and the scaling function and niching are not
+    /// used. Larger tournaments mean more selection pressure; a tournament
+    /// of 1 chooses uniformly at random.
+    Tournament(usize),
+}
@@ -0,0 +1,208 @@
+//! Solutions that share a large, read-only dataset with their context.
+//!
+//! Solutions must be `'static`, because the hive hands them between threads
+//! and keeps the fittest after the run is over, so they can't borrow from
+//! the context. A solution that needs to refer to the context's data, such
+//! as a selection of rows from a table, would have to own a copy of it, and
+//! every exploration would copy it again.
+//!
+//! Instead, a context can keep its data in a
+//! [`SharedContext`](struct.SharedContext.html), and pair each solution with
+//! a reference-counted handle to the data, as a [`Shared`](struct.Shared.html).
+//! Cloning a `Shared` clones only its own part; the data is never copied.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; extern crate rand; fn main() {
+//! use std::sync::Arc;
+//! use rand::{thread_rng, Rng};
+//! use abc::{Candidate, Context, HiveBuilder};
+//! use abc::shared::{Shared, SharedContext};
+//!
+//! // Choose the row of a large table with the largest value.
+//! struct Rows(SharedContext<Vec<f64>>);
+//!
+//! impl Context for Rows {
+//!     type Solution = Shared<Vec<f64>, usize>;
+//!     type Fitness = f64;
+//!
+//!     fn make(&self) -> Self::Solution {
+//!         self.0.share(thread_rng().gen_range(0, self.0.len()))
+//!     }
+//!
+//!     fn evaluate_fitness(&self, row: &Self::Solution) -> f64 {
+//!         row.data()[**row]
+//!     }
+//!
+//!     fn explore(&self, field: &[Arc<Candidate<Self::Solution>>], index: usize) -> Self::Solution {
+//!         let row = *field[index].solution as isize + thread_rng().gen_range(-3, 4);
+//!         self.0.share(row.max(0).min(self.0.len() as isize - 1) as usize)
+//!     }
+//! }
+//!
+//! let table = (0..10000).map(|i| 1.0 + (i % 700) as f64).collect::<Vec<_>>();
+//! let context = Rows(SharedContext::new(table));
+//! let hive = HiveBuilder::new(context, 10).build().unwrap();
+//! let best = hive.run_for_rounds(100).unwrap();
+//! assert_eq!(best.solution.data().len(), 10000);
+//! assert!(best.fitness > 600.0);
+//! # }
+//! ```
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+/// Read-only data owned by a context, to be shared with its solutions.
+///
+/// This dereferences to the data, for use in the context itself.
+pub struct SharedContext<D> {
+    data: Arc<D>,
+}
+
+impl<D> SharedContext<D> {
+    /// Takes ownership of `data`.
+    pub fn new(data: D) -> SharedContext<D> {
+        SharedContext { data: Arc::new(data) }
+    }
+
+    /// Shares data that is already reference-counted.
+    pub fn from_arc(data: Arc<D>) -> SharedContext<D> {
+        SharedContext { data }
+    }
+
+    /// Returns the reference-counted data.
+    pub fn data(&self) -> &Arc<D> {
+        &self.data
+    }
+
+    /// Pairs `value` with a handle to the data.
+    pub fn share<S>(&self, value: S) -> Shared<D, S> {
+        Shared {
+            data: self.data.clone(),
+            value,
+        }
+    }
+}
+
+impl<D> Clone for SharedContext<D> {
+    fn clone(&self) -> SharedContext<D> {
+        SharedContext { data: self.data.clone() }
+    }
+}
+
+impl<D> Deref for SharedContext<D> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        &self.data
+    }
+}
+
+impl<D: fmt::Debug> fmt::Debug for SharedContext<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SharedContext").field(&self.data).finish()
+    }
+}
+
+/// A solution's own value, with a handle to the data it shares.
+///
+/// This dereferences to the value. Cloning it clones the value and the
+/// handle, but not the data.
+///
+/// Two `Shared` solutions are equal if their values are equal and they share
+/// the same data. Formatting with `Debug`, or serializing with the `serde`
+/// feature, shows only the value.
+pub struct Shared<D, S> {
+    data: Arc<D>,
+    value: S,
+}
+
+impl<D, S> Shared<D, S> {
+    /// Returns the shared data.
+    pub fn data(&self) -> &D {
+        &self.data
+    }
+
+    /// Returns the reference-counted data.
+    pub fn data_arc(&self) -> &Arc<D> {
+        &self.data
+    }
+
+    /// Discards the handle to the data, and returns the value.
+    pub fn into_value(self) -> S {
+        self.value
+    }
+
+    /// Pairs a new value with the same data.
+    pub fn with_value<T>(&self, value: T) -> Shared<D, T> {
+        Shared {
+            data: self.data.clone(),
+            value,
+        }
+    }
+}
+
+impl<D, S: Clone> Clone for Shared<D, S> {
+    fn clone(&self) -> Shared<D, S> {
+        self.with_value(self.value.clone())
+    }
+}
+
+impl<D, S> Deref for Shared<D, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.value
+    }
+}
+
+impl<D, S> DerefMut for Shared<D, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.value
+    }
+}
+
+impl<D, S: PartialEq> PartialEq for Shared<D, S> {
+    fn eq(&self, other: &Shared<D, S>) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) && self.value == other.value
+    }
+}
+
+impl<D, S: fmt::Debug> fmt::Debug for Shared<D, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Shared").field(&self.value).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<D, S: Serialize> Serialize for Shared<D, S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_data() {
+        let context = SharedContext::new(vec![0u8; 1 << 20]);
+        let mut solution = context.share(vec![1, 2]);
+        let copy = solution.clone();
+        assert_eq!(copy, solution);
+        assert!(Arc::ptr_eq(copy.data_arc(), context.data()));
+        assert_eq!(Arc::strong_count(context.data()), 3);
+
+        solution.push(3);
+        assert_ne!(copy, solution);
+        assert_ne!(SharedContext::new(vec![0u8; 1 << 20]).share(vec![1, 2]), copy);
+        assert_eq!(copy.into_value(), vec![1, 2]);
+        assert_eq!(Arc::strong_count(context.data()), 2);
+    }
+}
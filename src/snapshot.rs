@@ -0,0 +1,73 @@
+use candidate::Provenance;
+use pareto::ParetoMember;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Saved state of one of the hive's working candidates.
+///
+/// These make up a [`HiveSnapshot`](struct.HiveSnapshot.html), and are also
+/// returned by [`Hive::population`](struct.Hive.html#method.population).
+pub struct WorkingSnapshot<S, F = f64> {
+    /// The candidate solution.
+    pub solution: S,
+
+    /// Cached fitness of the solution.
+    pub fitness: f64,
+
+    /// The solution's fitness as the context gave it. See
+    /// [`Candidate::raw_fitness`](struct.Candidate.html#structfield.raw_fitness).
+    pub raw_fitness: F,
+
+    /// Number of unimproved visits left before the candidate is scouted.
+    pub retries: usize,
+
+    /// The candidate's score for reporting, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub score: Option<f64>,
+
+    /// Where and when the candidate was created, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub provenance: Option<Provenance>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Saved state of a hive, from which it can later be resumed.
+///
+/// A snapshot is taken with [`Hive::snapshot`](struct.Hive.html#method.snapshot),
+/// and resumed with [`Hive::from_snapshot`](struct.Hive.html#method.from_snapshot).
+/// With the `serde` feature enabled, snapshots can be serialized (as long as
+/// the solutions can), so that long optimizations can be checkpointed to
+/// disk.
+pub struct HiveSnapshot<S, F = f64> {
+    /// The working candidates, in order.
+    pub working: Vec<WorkingSnapshot<S, F>>,
+
+    /// The best solution found so far.
+    pub best_solution: S,
+
+    /// Cached fitness of the best solution.
+    pub best_fitness: f64,
+
+    /// The best solution's fitness as the context gave it.
+    pub best_raw_fitness: F,
+
+    /// The best solution's score for reporting, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub best_score: Option<f64>,
+
+    /// Number of rounds completed by the hive, over all of its runs.
+    pub round: usize,
+
+    /// Number of fitness evaluations performed by the hive, over all of its runs.
+    pub evaluations: usize,
+
+    /// The non-dominated solutions found so far, for multi-objective contexts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pareto_front: Vec<ParetoMember<S>>,
+
+    /// The fittest distinct solutions found so far, with their cached and
+    /// raw fitnesses, best first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elites: Vec<(S, f64, F)>,
+}
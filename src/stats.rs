@@ -0,0 +1,309 @@
+//! Records how the search progresses from round to round.
+//!
+//! After each round, the hive summarizes the fitnesses of its working
+//! candidates in a [`RoundStats`](struct.RoundStats.html), along with the
+//! number of evaluations and scouting events in that round. The history is
+//! available through [`Hive::stats`](../struct.Hive.html#method.stats), and
+//! each new entry can also be sent down a channel with
+//! [`Hive::set_stats_sender`](../struct.Hive.html#method.set_stats_sender),
+//! which makes it easy to plot convergence curves. If the context measures
+//! [`distance`](../trait.Context.html#method.distance), each entry also
+//! describes the [`Diversity`](struct.Diversity.html) of the candidates.
+//!
+//! Each time a candidate is abandoned, the hive also records a
+//! [`ScoutEvent`](struct.ScoutEvent.html), available through
+//! [`Hive::scout_history`](../struct.Hive.html#method.scout_history).
+//!
+//! For a progress bar, [`HiveBuilder::set_progress`](../struct.HiveBuilder.html#method.set_progress)
+//! reports the [`Progress`](struct.Progress.html) of each run as its rounds
+//! end, with an estimate of the time remaining.
+//!
+//! Each rise in the best fitness is recorded as an
+//! [`Improvement`](struct.Improvement.html), available through
+//! [`Hive::improvement_history`](../struct.Hive.html#method.improvement_history),
+//! for plotting convergence after a run without streaming.
+//!
+//! The wall-clock time of each round is kept as a
+//! [`RoundTiming`](struct.RoundTiming.html), available through
+//! [`Hive::timings`](../struct.Hive.html#method.timings), so that a fitness
+//! function whose cost drifts over a long run can be spotted.
+//!
+//! When the hive is driven a round at a time with
+//! [`Hive::step`](../struct.Hive.html#method.step), each step returns a
+//! [`RoundSummary`](struct.RoundSummary.html).
+
+use std::f64;
+use std::time::Duration;
+
+use candidate::Candidate;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Summary of the working candidates at the end of a round.
+pub struct RoundStats {
+    /// The round that just ended, counting from 1 over the hive's lifetime.
+    /// Round 0 describes the initial population.
+    pub round: usize,
+
+    /// Highest fitness among the working candidates.
+    pub best: f64,
+
+    /// Mean fitness of the working candidates.
+    pub mean: f64,
+
+    /// Lowest fitness among the working candidates.
+    pub worst: f64,
+
+    /// Population standard deviation of the working candidates' fitnesses.
+    pub std_dev: f64,
+
+    /// Candidates abandoned and replaced by scouts during the round.
+    pub scouts: usize,
+
+    /// Fitness evaluations performed during the round.
+    pub evaluations: usize,
+
+    /// Variants explored during the round.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub explorations: usize,
+
+    /// Explorations during the round that improved on their candidate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub improvements: usize,
+
+    /// Tasks during the round whose exploration or evaluation panicked or
+    /// timed out, and whose candidates were scouted anew. See
+    /// [`set_panic_tolerance`](../struct.HiveBuilder.html#method.set_panic_tolerance).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub panics: usize,
+
+    /// How spread out the working candidates are, if the context measures
+    /// [`distance`](../trait.Context.html#method.distance).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diversity: Option<Diversity>,
+}
+
+impl RoundStats {
+    /// Summarizes `fitnesses`, which must not be empty.
+    pub fn new(round: usize, fitnesses: &[f64], scouts: usize, evaluations: usize) -> RoundStats {
+        let n = fitnesses.len() as f64;
+        let mean = fitnesses.iter().sum::<f64>() / n;
+        let variance = fitnesses.iter().map(|f| (f - mean) * (f - mean)).sum::<f64>() / n;
+        RoundStats {
+            round,
+            best: fitnesses.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            worst: fitnesses.iter().cloned().fold(f64::INFINITY, f64::min),
+            std_dev: variance.sqrt(),
+            scouts,
+            evaluations,
+            explorations: 0,
+            improvements: 0,
+            panics: 0,
+            diversity: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How long a round took.
+pub struct RoundTiming {
+    /// The round, counting from 1 over the hive's lifetime, as in
+    /// [`RoundStats`](struct.RoundStats.html).
+    pub round: usize,
+
+    /// Wall-clock time from the end of the previous round, or the start of
+    /// the run, to the end of this one.
+    pub duration: Duration,
+
+    /// Tasks in the round, as the task schedule counts them: by default,
+    /// one for each worker and observer.
+    pub tasks: usize,
+}
+
+impl RoundTiming {
+    /// The tasks carried out per second during the round.
+    pub fn tasks_per_second(&self) -> f64 {
+        let seconds = self.duration.as_secs_f64();
+        if seconds > 0f64 { self.tasks as f64 / seconds } else { 0f64 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Summary of the distances between every pair of working candidates.
+///
+/// A population that has collapsed onto one point shows a maximum distance
+/// near zero; a minimum distance near zero means at least two candidates
+/// have converged.
+pub struct Diversity {
+    /// Mean distance between a pair of candidates.
+    pub mean_distance: f64,
+
+    /// Distance between the closest pair of candidates.
+    pub min_distance: f64,
+
+    /// Distance between the farthest pair of candidates.
+    pub max_distance: f64,
+}
+
+impl Diversity {
+    /// Summarizes the pairwise `distances`, or returns `None` if there are none.
+    pub fn new(distances: &[f64]) -> Option<Diversity> {
+        if distances.is_empty() {
+            return None;
+        }
+        Some(Diversity {
+            mean_distance: distances.iter().sum::<f64>() / distances.len() as f64,
+            min_distance: distances.iter().cloned().fold(f64::INFINITY, f64::min),
+            max_distance: distances.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// A candidate that was abandoned and replaced by a scout.
+pub struct ScoutEvent {
+    /// The round under way when the candidate was abandoned, counting from 0
+    /// over the hive's lifetime, as reported by
+    /// [`Hive::get_round`](../struct.Hive.html#method.get_round).
+    pub round: usize,
+
+    /// Index of the candidate in the field.
+    pub index: usize,
+
+    /// Fitness of the abandoned candidate.
+    pub abandoned_fitness: f64,
+
+    /// Fitness of its replacement.
+    pub replacement_fitness: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// A rise in the best fitness found so far.
+pub struct Improvement {
+    /// The round under way when the best improved, counting from 0 over the
+    /// hive's lifetime, as reported by
+    /// [`Hive::get_round`](../struct.Hive.html#method.get_round).
+    pub round: usize,
+
+    /// Fitness of the new best candidate.
+    pub fitness: f64,
+
+    /// Fitness evaluations made over the hive's lifetime by then.
+    pub evaluations: usize,
+
+    /// Wall-clock time since the hive was built or resumed.
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How far the current run has got, reported at the end of each round.
+pub struct Progress {
+    /// Rounds completed so far in this run.
+    pub round: usize,
+
+    /// Rounds the run will last, if it was started with
+    /// [`Hive::run_for_rounds`](../struct.Hive.html#method.run_for_rounds).
+    pub rounds: Option<usize>,
+
+    /// Fitness of the best candidate found so far.
+    pub best_fitness: f64,
+
+    /// Fitness evaluations per second, over the run so far.
+    pub evaluations_per_second: f64,
+
+    /// Wall-clock time since the run started.
+    pub elapsed: Duration,
+
+    /// Estimated time until the run finishes, assuming the remaining rounds
+    /// go as fast as the ones so far, if the number of rounds is known.
+    pub remaining: Option<Duration>,
+}
+
+impl Progress {
+    /// Summarizes a run that has completed `round` of `rounds` rounds, and
+    /// made `evaluations` fitness evaluations, in `elapsed`.
+    pub fn new(round: usize,
+               rounds: Option<usize>,
+               best_fitness: f64,
+               evaluations: usize,
+               elapsed: Duration)
+               -> Progress {
+        let seconds = elapsed.as_secs_f64();
+        Progress {
+            round,
+            rounds,
+            best_fitness,
+            evaluations_per_second: if seconds > 0f64 { evaluations as f64 / seconds } else { 0f64 },
+            elapsed,
+            remaining: rounds.filter(|_| round > 0).map(|rounds| {
+                Duration::from_secs_f64(seconds * rounds.saturating_sub(round) as f64 / round as f64)
+            }),
+        }
+    }
+
+    /// The fraction of the run completed, if the number of rounds is known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.rounds.map(|rounds| if rounds > 0 { (self.round as f64 / rounds as f64).min(1f64) } else { 1f64 })
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The outcome of one round, returned by [`Hive::step`](../struct.Hive.html#method.step).
+pub struct RoundSummary<S: Clone + Send + Sync + 'static, F = f64> {
+    /// Statistics for the round.
+    pub stats: RoundStats,
+
+    /// The best candidate found so far.
+    pub best: Candidate<S, F>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let stats = RoundStats::new(3, &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], 1, 16);
+        assert_eq!(stats.best, 9.0);
+        assert_eq!(stats.worst, 2.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.std_dev, 2.0);
+        assert_eq!(stats.diversity, None);
+    }
+
+    #[test]
+    fn progress_estimates_remaining() {
+        let progress = Progress::new(5, Some(20), 1.0, 100, Duration::from_secs(2));
+        assert_eq!(progress.evaluations_per_second, 50.0);
+        assert_eq!(progress.remaining, Some(Duration::from_secs(6)));
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert_eq!(Progress::new(5, None, 1.0, 100, Duration::from_secs(2)).remaining, None);
+    }
+
+    #[test]
+    fn timing_throughput() {
+        let timing = RoundTiming {
+            round: 1,
+            duration: Duration::from_millis(250),
+            tasks: 40,
+        };
+        assert_eq!(timing.tasks_per_second(), 160.0);
+        assert_eq!(RoundTiming { duration: Duration::from_secs(0), ..timing }.tasks_per_second(), 0.0);
+    }
+
+    #[test]
+    fn diversity() {
+        let diversity = Diversity::new(&[1.0, 2.0, 6.0]).unwrap();
+        assert_eq!(diversity.mean_distance, 3.0);
+        assert_eq!(diversity.min_distance, 1.0);
+        assert_eq!(diversity.max_distance, 6.0);
+        assert_eq!(Diversity::new(&[]), None);
+    }
+}
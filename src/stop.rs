@@ -0,0 +1,317 @@
+//! Decides when a running hive should stop.
+//!
+//! Each time a worker thread asks for another task, the hive summarizes the
+//! progress of the current run in a [`RunState`](struct.RunState.html) and
+//! consults its [`StopCondition`](trait.StopCondition.html). Once the
+//! condition is met, no more tasks are handed out, and the run ends as soon
+//! as the tasks already underway have finished.
+//!
+//! Several conditions are available in this module, and they can be combined
+//! with [`and`](trait.StopCondition.html#method.and) and
+//! [`or`](trait.StopCondition.html#method.or).
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use std::time::Duration;
+//! use abc::stop::{StopCondition, MaxEvaluations, Stagnation, TimeLimit};
+//!
+//! // Stop after ten thousand evaluations or a minute, whichever comes first,
+//! // unless the search converges before then.
+//! let condition = MaxEvaluations(10_000)
+//!     .or(TimeLimit(Duration::from_secs(60)))
+//!     .or(Stagnation::new(50, 1e-6));
+//! # let _ = condition;
+//! # }
+//! ```
+//!
+//! Users may also implement their own conditions. Since conditions take
+//! `&mut self`, they are free to keep track of the run's history.
+//!
+//! A [`CancelToken`](struct.CancelToken.html) stops the hive from outside,
+//! for applications that already signal their own shutdown.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Summary of a run's progress, used to decide whether it should stop.
+pub struct RunState {
+    /// Rounds completed so far in this run.
+    ///
+    /// A round is complete once each of its tasks has been claimed (though
+    /// not necessarily finished) by a worker thread.
+    pub round: usize,
+
+    /// Fitness evaluations performed so far in this run.
+    pub evaluations: usize,
+
+    /// Wall-clock time since the run started.
+    pub elapsed: Duration,
+
+    /// Fitness of the best candidate found so far, in this run or earlier.
+    pub best_fitness: f64,
+}
+
+/// Rule for ending a run.
+pub trait StopCondition: Send {
+    /// Returns `true` if the run should stop.
+    ///
+    /// This is called each time a worker thread asks for a task, so it should
+    /// be cheap to evaluate.
+    fn should_stop(&mut self, state: &RunState) -> bool;
+
+    /// Stops once both `self` and `other` are met.
+    fn and<C: StopCondition>(self, other: C) -> And<Self, C>
+        where Self: Sized
+    {
+        And(self, other)
+    }
+
+    /// Stops once either `self` or `other` is met.
+    fn or<C: StopCondition>(self, other: C) -> Or<Self, C>
+        where Self: Sized
+    {
+        Or(self, other)
+    }
+}
+
+impl StopCondition for Box<dyn StopCondition> {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        (**self).should_stop(state)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Stops once both conditions are met.
+///
+/// Both conditions are always consulted, so that stateful conditions like
+/// [`Stagnation`](struct.Stagnation.html) see every update.
+pub struct And<A, B>(pub A, pub B);
+
+impl<A: StopCondition, B: StopCondition> StopCondition for And<A, B> {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        let first = self.0.should_stop(state);
+        let second = self.1.should_stop(state);
+        first && second
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Stops once either condition is met.
+///
+/// Both conditions are always consulted, so that stateful conditions like
+/// [`Stagnation`](struct.Stagnation.html) see every update.
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A: StopCondition, B: StopCondition> StopCondition for Or<A, B> {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        let first = self.0.should_stop(state);
+        let second = self.1.should_stop(state);
+        first || second
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Stops after a fixed number of rounds.
+pub struct MaxRounds(pub usize);
+
+impl StopCondition for MaxRounds {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.round >= self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Stops after a fixed number of fitness evaluations.
+///
+/// Evaluations that are underway when the budget runs out will still finish,
+/// so a run may overrun its budget by up to one evaluation per thread.
+pub struct MaxEvaluations(pub usize);
+
+impl StopCondition for MaxEvaluations {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.evaluations >= self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Stops after a fixed amount of wall-clock time.
+///
+/// Tasks that are underway when time runs out will still finish, so a run
+/// may overrun its budget by up to one fitness evaluation per thread.
+pub struct TimeLimit(pub Duration);
+
+impl StopCondition for TimeLimit {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.elapsed >= self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Stops once the best fitness reaches a target.
+pub struct TargetFitness(pub f64);
+
+impl StopCondition for TargetFitness {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        state.best_fitness >= self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Stops once the search has converged.
+///
+/// The search is considered converged after a number of consecutive rounds
+/// in which the best fitness fails to rise more than `epsilon` above a
+/// baseline. The baseline starts at the best fitness when the run begins,
+/// and moves up each time a round ends with an improvement greater than
+/// `epsilon`.
+pub struct Stagnation {
+    rounds: usize,
+    epsilon: f64,
+    baseline: Option<f64>,
+    last_round: usize,
+    stagnant: usize,
+}
+
+impl Stagnation {
+    /// Stops after `rounds` rounds without an improvement beyond `epsilon`.
+    pub fn new(rounds: usize, epsilon: f64) -> Stagnation {
+        Stagnation {
+            rounds,
+            epsilon,
+            baseline: None,
+            last_round: 0,
+            stagnant: 0,
+        }
+    }
+}
+
+impl StopCondition for Stagnation {
+    fn should_stop(&mut self, state: &RunState) -> bool {
+        let baseline = *self.baseline.get_or_insert(state.best_fitness);
+        if state.round > self.last_round {
+            if state.best_fitness - baseline > self.epsilon {
+                self.baseline = Some(state.best_fitness);
+                self.stagnant = 0;
+            } else {
+                self.stagnant += state.round - self.last_round;
+            }
+            self.last_round = state.round;
+        }
+        self.stagnant >= self.rounds
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+/// Stops once cancelled from elsewhere.
+///
+/// The token is shared by its clones, so one clone can be given to the hive
+/// as a stop condition, and another kept by whatever decides to shut down,
+/// such as a Ctrl-C handler, without holding a reference to the hive. A
+/// cancelled token stays cancelled, and stops every later run at once.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate abc; fn main() {
+/// use std::thread;
+/// use std::time::Duration;
+/// use abc::HiveBuilder;
+/// use abc::stop::CancelToken;
+/// use abc::vector::BoundedVecContext;
+///
+/// let token = CancelToken::new();
+/// let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+/// let hive = HiveBuilder::new(context, 10).set_stop_condition(token.clone()).build().unwrap();
+///
+/// let shutdown = token.clone();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(10));
+///     shutdown.cancel();
+/// });
+/// hive.run_forever().unwrap();
+/// assert!(token.is_cancelled());
+/// # }
+/// ```
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// Cancels the token, stopping any run that it is a condition for.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` once the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancelToken {
+    /// Wraps a flag that the application already sets on shutdown.
+    fn from(flag: Arc<AtomicBool>) -> CancelToken {
+        CancelToken(flag)
+    }
+}
+
+impl StopCondition for CancelToken {
+    fn should_stop(&mut self, _: &RunState) -> bool {
+        self.is_cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(round: usize, best_fitness: f64) -> RunState {
+        RunState {
+            round,
+            evaluations: 0,
+            elapsed: Duration::from_secs(0),
+            best_fitness,
+        }
+    }
+
+    #[test]
+    fn combinators() {
+        let mut either = MaxRounds(2).or(TargetFitness(1.0));
+        let mut both = MaxRounds(2).and(TargetFitness(1.0));
+        assert!(!either.should_stop(&state(1, 0.0)));
+        assert!(either.should_stop(&state(1, 1.0)));
+        assert!(!both.should_stop(&state(1, 1.0)));
+        assert!(both.should_stop(&state(2, 1.0)));
+    }
+
+    #[test]
+    fn stagnation() {
+        let mut stagnation = Stagnation::new(2, 0.5);
+        assert!(!stagnation.should_stop(&state(0, 1.0)));
+        // An improvement within epsilon doesn't reset the count.
+        assert!(!stagnation.should_stop(&state(1, 1.2)));
+        // An improvement beyond epsilon does.
+        assert!(!stagnation.should_stop(&state(2, 2.0)));
+        assert!(!stagnation.should_stop(&state(3, 2.0)));
+        assert!(stagnation.should_stop(&state(4, 2.0)));
+    }
+
+    #[test]
+    fn cancellation_is_shared() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut token = CancelToken::from(flag.clone());
+        let clone = token.clone();
+        assert!(!token.should_stop(&state(0, 0.0)));
+        clone.cancel();
+        assert!(token.should_stop(&state(0, 0.0)));
+        assert!(flag.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::mpsc::{Sender, SyncSender, Receiver, TrySendError, channel, sync_channel};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+/// How a streaming hive buffers results that the receiver hasn't read yet.
+///
+/// This applies to [`Hive::stream`](struct.Hive.html#method.stream) and
+/// [`Hive::stream_events`](struct.Hive.html#method.stream_events), and is set
+/// with [`HiveBuilder::set_stream_buffer`](struct.HiveBuilder.html#method.set_stream_buffer).
+pub enum StreamBuffer {
+    /// Keep every result until it is read. This is the default.
+    #[default]
+    Unbounded,
+
+    /// Keep up to this many results; once the buffer is full, the hive waits
+    /// for the receiver to catch up.
+    Bounded(usize),
+
+    /// Keep only the latest result, discarding any that the receiver hasn't
+    /// read by the time a newer one arrives.
+    ///
+    /// A result that arrives while the receiver holds an unread one is
+    /// delivered once it has been read, no later than the end of the round.
+    Latest,
+}
+
+/// Opens a channel with the given buffering.
+pub fn open<T>(buffer: StreamBuffer) -> (StreamSender<T>, Receiver<T>) {
+    match buffer {
+        StreamBuffer::Unbounded => {
+            let (sender, receiver) = channel();
+            (StreamSender::Unbounded(sender), receiver)
+        }
+        StreamBuffer::Bounded(capacity) => {
+            let (sender, receiver) = sync_channel(capacity);
+            (StreamSender::Bounded(sender), receiver)
+        }
+        StreamBuffer::Latest => {
+            let (sender, receiver) = sync_channel(1);
+            (StreamSender::Latest(sender, None), receiver)
+        }
+    }
+}
+
+/// The sending half of a channel opened by a `StreamBuffer`.
+pub enum StreamSender<T> {
+    /// Never blocks.
+    Unbounded(Sender<T>),
+
+    /// Blocks while the channel is full.
+    Bounded(SyncSender<T>),
+
+    /// Holds the latest result back while the channel is full.
+    Latest(SyncSender<T>, Option<T>),
+}
+
+impl<T> StreamSender<T> {
+    /// Sends a result. Returns `Err` if the receiver has been dropped.
+    pub fn send(&mut self, item: T) -> Result<(), ()> {
+        match *self {
+            StreamSender::Unbounded(ref sender) => sender.send(item).map_err(|_| ()),
+            StreamSender::Bounded(ref sender) => sender.send(item).map_err(|_| ()),
+            StreamSender::Latest(_, ref mut pending) => {
+                *pending = Some(item);
+                self.flush()
+            }
+        }
+    }
+
+    /// Delivers a result that was held back, if there's room for it now.
+    /// Returns `Err` if the receiver has been dropped.
+    pub fn flush(&mut self) -> Result<(), ()> {
+        if let StreamSender::Latest(ref sender, ref mut pending) = *self {
+            if let Some(item) = pending.take() {
+                match sender.try_send(item) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(item)) => *pending = Some(item),
+                    Err(TrySendError::Disconnected(_)) => return Err(()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_keeps_newest() {
+        let (mut sender, receiver) = open(StreamBuffer::Latest);
+        for i in 0..5 {
+            sender.send(i).unwrap();
+        }
+        assert_eq!(receiver.try_recv(), Ok(0));
+        sender.flush().unwrap();
+        assert_eq!(receiver.try_recv(), Ok(4));
+        assert!(receiver.try_recv().is_err());
+        drop(receiver);
+        assert!(sender.send(5).is_err());
+    }
+}
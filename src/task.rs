@@ -1,91 +1,155 @@
-#[derive(Clone, Debug, PartialEq, Eq)]
-/// Token indicating which abstract bee should do work next.
-pub enum Task {
-    Worker(usize),
-    Observer(usize), // The index is used for cycling, disregarded at execution.
-}
+use std::time::Instant;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use colony::TaskSchedule;
+pub use colony::Task;
+use stop::{StopCondition, RunState, MaxRounds};
 
 /// Task iterator.
 pub struct TaskGenerator {
-    workers: usize,
-    observers: usize,
-    next: Task,
-    max_rounds: Option<usize>,
+    schedule: Box<dyn TaskSchedule>,
+    condition: Option<Box<dyn StopCondition>>,
     stopped: bool,
 
-    /// Current round of execution. Starts at 0, then increments after yielding
-    /// the last task for each successive round. Since the algorithm staggers
-    /// the rounds, this will always be a relatively fuzzy measurement.
-    pub round: usize,
+    started: Instant,
+    best_fitness: f64,
+    evaluations: Option<(Arc<AtomicUsize>, usize)>,
 }
 
 impl TaskGenerator {
-    pub fn new(workers: usize, observers: usize) -> TaskGenerator {
-        assert!(workers > 0);
+    /// Hands out the tasks of `schedule`.
+    pub fn new(schedule: Box<dyn TaskSchedule>) -> TaskGenerator {
         TaskGenerator {
-            workers: workers,
-            observers: observers,
-            round: 0,
-            max_rounds: None,
-            next: Task::Worker(0),
+            schedule,
+            condition: None,
             stopped: false,
+            started: Instant::now(),
+            best_fitness: f64::NEG_INFINITY,
+            evaluations: None,
         }
     }
 
-    pub fn max_rounds(mut self, max_rounds: usize) -> TaskGenerator {
-        self.max_rounds = Some(max_rounds);
+    /// Current round of execution. Starts at 0, then increments after
+    /// yielding the last task for each successive round. Since the algorithm
+    /// staggers the rounds, this will always be a relatively fuzzy
+    /// measurement.
+    pub fn round(&self) -> usize {
+        self.schedule.round()
+    }
+
+    /// Returns the number of tasks in each round.
+    pub fn tasks_per_round(&self) -> usize {
+        self.schedule.tasks_per_round()
+    }
+
+    /// Returns whether the schedule hands out scout tasks.
+    pub fn has_scouts(&self) -> bool {
+        self.schedule.has_scouts()
+    }
+
+    pub fn max_rounds(self, max_rounds: usize) -> TaskGenerator {
+        self.until(MaxRounds(max_rounds))
+    }
+
+    /// Stops yielding tasks once `condition` is met.
+    ///
+    /// If a condition has already been set, the generator stops as soon as
+    /// either one is met.
+    pub fn until<C: StopCondition + 'static>(mut self, condition: C) -> TaskGenerator {
+        self.condition = Some(match self.condition.take() {
+            Some(existing) => Box::new(existing.or(condition)),
+            None => Box::new(condition),
+        });
         self
     }
 
+    /// Counts fitness evaluations as they are recorded in `counter`.
+    pub fn track_evaluations(mut self, counter: Arc<AtomicUsize>) -> TaskGenerator {
+        let start = counter.load(Ordering::Relaxed);
+        self.evaluations = Some((counter, start));
+        self
+    }
+
+    /// Records the best fitness found so far, for the stop condition.
+    pub fn improve(&mut self, fitness: f64) {
+        if fitness > self.best_fitness {
+            self.best_fitness = fitness;
+        }
+    }
+
+    /// Claims all of the remaining tasks in the current round.
+    ///
+    /// The stop condition is only checked once, before the first task.
+    pub fn next_round(&mut self) -> Vec<Task> {
+        let round = self.round();
+        let mut tasks = Vec::new();
+        if let Some(task) = self.next() {
+            tasks.push(task);
+            while self.round() == round {
+                tasks.push(self.schedule.next_task());
+            }
+        }
+        tasks
+    }
+
+    /// Changes the number of observers. If the current round has already
+    /// reached its observers, this applies from the next task.
+    pub fn set_observers(&mut self, observers: usize) {
+        self.schedule.set_observers(observers);
+    }
+
     pub fn stop(&mut self) {
         self.stopped = true;
     }
+
+    /// Summarizes the progress of the run so far.
+    pub fn state(&self) -> RunState {
+        RunState {
+            round: self.round(),
+            evaluations: self.evaluations
+                             .as_ref()
+                             .map_or(0, |&(ref counter, start)| {
+                                 counter.load(Ordering::Relaxed) - start
+                             }),
+            elapsed: self.started.elapsed(),
+            best_fitness: self.best_fitness,
+        }
+    }
 }
 
 impl Iterator for TaskGenerator {
     type Item = Task;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if !self.stopped && self.condition.is_some() {
+            let state = self.state();
+            if let Some(condition) = self.condition.as_mut() {
+                self.stopped = condition.should_stop(&state);
+            }
+        }
+
         if self.stopped {
             None
         } else {
-            // The task in the TaskGenerator's state is always the one to be
-            // popped from the queue.
-            let current = self.next.clone();
-
-            self.next = match self.next {
-                Task::Worker(n) if n == self.workers - 1 => {
-                    if self.observers > 0 {
-                        Task::Observer(0)
-                    } else {
-                        Task::Worker(0)
-                    }
-                }
-                Task::Worker(n) => Task::Worker(n + 1),
-                Task::Observer(n) if n == self.observers - 1 => {
-                    // After this task, we need to start the next round.
-                    self.round += 1;
-                    if let Some(n) = self.max_rounds {
-                        if self.round >= n {
-                            self.stopped = true;
-                        }
-                    }
-                    Task::Worker(0)
-                }
-                Task::Observer(n) => Task::Observer(n + 1),
-            };
-            Some(current)
+            Some(self.schedule.next_task())
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::TaskGenerator;
+    use colony::{CycleSpec, TaskCycle};
+
+    fn cycle(workers: usize, observers: usize, spec: CycleSpec) -> TaskGenerator {
+        TaskGenerator::new(Box::new(TaskCycle::with_spec(workers, observers, spec)))
+    }
 
     #[test]
     fn basic_cycle() {
         use super::*;
-        let tg = TaskGenerator::new(3, 2).max_rounds(2);
+        let tg = cycle(3, 2, CycleSpec::default()).max_rounds(2);
         let gathered: Vec<_> = tg.collect();
         let expected = [Task::Worker(0),
                         Task::Worker(1),
@@ -100,4 +164,32 @@ mod tests {
         assert_eq!(gathered.len(), expected.len());
         assert!(gathered.iter().zip(expected.iter()).all(|(x, y)| *x == *y));
     }
+
+    #[test]
+    fn no_observers() {
+        use super::*;
+        let tg = cycle(2, 0, CycleSpec::default()).max_rounds(2);
+        assert_eq!(tg.collect::<Vec<_>>(),
+                   vec![Task::Worker(0), Task::Worker(1), Task::Worker(0), Task::Worker(1)]);
+    }
+
+    #[test]
+    fn weighted_cycle() {
+        use super::*;
+        use colony::Phase;
+        let spec = CycleSpec::new(vec![Phase::Workers, Phase::Observers, Phase::Observers, Phase::Scouts]);
+        let mut tg = cycle(2, 1, spec).max_rounds(2);
+        assert_eq!(tg.next_round(),
+                   vec![Task::Worker(0), Task::Worker(1), Task::Observer(0), Task::Observer(0), Task::Scout(0), Task::Scout(1)]);
+        assert_eq!(tg.round(), 1);
+        assert_eq!(tg.count(), 6);
+    }
+
+    #[test]
+    fn expired_time_limit() {
+        use std::time::Duration;
+        use stop::TimeLimit;
+        let mut tg = cycle(3, 2, CycleSpec::default()).until(TimeLimit(Duration::from_secs(0)));
+        assert_eq!(tg.next(), None);
+    }
 }
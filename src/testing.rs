@@ -0,0 +1,235 @@
+//! Statistical checks on the algorithm, for regression tests.
+//!
+//! A single run of the hive says little about whether a change to the
+//! algorithm helped or hurt, since each run follows its own random path.
+//! [`trials`](fn.trials.html) runs several independent hives on the same
+//! problem, each with the same budget of fitness evaluations, and gathers
+//! their best candidates in [`Trials`](struct.Trials.html), which can
+//! assert that the typical run, or enough of the runs, reach a target.
+//!
+//! The benchmark functions here are standard minimization problems, each
+//! wrapped in a [`BoundedVecContext`](../vector/struct.BoundedVecContext.html)
+//! over its usual domain. Their global minimum is 0, so they are meant for
+//! hives built with
+//! [`Objective::Minimize`](../enum.Objective.html#variant.Minimize).
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{HiveBuilder, Objective};
+//! use abc::testing::{sphere, trials};
+//!
+//! let trials = trials(7, 2_000, |trial| {
+//!     HiveBuilder::new(sphere(4), 10)
+//!         .set_objective(Objective::Minimize)
+//!         .set_threads(1)
+//!         .set_rng_seed(trial as u64)
+//! }).unwrap();
+//! trials.assert_median_raw_at_most(0.01);
+//! assert!(trials.success_rate(|best| best.raw_fitness < 1.0) > 0.9);
+//! # }
+//! ```
+
+use std::f64::consts::PI;
+
+use candidate::Candidate;
+use context::Context;
+use hive::HiveBuilder;
+use result::Result as AbcResult;
+use vector::BoundedVecContext;
+use {Fitness, TotalFitness};
+
+/// A benchmark problem over real vectors.
+pub type Benchmark = BoundedVecContext<fn(&[f64]) -> f64>;
+
+fn sphere_cost(x: &[f64]) -> f64 {
+    x.iter().map(|xi| xi * xi).sum()
+}
+
+fn rastrigin_cost(x: &[f64]) -> f64 {
+    10.0 * x.len() as f64 + x.iter().map(|xi| xi * xi - 10.0 * (2.0 * PI * xi).cos()).sum::<f64>()
+}
+
+fn rosenbrock_cost(x: &[f64]) -> f64 {
+    x.windows(2).map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2)).sum()
+}
+
+fn ackley_cost(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let squares = x.iter().map(|xi| xi * xi).sum::<f64>() / n;
+    let cosines = x.iter().map(|xi| (2.0 * PI * xi).cos()).sum::<f64>() / n;
+    // Rounding can leave the minimum a hair below 0.
+    (-20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + ::std::f64::consts::E).max(0.0)
+}
+
+/// The sphere function, Σ *x*<sub>*i*</sub>², on [−5.12, 5.12].
+pub fn sphere(dimensions: usize) -> Benchmark {
+    BoundedVecContext::uniform(dimensions, -5.12, 5.12, sphere_cost as fn(&[f64]) -> f64)
+}
+
+/// The Rastrigin function, which has a local minimum at every integer
+/// point, on [−5.12, 5.12].
+pub fn rastrigin(dimensions: usize) -> Benchmark {
+    BoundedVecContext::uniform(dimensions, -5.12, 5.12, rastrigin_cost as fn(&[f64]) -> f64)
+}
+
+/// The Rosenbrock function, whose minimum at (1, …, 1) lies along a narrow,
+/// curved valley, on [−2.048, 2.048].
+pub fn rosenbrock(dimensions: usize) -> Benchmark {
+    BoundedVecContext::uniform(dimensions, -2.048, 2.048, rosenbrock_cost as fn(&[f64]) -> f64)
+}
+
+/// The Ackley function, which is nearly flat away from its minimum, on
+/// [−32.768, 32.768].
+pub fn ackley(dimensions: usize) -> Benchmark {
+    BoundedVecContext::uniform(dimensions, -32.768, 32.768, ackley_cost as fn(&[f64]) -> f64)
+}
+
+/// The best candidates of several independent runs.
+///
+/// Created by [`trials`](fn.trials.html).
+pub struct Trials<S: Clone + Send + Sync + 'static, F = f64> {
+    /// The best candidate of each run, in the order the runs were made.
+    pub best: Vec<Candidate<S, F>>,
+
+    /// The number of fitness evaluations each run made, including those for
+    /// its initial population. A run may overrun its budget by a few
+    /// evaluations per thread.
+    pub evaluations: Vec<usize>,
+}
+
+/// Runs `count` hives, one after another, each until it has made
+/// `evaluations` fitness evaluations, and gathers their best candidates.
+///
+/// `build` is given the number of each trial, counting from 0, so that it
+/// can seed each hive differently. The evaluation budget replaces any the
+/// builder has set.
+///
+/// # Panics
+///
+/// Panics if `count` is 0.
+pub fn trials<Ctx, F>(count: usize, evaluations: usize, build: F) -> AbcResult<Trials<Ctx::Solution, Ctx::Fitness>>
+    where Ctx: Context,
+          F: Fn(usize) -> HiveBuilder<Ctx>
+{
+    if count == 0 {
+        panic!("There must be at least one trial.");
+    }
+    let mut trials = Trials {
+        best: Vec::with_capacity(count),
+        evaluations: Vec::with_capacity(count),
+    };
+    for trial in 0..count {
+        let hive = build(trial).set_evaluation_budget(evaluations).build()?;
+        hive.run_forever()?;
+        trials.best.push((*hive.best_arc()?).clone());
+        trials.evaluations.push(hive.evaluations());
+    }
+    Ok(trials)
+}
+
+impl<S: Clone + Send + Sync + 'static, F: Fitness> Trials<S, F> {
+    /// Returns the median of the runs' best fitnesses.
+    pub fn median_fitness(&self) -> f64 {
+        self.quantile_fitness(0.5)
+    }
+
+    /// Returns the median of the runs' best raw fitnesses, which are the
+    /// costs when minimizing, as `f64`s.
+    pub fn median_raw_fitness(&self) -> f64 {
+        median(self.best.iter().map(|c| c.raw_fitness.to_f64()).collect())
+    }
+
+    /// Returns the best fitness that a fraction `q` of the runs fell short
+    /// of, taking the nearest run. A `q` of 0 gives the worst run, and 1
+    /// the best.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not in [0, 1].
+    pub fn quantile_fitness(&self, q: f64) -> f64 {
+        if !(0.0..=1.0).contains(&q) {
+            panic!("Quantiles must be in [0, 1].");
+        }
+        let mut fitnesses = self.best.iter().map(|c| TotalFitness(c.fitness)).collect::<Vec<_>>();
+        fitnesses.sort();
+        let rank = (q * (fitnesses.len() - 1) as f64).round() as usize;
+        fitnesses[rank].0
+    }
+
+    /// Returns the fraction of runs whose best candidate satisfies `success`.
+    pub fn success_rate<P>(&self, success: P) -> f64
+        where P: Fn(&Candidate<S, F>) -> bool
+    {
+        self.best.iter().filter(|c| success(c)).count() as f64 / self.best.len() as f64
+    }
+
+    /// Panics unless the median best fitness is at least `fitness`.
+    pub fn assert_median_at_least(&self, fitness: f64) {
+        let median = self.median_fitness();
+        assert!(median >= fitness,
+                "median best fitness {} fell short of {} over {} trials: {:?}",
+                median,
+                fitness,
+                self.best.len(),
+                self.best.iter().map(|c| c.fitness).collect::<Vec<_>>());
+    }
+
+    /// Panics unless the median best raw fitness is at most `cost`, for
+    /// hives that minimize.
+    pub fn assert_median_raw_at_most(&self, cost: f64) {
+        let median = self.median_raw_fitness();
+        assert!(median <= cost,
+                "median best cost {} exceeded {} over {} trials: {:?}",
+                median,
+                cost,
+                self.best.len(),
+                self.best.iter().map(|c| c.raw_fitness.to_f64()).collect::<Vec<_>>());
+    }
+}
+
+/// Finds the median of some values, averaging the middle two of an even
+/// number. NaN counts as the lowest value.
+fn median(values: Vec<f64>) -> f64 {
+    let mut values = values.into_iter().map(TotalFitness).collect::<Vec<_>>();
+    values.sort();
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1].0 + values[middle].0) / 2.0
+    } else {
+        values[middle].0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objective::Objective;
+
+    #[test]
+    fn benchmarks_vanish_at_their_minima() {
+        assert_eq!(sphere_cost(&[0.0; 5]), 0.0);
+        assert_eq!(rastrigin_cost(&[0.0; 5]), 0.0);
+        assert_eq!(rosenbrock_cost(&[1.0; 5]), 0.0);
+        assert!(ackley_cost(&[0.0; 5]) < 1e-12);
+    }
+
+    #[test]
+    fn more_evaluations_do_better() {
+        let run = |evaluations: usize| {
+            trials(9, evaluations, |trial| {
+                HiveBuilder::new(rastrigin(3), 10)
+                    .set_objective(Objective::Minimize)
+                    .set_threads(2)
+                    .set_rng_seed(trial as u64)
+                    .set_deterministic(true)
+            }).unwrap()
+        };
+        let (short, long) = (run(200), run(4_000));
+        assert!(long.evaluations.iter().all(|&e| e >= 4_000));
+        assert!(long.median_raw_fitness() < short.median_raw_fitness());
+        long.assert_median_raw_at_most(2.0);
+        assert!(long.quantile_fitness(1.0) >= long.median_fitness());
+    }
+}
@@ -0,0 +1,53 @@
+//! Instrumentation with `tracing`, which compiles away without the feature.
+//!
+//! The hive reports through `trace_span!` and `trace_event!`, which take a
+//! level name followed by the arguments of `tracing::span!` or
+//! `tracing::event!`. Without the `tracing` feature, their arguments are
+//! never evaluated, and spans are a stand-in [`Span`](struct.Span.html) that
+//! does nothing.
+
+#[cfg(feature = "tracing")]
+pub use tracing::Span;
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)+) => { ::tracing::span!(::tracing::Level::$level, $($arg)+) };
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => { ::tracing::event!(::tracing::Level::$level, $($arg)+) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)+) => { $crate::trace::Span };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+/// Stands in for `tracing::Span` when the feature is off.
+#[cfg(not(feature = "tracing"))]
+#[derive(Clone, Debug)]
+pub struct Span;
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    /// Returns a span that does nothing.
+    pub fn current() -> Span {
+        Span
+    }
+
+    /// Does nothing, since there is no span to enter.
+    pub fn entered(self) -> Span {
+        self
+    }
+
+    /// Calls `f`.
+    pub fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        f()
+    }
+}
@@ -0,0 +1,193 @@
+//! Self-tuning of the hive's parameters while it runs, as in meta-ABC.
+//!
+//! The best number of observers, the patience of each candidate, and the
+//! selection pressure all depend on the problem, and on how far the search
+//! has got. A [`ParameterController`](trait.ParameterController.html) looks
+//! at each round's [`RoundStats`](../stats/struct.RoundStats.html) as it
+//! ends, and adjusts the [`Parameters`](struct.Parameters.html) for the
+//! rounds that follow. It is set with
+//! [`HiveBuilder::set_parameter_controller`](../struct.HiveBuilder.html#method.set_parameter_controller).
+//!
+//! [`Adaptive`](struct.Adaptive.html) is a simple controller, which reacts
+//! to the rate of scouting and of improvement. Any
+//! `FnMut(&RoundStats, &mut Parameters)` is also a controller.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::HiveBuilder;
+//! use abc::tuning::Adaptive;
+//! use abc::vector::BoundedVecContext;
+//!
+//! let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| 60.0 - x[0] * x[0] - x[1] * x[1]);
+//! let hive = HiveBuilder::new(context, 10)
+//!     .set_parameter_controller(Adaptive::default())
+//!     .build()
+//!     .unwrap();
+//! hive.run_for_rounds(50).unwrap();
+//! let parameters = hive.parameters();
+//! assert!(parameters.retries >= 1 && parameters.observers >= 1);
+//! # }
+//! ```
+
+use stats::RoundStats;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The parameters that a [`ParameterController`](trait.ParameterController.html)
+/// may change between rounds.
+pub struct Parameters {
+    /// Number of working candidates. This is for reference only: changes to
+    /// it are ignored.
+    pub workers: usize,
+
+    /// Number of observer bees in each round.
+    pub observers: usize,
+
+    /// Retries for each candidate entering the field, under
+    /// [`LimitStrategy::Fixed`](../enum.LimitStrategy.html#variant.Fixed).
+    /// Other limit strategies ignore this.
+    pub retries: usize,
+
+    /// Exponent applied to the scaled weights before observers choose a
+    /// candidate. Above 1, observers favour fitter candidates more strongly;
+    /// below 1, their choices are more even. This starts at 1, which leaves
+    /// the weights alone. Tournament selection ignores it.
+    pub pressure: f64,
+}
+
+/// Adjusts the hive's parameters as each round ends.
+pub trait ParameterController: Send {
+    /// Updates `parameters` in light of the round described by `stats`.
+    ///
+    /// The new values apply from the next round. Candidates already in the
+    /// field keep the retries they were given. A pressure that is negative
+    /// or not finite is ignored.
+    fn adjust(&mut self, stats: &RoundStats, parameters: &mut Parameters);
+}
+
+impl<F> ParameterController for F
+    where F: FnMut(&RoundStats, &mut Parameters) + Send
+{
+    fn adjust(&mut self, stats: &RoundStats, parameters: &mut Parameters) {
+        self(stats, parameters)
+    }
+}
+
+/// A controller that reacts to scouting and to the rate of improvement.
+///
+/// After each round:
+///
+/// * If more than `scout_rate` of the workers were scouted, candidates are
+///   being given up too soon, so retries grow by a fifth (and at least one).
+///   If none were, retries shrink by a tenth (and at least one), down to
+///   `min_retries`.
+/// * If more than `success_rate` of the explorations improved on their
+///   candidate, exploiting the fittest candidates is paying off, so an
+///   observer is added (up to twice the workers) and the pressure rises by
+///   `step`. Otherwise, an observer is removed (down to half the workers)
+///   and the pressure falls by `step`. The pressure stays between
+///   `min_pressure` and `max_pressure`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Adaptive {
+    /// Fraction of workers scouted in a round above which retries grow.
+    pub scout_rate: f64,
+
+    /// Fraction of explorations improving above which observers and
+    /// pressure grow.
+    pub success_rate: f64,
+
+    /// Factor by which the pressure rises or falls.
+    pub step: f64,
+
+    /// Fewest retries to allow.
+    pub min_retries: usize,
+
+    /// Lowest pressure to allow.
+    pub min_pressure: f64,
+
+    /// Highest pressure to allow.
+    pub max_pressure: f64,
+}
+
+impl Default for Adaptive {
+    /// Grows retries when over a tenth of the workers are scouted in a
+    /// round, and pressure when over a fifth of explorations succeed, in
+    /// steps of 10%.
+    fn default() -> Adaptive {
+        Adaptive {
+            scout_rate: 0.1,
+            success_rate: 0.2,
+            step: 1.1,
+            min_retries: 1,
+            min_pressure: 0.25,
+            max_pressure: 4.0,
+        }
+    }
+}
+
+impl ParameterController for Adaptive {
+    fn adjust(&mut self, stats: &RoundStats, parameters: &mut Parameters) {
+        let workers = parameters.workers.max(1);
+        if stats.scouts as f64 > self.scout_rate * workers as f64 {
+            parameters.retries += (parameters.retries / 5).max(1);
+        } else if stats.scouts == 0 {
+            let shrink = (parameters.retries / 10).max(1);
+            parameters.retries = parameters.retries.saturating_sub(shrink).max(self.min_retries);
+        }
+
+        if stats.explorations > 0 {
+            let success = stats.improvements as f64 / stats.explorations as f64;
+            if success > self.success_rate {
+                parameters.observers = (parameters.observers + 1).min(2 * workers);
+                parameters.pressure *= self.step;
+            } else {
+                parameters.observers = parameters.observers.saturating_sub(1).max(workers.div_ceil(2));
+                parameters.pressure /= self.step;
+            }
+            parameters.pressure = parameters.pressure.max(self.min_pressure).min(self.max_pressure);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> Parameters {
+        Parameters {
+            workers: 10,
+            observers: 10,
+            retries: 10,
+            pressure: 1.0,
+        }
+    }
+
+    fn stats(scouts: usize, explorations: usize, improvements: usize) -> RoundStats {
+        let mut stats = RoundStats::new(1, &[1.0, 2.0], scouts, 20);
+        stats.explorations = explorations;
+        stats.improvements = improvements;
+        stats
+    }
+
+    #[test]
+    fn adaptive_reacts_to_scouts_and_success() {
+        let mut controller = Adaptive::default();
+
+        let mut frequent_scouts = parameters();
+        controller.adjust(&stats(5, 20, 10), &mut frequent_scouts);
+        assert_eq!(frequent_scouts.retries, 12);
+        assert_eq!(frequent_scouts.observers, 11);
+        assert!(frequent_scouts.pressure > 1.0);
+
+        let mut stalled = parameters();
+        for _ in 0..100 {
+            controller.adjust(&stats(0, 20, 0), &mut stalled);
+        }
+        assert_eq!(stalled.retries, 1);
+        assert_eq!(stalled.observers, 5);
+        assert_eq!(stalled.pressure, 0.25);
+    }
+}
@@ -0,0 +1,243 @@
+//! Building blocks for writing `explore` on slices of real numbers.
+//!
+//! [`choose_other_candidate`](fn.choose_other_candidate.html) picks the
+//! partner that an exploration moves relative to, for solutions of any type.
+//!
+//! Most explore operations for real-valued solutions are variations on
+//! Karaboga's rule, which moves a dimension *j* of the current solution
+//! relative to the same dimension of a partner *k*:
+//!
+//! <center>*v*<sub>*j*</sub> = *x*<sub>*j*</sub>
+//! + φ(*x*<sub>*j*</sub> − *x*<sub>*k*,*j*</sub>)</center>
+//!
+//! where φ is drawn uniformly from [−*scale*, *scale*]. The variations differ
+//! in which dimensions are moved: [`perturb_one`](fn.perturb_one.html) moves
+//! one at random, as in the original algorithm,
+//! [`perturb_all`](fn.perturb_all.html) moves every dimension, and
+//! [`perturb_with_rate`](fn.perturb_with_rate.html) moves each with some
+//! probability, as in modified ABC. A [`BoundPolicy`](enum.BoundPolicy.html)
+//! then brings the result back within bounds.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; extern crate rand; fn main() {
+//! use rand::thread_rng;
+//! use abc::Candidate;
+//! use abc::util::{choose_other_candidate, perturb_one, BoundPolicy};
+//!
+//! let field = vec![Candidate::new(vec![0.5, 0.5], 1.0), Candidate::new(vec![-1.0, 2.0], 1.0)];
+//! let mut rng = thread_rng();
+//! let partner = choose_other_candidate(&field, 0, &mut rng);
+//! assert_eq!(partner, 1);
+//!
+//! let mut variant = field[0].solution.clone();
+//! perturb_one(&mut variant, &field[partner].solution, 1.0, &mut rng);
+//! BoundPolicy::Reflect.apply(&mut variant, &[0.0, 0.0], &[1.0, 1.0], &mut rng);
+//! assert!(variant.iter().all(|&x| x >= 0.0 && x <= 1.0));
+//! # }
+//! ```
+
+extern crate rand;
+
+use self::rand::Rng;
+
+/// Chooses the index of a partner for the candidate at `index`, uniformly
+/// from the rest of the field.
+///
+/// The partner is never the candidate itself, unless it is alone in the
+/// field, in which case `index` is returned. The field may hold candidates,
+/// or the reference-counted candidates that the hive passes to
+/// [`Context::explore`](../trait.Context.html#tymethod.explore).
+///
+/// # Panics
+///
+/// Panics if `index` is not in the field.
+pub fn choose_other_candidate<C, R: Rng>(field: &[C], index: usize, rng: &mut R) -> usize {
+    if index >= field.len() {
+        panic!("Candidate index {} is outside a field of {}.", index, field.len());
+    }
+    if field.len() == 1 {
+        return index;
+    }
+    let partner = rng.gen_range(0, field.len() - 1);
+    if partner >= index { partner + 1 } else { partner }
+}
+
+/// Moves dimension `j` of `solution` relative to `partner`.
+fn perturb<R: Rng>(solution: &mut [f64], partner: &[f64], j: usize, scale: f64, rng: &mut R) {
+    let phi = rng.gen_range(-scale, scale);
+    solution[j] += phi * (solution[j] - partner[j]);
+}
+
+/// Checks the arguments shared by the perturbation operators.
+fn check(solution: &[f64], partner: &[f64], scale: f64) {
+    if solution.is_empty() || solution.len() != partner.len() {
+        panic!("Solution and partner must be non-empty and of equal length.");
+    }
+    if !(scale > 0.0 && scale.is_finite()) {
+        panic!("Perturbation scale must be positive and finite.");
+    }
+}
+
+/// Moves one dimension of `solution`, chosen at random, relative to
+/// `partner`, and returns its index.
+///
+/// # Panics
+///
+/// Panics if `solution` is empty or a different length from `partner`, or
+/// if `scale` is not positive and finite.
+pub fn perturb_one<R: Rng>(solution: &mut [f64], partner: &[f64], scale: f64, rng: &mut R) -> usize {
+    check(solution, partner, scale);
+    let j = rng.gen_range(0, solution.len());
+    perturb(solution, partner, j, scale, rng);
+    j
+}
+
+/// Moves every dimension of `solution` relative to `partner`, each with its
+/// own φ.
+///
+/// # Panics
+///
+/// Panics as [`perturb_one`](fn.perturb_one.html) does.
+pub fn perturb_all<R: Rng>(solution: &mut [f64], partner: &[f64], scale: f64, rng: &mut R) {
+    check(solution, partner, scale);
+    for j in 0..solution.len() {
+        perturb(solution, partner, j, scale, rng);
+    }
+}
+
+/// Moves each dimension of `solution` relative to `partner` with probability
+/// `rate`, and returns the number moved.
+///
+/// If no dimension happens to be chosen, one is moved at random, so the
+/// solution always changes (unless it matches `partner`).
+///
+/// # Panics
+///
+/// Panics as [`perturb_one`](fn.perturb_one.html) does, or if `rate` is not
+/// in [0, 1].
+pub fn perturb_with_rate<R: Rng>(solution: &mut [f64],
+                                 partner: &[f64],
+                                 rate: f64,
+                                 scale: f64,
+                                 rng: &mut R)
+                                 -> usize {
+    check(solution, partner, scale);
+    if !(0.0..=1.0).contains(&rate) {
+        panic!("Perturbation rate must be in [0, 1].");
+    }
+    let mut moved = 0;
+    for j in 0..solution.len() {
+        if rng.next_f64() < rate {
+            perturb(solution, partner, j, scale, rng);
+            moved += 1;
+        }
+    }
+    if moved == 0 {
+        perturb_one(solution, partner, scale, rng);
+        moved = 1;
+    }
+    moved
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// How to bring a dimension that has left its bounds back within them.
+pub enum BoundPolicy {
+    /// Move it to the nearest bound. This is simple, but piles candidates up
+    /// on the edges of the space.
+    Clamp,
+
+    /// Reflect it back from the bound it crossed, by as far as it overshot.
+    /// If it overshot by more than the width of the bounds, it is clamped.
+    Reflect,
+
+    /// Draw it again uniformly from the bounds.
+    Resample,
+}
+
+impl BoundPolicy {
+    /// Brings each dimension of `solution` within `lower` and `upper`.
+    /// Dimensions already within bounds are left alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds are shorter than `solution`.
+    pub fn apply<R: Rng>(&self, solution: &mut [f64], lower: &[f64], upper: &[f64], rng: &mut R) {
+        if lower.len() < solution.len() || upper.len() < solution.len() {
+            panic!("Bounds must cover every dimension of the solution.");
+        }
+        for (j, x) in solution.iter_mut().enumerate() {
+            let (l, u) = (lower[j], upper[j]);
+            if *x >= l && *x <= u {
+                continue;
+            }
+            *x = match *self {
+                BoundPolicy::Clamp => x.max(l).min(u),
+                BoundPolicy::Reflect => {
+                    let reflected = if *x < l { 2.0 * l - *x } else { 2.0 * u - *x };
+                    reflected.max(l).min(u)
+                }
+                BoundPolicy::Resample => l + rng.next_f64() * (u - l),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use self::rand::thread_rng;
+    use candidate::Candidate;
+
+    #[test]
+    fn chooses_other_candidates_uniformly() {
+        let mut rng = thread_rng();
+        let field = (0..4).map(|i| Candidate::new(i, 1.0)).collect::<Vec<_>>();
+        let mut counts = [0; 4];
+        for _ in 0..3000 {
+            counts[choose_other_candidate(&field, 2, &mut rng)] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        assert!(counts.iter().enumerate().all(|(i, &n)| i == 2 || (800..1200).contains(&n)));
+        assert_eq!(choose_other_candidate(&field[..1], 0, &mut rng), 0);
+    }
+
+    #[test]
+    fn perturbs_chosen_dimensions() {
+        let mut rng = thread_rng();
+        let partner = [1.0; 4];
+
+        let mut one = [0.0; 4];
+        let j = perturb_one(&mut one, &partner, 1.0, &mut rng);
+        assert!((0..4).all(|i| (one[i] != 0.0) == (i == j)));
+
+        let mut all = [0.0; 4];
+        perturb_all(&mut all, &partner, 1.0, &mut rng);
+        assert!(all.iter().all(|&x| x != 0.0 && x.abs() <= 1.0));
+
+        let mut none = [0.0; 4];
+        assert_eq!(perturb_with_rate(&mut none, &partner, 0.0, 1.0, &mut rng), 1);
+        assert_eq!(none.iter().filter(|&&x| x != 0.0).count(), 1);
+    }
+
+    #[test]
+    fn bound_policies() {
+        let mut rng = thread_rng();
+        let (lower, upper) = ([0.0; 3], [1.0; 3]);
+
+        let mut clamped = [-0.25, 0.5, 1.25];
+        BoundPolicy::Clamp.apply(&mut clamped, &lower, &upper, &mut rng);
+        assert_eq!(clamped, [0.0, 0.5, 1.0]);
+
+        let mut reflected = [-0.25, 0.5, 3.0];
+        BoundPolicy::Reflect.apply(&mut reflected, &lower, &upper, &mut rng);
+        assert_eq!(reflected, [0.25, 0.5, 0.0]);
+
+        let mut resampled = [-0.25, 0.5, 1.25];
+        BoundPolicy::Resample.apply(&mut resampled, &lower, &upper, &mut rng);
+        assert_eq!(resampled[1], 0.5);
+        assert!(resampled.iter().all(|x| (0.0..=1.0).contains(x)));
+    }
+}
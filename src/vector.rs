@@ -0,0 +1,406 @@
+//! Ready-made context for optimizing vectors of real numbers.
+//!
+//! Most applications of the ABC algorithm search a box-bounded region of
+//! real-valued space. For those problems, [`BoundedVecContext`](struct.BoundedVecContext.html)
+//! supplies the canonical `make` and `explore` operations, so that only the
+//! fitness function needs to be written.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate abc; fn main() {
+//! use abc::{HiveBuilder, Objective};
+//! use abc::vector::BoundedVecContext;
+//!
+//! // Minimize x² + y² within [-5, 5]².
+//! let context = BoundedVecContext::uniform(2, -5.0, 5.0, |x: &[f64]| {
+//!     x.iter().map(|xi| xi * xi).sum::<f64>()
+//! });
+//! let hive = HiveBuilder::new(context, 10).set_objective(Objective::Minimize).build().unwrap();
+//! let best = hive.run_for_rounds(100).unwrap();
+//! assert!(best.solution.iter().all(|xi| xi.abs() <= 5.0));
+//! # }
+//! ```
+
+extern crate rand;
+
+use std::sync::Arc;
+
+use self::rand::{thread_rng, Rng};
+
+use candidate::Candidate;
+use context::Context;
+use init::Bounded;
+use stats::RoundStats;
+use util::{choose_other_candidate, perturb_one, perturb_with_rate};
+
+/// Factor by which the modified ABC scale shrinks, per Rechenberg's rule.
+const SCALE_STEP: f64 = 0.85;
+
+/// Context for searching vectors of `f64` within per-dimension bounds.
+///
+/// New solutions are drawn uniformly from the bounds. Exploration follows
+/// Karaboga's original rule: one dimension *j* of the solution is chosen at
+/// random, along with a different candidate *k* from the field, and
+///
+/// <center>*v*<sub>*j*</sub> = *x*<sub>*j*</sub>
+/// + φ(*x*<sub>*j*</sub> − *x*<sub>*k*,*j*</sub>)</center>
+///
+/// where φ is drawn uniformly from [-1, 1]. The result is clamped to the
+/// bounds. With [`with_modification`](#method.with_modification), it follows
+/// the modified ABC rule instead.
+pub struct BoundedVecContext<F> {
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    fitness: F,
+    modification: Option<Modification>,
+    adaptation: Adaptation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Settings for the exploration of Akay and Karaboga's modified ABC (MABC).
+///
+/// Rather than a single dimension, each dimension *j* is changed with
+/// probability `rate` (the modification rate, MR), and at least one always
+/// is. φ is drawn uniformly from [−`scale`, `scale`] (the scaling factor,
+/// SF), so a smaller scale searches closer to the candidate.
+///
+/// The scale adapts by Rechenberg's 1/5 rule, if
+/// [`BoundedVecContext::adapt`](struct.BoundedVecContext.html#method.adapt)
+/// is called at the end of each round: every `period` rounds, if fewer than
+/// a fifth of the explorations improved on their candidate, the scale is
+/// multiplied by 0.85, and if more did, it is divided by 0.85.
+pub struct Modification {
+    /// Probability of changing each dimension.
+    pub rate: f64,
+
+    /// Largest magnitude of φ.
+    pub scale: f64,
+
+    /// Number of rounds between adjustments of the scale.
+    pub period: usize,
+}
+
+impl Default for Modification {
+    /// A modification rate of 0.4 and a scale of 1, adapted every 10 rounds.
+    fn default() -> Modification {
+        Modification {
+            rate: 0.4,
+            scale: 1.0,
+            period: 10,
+        }
+    }
+}
+
+/// Explorations and improvements since the scale was last adjusted.
+#[derive(Clone, Copy, Debug, Default)]
+struct Adaptation {
+    rounds: usize,
+    explorations: usize,
+    improvements: usize,
+}
+
+impl<F> BoundedVecContext<F>
+    where F: Fn(&[f64]) -> f64 + Send + Sync
+{
+    /// Creates a context with separate bounds for each dimension.
+    ///
+    /// * `lower` - Lowest allowed value in each dimension.
+    /// * `upper` - Highest allowed value in each dimension.
+    /// * `fitness` - Scores a solution (the algorithm will maximize this, and
+    ///   observers expect it to be positive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds are empty, have different lengths, or if any
+    /// lower bound exceeds its upper bound.
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>, fitness: F) -> BoundedVecContext<F> {
+        if lower.is_empty() || lower.len() != upper.len() {
+            panic!("BoundedVecContext bounds must be non-empty and of equal length.");
+        }
+        if lower.iter().zip(upper.iter()).any(|(l, u)| l.is_nan() || u.is_nan() || l > u) {
+            panic!("BoundedVecContext lower bounds must not exceed upper bounds.");
+        }
+        BoundedVecContext {
+            lower,
+            upper,
+            fitness,
+            modification: None,
+            adaptation: Adaptation::default(),
+        }
+    }
+
+    /// Explores with the modified ABC rule, as described by `modification`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not in (0, 1], the scale is not positive and
+    /// finite, or the period is 0.
+    pub fn with_modification(mut self, modification: Modification) -> BoundedVecContext<F> {
+        if !(modification.rate > 0.0 && modification.rate <= 1.0) {
+            panic!("Modification rate must be in (0, 1].");
+        }
+        if !(modification.scale > 0.0 && modification.scale.is_finite()) {
+            panic!("Modification scale must be positive and finite.");
+        }
+        if modification.period == 0 {
+            panic!("Modification period must be at least 1.");
+        }
+        self.modification = Some(modification);
+        self.adaptation = Adaptation::default();
+        self
+    }
+
+    /// Returns the modified ABC settings, with the current scale, if they
+    /// are in use.
+    pub fn modification(&self) -> Option<Modification> {
+        self.modification
+    }
+
+    /// Adapts the modified ABC scale to the round described by `stats`.
+    ///
+    /// This does nothing unless [`with_modification`](#method.with_modification)
+    /// was used. It is meant to be called from a
+    /// [round-end hook](../struct.HiveBuilder.html#method.on_round_end):
+    ///
+    /// ```
+    /// # extern crate abc; fn main() {
+    /// use abc::HiveBuilder;
+    /// use abc::vector::{BoundedVecContext, Modification};
+    ///
+    /// let context = BoundedVecContext::uniform(10, -5.0, 5.0, |x: &[f64]| {
+    ///     300.0 - x.iter().map(|xi| xi * xi).sum::<f64>()
+    /// }).with_modification(Modification::default());
+    /// let hive = HiveBuilder::new(context, 10)
+    ///     .on_round_end(|context, stats| context.adapt(stats))
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(hive.run_for_rounds(100).unwrap().fitness > 295.0);
+    /// # }
+    /// ```
+    pub fn adapt(&mut self, stats: &RoundStats) {
+        let modification = match self.modification {
+            Some(ref mut modification) => modification,
+            None => return,
+        };
+        self.adaptation.rounds += 1;
+        self.adaptation.explorations += stats.explorations;
+        self.adaptation.improvements += stats.improvements;
+        if self.adaptation.rounds < modification.period {
+            return;
+        }
+        let explorations = self.adaptation.explorations;
+        let improvements = self.adaptation.improvements;
+        self.adaptation = Adaptation::default();
+        if explorations == 0 {
+            return;
+        }
+        let success = improvements as f64 / explorations as f64;
+        if success < 0.2 {
+            modification.scale *= SCALE_STEP;
+        } else if success > 0.2 {
+            modification.scale /= SCALE_STEP;
+        }
+        modification.scale = modification.scale.clamp(f64::MIN_POSITIVE, f64::MAX);
+    }
+
+    /// Creates a context with the same bounds in each of `dimensions` dimensions.
+    pub fn uniform(dimensions: usize, min: f64, max: f64, fitness: F) -> BoundedVecContext<F> {
+        BoundedVecContext::new(vec![min; dimensions], vec![max; dimensions], fitness)
+    }
+
+    /// Returns the number of dimensions in each solution.
+    pub fn dimensions(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// Returns the lowest allowed value in each dimension.
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    /// Returns the highest allowed value in each dimension.
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Clamps each dimension of `solution` to the bounds.
+    pub fn clamp(&self, solution: &mut [f64]) {
+        for (j, x) in solution.iter_mut().enumerate() {
+            *x = x.max(self.lower[j]).min(self.upper[j]);
+        }
+    }
+
+    /// Reflects `solution` through the centre of the bounds.
+    ///
+    /// Each dimension *x* becomes *a* + *b* − *x*, where [*a*, *b*] are its
+    /// bounds. This is the opposite point used by
+    /// [`scout::Opposition`](../scout/struct.Opposition.html).
+    pub fn opposite(&self, solution: &[f64]) -> Vec<f64> {
+        solution.iter()
+                .enumerate()
+                .map(|(j, x)| self.lower[j] + self.upper[j] - x)
+                .collect()
+    }
+}
+
+impl<F> Context for BoundedVecContext<F>
+    where F: Fn(&[f64]) -> f64 + Send + Sync
+{
+    type Solution = Vec<f64>;
+    type Fitness = f64;
+
+    fn make(&self) -> Vec<f64> {
+        self.make_with_rng(&mut thread_rng())
+    }
+
+    fn evaluate_fitness(&self, solution: &Vec<f64>) -> f64 {
+        (self.fitness)(solution)
+    }
+
+    fn explore(&self, field: &[Arc<Candidate<Vec<f64>>>], index: usize) -> Vec<f64> {
+        self.explore_with_rng(field, index, &mut thread_rng())
+    }
+
+    /// Euclidean distance.
+    fn distance(&self, a: &Vec<f64>, b: &Vec<f64>) -> Option<f64> {
+        Some(a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt())
+    }
+
+    /// Reflects the solution through the centre of the bounds.
+    fn opposite(&self, solution: &Vec<f64>) -> Vec<f64> {
+        BoundedVecContext::opposite(self, solution)
+    }
+
+    fn make_with_rng<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        self.lower
+            .iter()
+            .zip(self.upper.iter())
+            .map(|(l, u)| l + rng.next_f64() * (u - l))
+            .collect()
+    }
+
+    fn explore_with_rng<R: Rng>(&self,
+                                field: &[Arc<Candidate<Vec<f64>>>],
+                                index: usize,
+                                rng: &mut R)
+                                -> Vec<f64> {
+        let mut variant = field[index].solution.clone();
+
+        let partner = choose_other_candidate(field, index, rng);
+
+        let partner = &field[partner].solution;
+        match self.modification {
+            None => {
+                perturb_one(&mut variant, partner, 1.0, rng);
+            }
+            Some(Modification { rate, scale, .. }) => {
+                perturb_with_rate(&mut variant, partner, rate, scale, rng);
+            }
+        }
+        self.clamp(&mut variant);
+        variant
+    }
+}
+
+impl<F> Bounded for BoundedVecContext<F>
+    where F: Fn(&[f64]) -> f64 + Send + Sync
+{
+    fn bounds(&self) -> (&[f64], &[f64]) {
+        (&self.lower, &self.upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive::HiveBuilder;
+
+    #[test]
+    fn stays_in_bounds() {
+        let context = BoundedVecContext::new(vec![-1.0, 0.0],
+                                             vec![1.0, 10.0],
+                                             |x: &[f64]| 2.0 + x[0] + x[1]);
+        let hive = HiveBuilder::new(context, 6).set_threads(2).build().unwrap();
+        let best = hive.run_for_rounds(200).unwrap();
+        assert!(best.solution[0] >= -1.0 && best.solution[0] <= 1.0);
+        assert!(best.solution[1] >= 0.0 && best.solution[1] <= 10.0);
+        assert!(best.fitness > 11.0);
+    }
+
+    #[test]
+    fn reports_diversity() {
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |x: &[f64]| 3.0 - x[0] - x[1]);
+        let hive = HiveBuilder::new(context, 6).set_threads(2).build().unwrap();
+        hive.run_for_rounds(5).unwrap();
+        for stats in hive.stats().unwrap() {
+            let diversity = stats.diversity.unwrap();
+            assert!(diversity.min_distance <= diversity.mean_distance);
+            assert!(diversity.max_distance <= 8f64.sqrt());
+        }
+    }
+
+    #[test]
+    fn opposition() {
+        // Each initial candidate beat its opposite, so none is below the
+        // diagonal.
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |x: &[f64]| 3.0 + x[0] + x[1]);
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(1)
+            .set_retries(100)
+            .set_opposition(1.0)
+            .build()
+            .unwrap();
+        assert!(hive.population().unwrap().iter().all(|w| w.solution[0] + w.solution[1] >= 0.0));
+
+        // With a jumping rate of 1, each worker also evaluates an opposite.
+        hive.run_for_rounds(5).unwrap();
+        assert_eq!(hive.snapshot().unwrap().evaluations, 8 + 5 * 12);
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        // Every variant is within 10 of a working candidate, so only scouts
+        // are ever evaluated.
+        let context = BoundedVecContext::uniform(2, -1.0, 1.0, |x: &[f64]| 3.0 - x[0] - x[1]);
+        let hive = HiveBuilder::new(context, 4)
+            .set_threads(1)
+            .set_retries(1)
+            .set_duplicate_distance(10.0)
+            .build()
+            .unwrap();
+        hive.run_for_rounds(5).unwrap();
+        assert!(hive.stats().unwrap()[1..].iter().all(|stats| stats.evaluations == stats.scouts));
+    }
+
+    #[test]
+    fn modification() {
+        let modification = Modification {
+            rate: 1.0,
+            scale: 1.0,
+            period: 2,
+        };
+        let mut context = BoundedVecContext::uniform(3, -10.0, 10.0, |x: &[f64]| 1.0 + x[0])
+            .with_modification(modification);
+
+        // With a rate of 1, every dimension changes.
+        let field = vec![Arc::new(Candidate::new(vec![0.0, 0.0, 0.0], 1.0)), Arc::new(Candidate::new(vec![1.0, 1.0, 1.0], 1.0))];
+        let variant = context.explore(&field, 0);
+        assert!(variant.iter().all(|&x| x != 0.0 && x.abs() <= 1.0));
+
+        // The scale adjusts only after each period.
+        let mut stats = RoundStats::new(1, &[1.0], 0, 10);
+        stats.explorations = 10;
+        stats.improvements = 1;
+        context.adapt(&stats);
+        assert_eq!(context.modification().unwrap().scale, 1.0);
+        context.adapt(&stats);
+        assert_eq!(context.modification().unwrap().scale, 0.85);
+
+        stats.improvements = 5;
+        context.adapt(&stats);
+        context.adapt(&stats);
+        assert_eq!(context.modification().unwrap().scale, 1.0);
+    }
+}